@@ -3,6 +3,9 @@ use std::io::BufReader;
 use std::path::PathBuf;
 use xz2::bufread::{XzDecoder, XzEncoder};
 
+/// Must match the pinned version of the `nlprule` dependency.
+const NLPRULE_VERSION: &str = "0.6.4";
+
 fn main() -> std::result::Result<(), Box<(dyn std::error::Error + 'static)>> {
     println!("cargo:rerun-if-changed=build.rs");
     println!("cargo:rerun-if-changed=Cargo.toml");
@@ -67,6 +70,23 @@ fn main() -> std::result::Result<(), Box<(dyn std::error::Error + 'static)>> {
             .validate()?;
     }
 
+    // shown as part of `--version`
+    let mut backends = Vec::with_capacity(2);
+    if env::var_os("CARGO_FEATURE_HUNSPELL").is_some() {
+        // `hunspell-rs` is always built with its `bundled` feature
+        backends.push("hunspell bundled".to_owned());
+    }
+    if env::var_os("CARGO_FEATURE_NLPRULES").is_some() {
+        backends.push(format!("nlprule {}", NLPRULE_VERSION));
+    }
+    if backends.is_empty() {
+        backends.push("no checkers".to_owned());
+    }
+    println!(
+        "cargo:rustc-env=CARGO_SPELLCHECK_BACKENDS={}",
+        backends.join(", ")
+    );
+
     let _ = out;
     Ok(())
 }
@@ -0,0 +1,86 @@
+//! Line based unified diffs, as printed by `fix --dry-run`.
+
+use similar::TextDiff;
+
+/// A unified diff of `old` and `new` with `context` unchanged lines around
+/// each change, empty if both are equal.
+pub(crate) fn unified_diff(
+    old: &str,
+    new: &str,
+    old_name: &str,
+    new_name: &str,
+    context: usize,
+) -> String {
+    if old == new {
+        return String::new();
+    }
+    TextDiff::from_lines(old, new)
+        .unified_diff()
+        .context_radius(context)
+        .header(old_name, new_name)
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn equal() {
+        assert_eq!(unified_diff("a\nb\n", "a\nb\n", "a/x", "b/x", 3), "");
+    }
+
+    #[test]
+    fn hunks() {
+        let old = "1\n2\n3\n4\n5\n6\n7\n8\n9\n10\n11\n12\n";
+        let new = "1\n2\n3\nfour\n5\n6\n7\n8\n9\n10\n11\n";
+        assert_eq!(
+            unified_diff(old, new, "a/x", "b/x", 1),
+            r#"--- a/x
++++ b/x
+@@ -3,3 +3,3 @@
+ 3
+-4
++four
+ 5
+@@ -11,2 +11 @@
+ 11
+-12
+"#
+        );
+        assert_eq!(
+            unified_diff(old, new, "a/x", "b/x", 3),
+            r#"--- a/x
++++ b/x
+@@ -1,7 +1,7 @@
+ 1
+ 2
+ 3
+-4
++four
+ 5
+ 6
+ 7
+@@ -9,4 +9,3 @@
+ 9
+ 10
+ 11
+-12
+"#
+        );
+    }
+
+    #[test]
+    fn no_final_newline() {
+        let diff = unified_diff("/// A tset.", "/// A test.", "a/x", "b/x", 3);
+        assert!(diff.starts_with("--- a/x\n+++ b/x\n"), "{}", diff);
+        assert!(diff.contains("-/// A tset.\n"), "{}", diff);
+        assert!(diff.contains("+/// A test.\n"), "{}", diff);
+        assert!(diff.contains("\\ No newline at end of file"), "{}", diff);
+
+        assert_eq!(
+            unified_diff("", "new\n", "a/x", "b/x", 3),
+            "--- a/x\n+++ b/x\n@@ -0,0 +1 @@\n+new\n"
+        );
+    }
+}
@@ -1,7 +1,7 @@
 //! Covers all user triggered actions (except for signals).
 
 use super::*;
-use crate::checker::Checkers;
+use crate::checker::{replacement_confidence, Checkers};
 use crate::errors::*;
 use crate::reflow::Reflow;
 use log::{debug, trace};
@@ -10,10 +10,14 @@ use fs_err as fs;
 use futures::stream::{self, StreamExt, TryStreamExt};
 use rayon::iter::ParallelIterator;
 
-use std::io::{Read, Write};
-use std::path::PathBuf;
+use std::collections::{BTreeSet, HashSet};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
 
 pub mod bandaid;
+mod diff;
 pub mod interactive;
 
 pub(crate) use bandaid::*;
@@ -42,6 +46,82 @@ impl Finish {
     }
 }
 
+/// Tracks already reported mistakes, identified by the file path, the word and
+/// the span.
+///
+/// Avoids reporting the same mistake twice, i.e. if the same file is covered
+/// by multiple content origins.
+#[derive(Debug, Default, Clone)]
+pub(crate) struct Reported(HashSet<(PathBuf, String, Span)>);
+
+impl Reported {
+    /// Only retain the suggestions that were not reported before.
+    pub(crate) fn retain_unreported<'s>(
+        &mut self,
+        suggestions: Vec<Suggestion<'s>>,
+    ) -> Vec<Suggestion<'s>> {
+        suggestions
+            .into_iter()
+            .filter(|suggestion| {
+                let key = (
+                    suggestion.origin.as_path().to_owned(),
+                    suggestion.word(),
+                    suggestion.span,
+                );
+                let unreported = self.0.insert(key);
+                if !unreported {
+                    trace!("Skipping already reported suggestion {:?}", suggestion);
+                }
+                unreported
+            })
+            .collect()
+    }
+}
+
+/// Pick the first replacement of up to `max` suggestions of `origin`, which
+/// are expected to be sorted, for fixing without user interaction.
+///
+/// Returns the suggestions that were not picked, either because `max` is
+/// reached, there is no replacement, its confidence is below `min_confidence`,
+/// or they overlap with a picked one.
+fn pick_first_replacements<'s>(
+    origin: &ContentOrigin,
+    suggestions: Vec<Suggestion<'s>>,
+    mut max: usize,
+    min_confidence: Confidence,
+    picked: &mut UserPicked,
+) -> Vec<Suggestion<'s>> {
+    let mut last_span = None::<Span>;
+    suggestions
+        .into_iter()
+        .filter(|suggestion| {
+            if max == 0 {
+                return true;
+            }
+            let replacement = match suggestion.replacements.first() {
+                Some(replacement) => replacement,
+                None => return true,
+            };
+            if replacement_confidence(suggestion.word().as_str(), replacement) < min_confidence {
+                return true;
+            }
+            // overlapping patches can not be applied
+            if let Some(last_span) = last_span {
+                if suggestion.span.start <= last_span.end {
+                    return true;
+                }
+            }
+            last_span = Some(suggestion.span);
+            max -= 1;
+            picked.add_bandaid(
+                origin,
+                BandAid::from((replacement.to_owned(), &suggestion.span)),
+            );
+            false
+        })
+        .collect()
+}
+
 /// A patch to be stitched on-top of another string.
 ///
 /// Has intentionally no awareness of any rust or cmark/markdown semantics.
@@ -85,9 +165,11 @@ impl From<BandAid> for Patch {
 
 /// Correct lines by applying patches.
 ///
-/// Assumes all `BandAids` do not overlap when replacing. Inserting multiple
-/// times at a particular `LineColumn` is OK, but replacing overlapping `Span`s
-/// of the original source is not.
+/// Assumes all `BandAids` are ordered and do not overlap when replacing.
+/// Inserting multiple times at a particular `LineColumn` is OK, but replacing
+/// overlapping `Span`s of the original source is not and errors. The spans are
+/// converted to byte ranges while walking the source once, like
+/// [`Span::to_byte_range`] does for a single span.
 ///
 /// This function is not concerned with _any_ semantics or comments or
 /// whatsoever at all, it blindly replaces what is given to it.
@@ -100,11 +182,6 @@ where
     II: IntoIterator<IntoIter = I, Item = Patch>,
     I: Iterator<Item = Patch>,
 {
-    let mut patches = patches.into_iter().peekable();
-
-    let mut source_iter =
-        iter_with_line_column_from(source_buffer, LineColumn { line: 1, column: 0 }).peekable();
-
     const TARGET: &str = "patch";
     let mut write_to_sink = |topic: &str, data: &str| -> Result<()> {
         log::trace!(target: TARGET, "w<{}>: {}", topic, data.escape_debug());
@@ -112,110 +189,154 @@ where
         Ok(())
     };
 
-    let mut cc_end_byte_offset = 0;
+    let mut chars = crate::util::iter_with_line_column(source_buffer).peekable();
+    // skip the characters before `position`, or up to and including it, and
+    // return the byte offset of the next one
+    let mut advance = |position: LineColumn, inclusive: bool| -> usize {
+        while let Some(&(_c, byte_offset, _idx, cursor)) = chars.peek() {
+            if cursor > position || (cursor == position && !inclusive) {
+                return byte_offset;
+            }
+            chars.next();
+        }
+        source_buffer.len()
+    };
 
-    let mut current = None;
     let mut byte_cursor = 0usize;
-    loop {
-        let cc_start_byte_offset = if let Some(ref current) = current {
-            let (cc_start, data, insertion) = match current {
-                Patch::Replace {
-                    replace_span,
-                    replacement,
-                } => (replace_span.end, replacement.as_str(), false),
-                Patch::Insert { insert_at, content } => (insert_at.clone(), content.as_str(), true),
-            };
-
-            write_to_sink("new", data)?;
-
-            if insertion {
-                // do not advance anythin on insertion
-                byte_cursor
-            } else {
-                // skip the range of chars based on the line column
-                // so the cursor continues after the "replaced" characters
-                let mut cc_start_byte_offset = byte_cursor;
-                'skip: while let Some((c, byte_offset, _idx, linecol)) = source_iter.peek() {
-                    let byte_offset = *byte_offset;
-                    let linecol = *linecol;
-
-                    cc_start_byte_offset = byte_offset + c.len_utf8();
-
-                    if linecol >= cc_start {
-                        log::trace!(
-                            target: TARGET,
-                            "skip buffer: >{}<",
-                            &source_buffer[cc_end_byte_offset..cc_start_byte_offset].escape_debug()
-                        );
-
-                        break 'skip;
-                    }
-
-                    log::trace!(target: TARGET, "skip[{}]: >{}<", _idx, c.escape_debug());
-
-                    let _ = source_iter.next();
-                }
-                cc_start_byte_offset
-            }
-        } else {
-            byte_cursor
+    let mut position_cursor = LineColumn { line: 1, column: 0 };
+    for patch in patches {
+        let (start, end, data) = match patch {
+            Patch::Replace {
+                replace_span,
+                ref replacement,
+            } => (
+                replace_span.start,
+                Some(replace_span.end),
+                replacement.as_str(),
+            ),
+            Patch::Insert {
+                insert_at,
+                ref content,
+            } => (insert_at, None, content.as_str()),
         };
-        debug_assert!(byte_cursor <= cc_start_byte_offset);
-        byte_cursor = cc_start_byte_offset;
-
-        cc_end_byte_offset = if let Some(upcoming) = patches.peek() {
-            let cc_end = match upcoming {
-                Patch::Replace { replace_span, .. } => replace_span.start,
-                Patch::Insert { insert_at, .. } => insert_at.clone(),
-            };
+        if start < position_cursor {
+            bail!(
+                "Patch {:?} overlaps with the previous one, ending before {:?}",
+                patch,
+                position_cursor
+            )
+        }
+        let range_start = advance(start, false);
+        let range_end = match end {
+            Some(end) => std::cmp::max(range_start, advance(end, true)),
+            None => range_start,
+        };
+        position_cursor = end
+            .map(|end| LineColumn {
+                line: end.line,
+                column: end.column + 1,
+            })
+            .unwrap_or(start);
+        // carbon copy until the patch, skip the replaced bytes
+        write_to_sink("cc", &source_buffer[byte_cursor..range_start])?;
+        write_to_sink("new", data)?;
+        byte_cursor = range_end;
+    }
+    write_to_sink("cc", &source_buffer[byte_cursor..])?;
 
-            // do not write anything
+    Ok(())
+}
 
-            // carbon copy until this byte offset
-            let mut cc_end_byte_offset = byte_cursor;
-            'cc: while let Some((c, byte_offset, _idx, linecol)) = source_iter.peek() {
-                let byte_offset = *byte_offset;
-                let linecol = *linecol;
+/// The content of the file at `path` before and after applying `bandaids`,
+/// which must be sorted and non overlapping. The corrected content keeps the
+/// line endings and the byte order mark of the file.
+fn corrected_content(
+    path: &Path,
+    bandaids: impl IntoIterator<Item = BandAid>,
+) -> Result<(String, String)> {
+    trace!("Attempting to open {} as read", path.display());
+    let original = fs::read_to_string(path)?;
+    let format = TextFormat::detect(&original);
+    let content = TextFormat::normalize(&original);
+
+    let mut patched = Vec::with_capacity(content.len());
+    apply_patches(
+        bandaids.into_iter().map(|x| Patch::from(x)),
+        content.as_str(),
+        &mut patched,
+    )?;
+    let patched = String::from_utf8(patched)?;
+    Ok((original, format.denormalize(&patched)))
+}
 
-                if linecol >= cc_end {
-                    log::trace!(
-                        target: TARGET,
-                        "copy buffer: >{}<",
-                        &source_buffer[cc_start_byte_offset..cc_end_byte_offset].escape_debug()
-                    );
-                    break 'cc;
-                }
+/// The byte order mark, which some editors put at the start of UTF-8 files.
+const BOM: char = '\u{FEFF}';
+
+/// Line ending style and byte order mark of a file, restored when writing back
+/// the corrected content.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct TextFormat {
+    /// Lines end with `\r\n` rather than `\n`.
+    crlf: bool,
+    /// The file starts with a byte order mark.
+    bom: bool,
+}
 
-                cc_end_byte_offset = byte_offset + c.len_utf8();
+impl TextFormat {
+    /// Detect the format of `content`, the line ending style is the one of
+    /// the first line.
+    fn detect(content: &str) -> Self {
+        Self {
+            crlf: content
+                .find('\n')
+                .map_or(false, |idx| content[..idx].ends_with('\r')),
+            bom: content.starts_with(BOM),
+        }
+    }
 
-                log::trace!(target: TARGET, "copy[{}]: >{}<", _idx, c.escape_debug());
+    /// Convert all line endings to `\n`, which does not move any span.
+    ///
+    /// The byte order mark is kept, the spans count it as a character of the
+    /// first line.
+    fn normalize(content: &str) -> String {
+        content.replace("\r\n", "\n")
+    }
 
-                let _ = source_iter.next();
-                // we need to drag this one behind, since...
-            }
-            // in the case we reach EOF here the `cc_end_byte_offset` could never be updated correctly
-            std::cmp::min(cc_end_byte_offset, source_buffer.len())
+    /// Convert patched, normalized `content` back to the original format.
+    fn denormalize(&self, content: &str) -> String {
+        // replacements might carry their own `\r\n` or drop the mark
+        let content = Self::normalize(content.strip_prefix(BOM).unwrap_or(content));
+        let mut acc = String::with_capacity(content.len() + content.len() / 32 + 3);
+        if self.bom {
+            acc.push(BOM);
+        }
+        if self.crlf {
+            acc.push_str(&content.replace('\n', "\r\n"));
         } else {
-            source_buffer.len()
-        };
-        debug_assert!(byte_cursor <= cc_end_byte_offset);
-
-        byte_cursor = cc_end_byte_offset;
-
-        let cc_range = cc_start_byte_offset..cc_end_byte_offset;
-
-        write_to_sink("cc", &source_buffer[cc_range])?;
-
-        // move on to the next
-        current = patches.next();
-
-        if current.is_none() {
-            // we already made sure earlier to write out everything
-            break;
+            acc.push_str(&content);
         }
+        acc
     }
+}
 
-    Ok(())
+/// A misspelling is only a warning if none of its replacements is
+/// sufficiently close, since it is likely a technical term or a name.
+fn is_warning(suggestion: &Suggestion<'_>, warn_threshold: f32) -> bool {
+    suggestion.detector == Detector::Hunspell
+        && suggestion.best_confidence() < Confidence::new(warn_threshold)
+}
+
+/// The summary line of a check run, i.e. `Found 42 spelling errors in 3/156
+/// checked files`.
+fn summary(mistakes: usize, files_with_mistakes: usize, files: usize) -> String {
+    format!(
+        "Found {} spelling error{} in {}/{} checked file{}",
+        mistakes,
+        if mistakes == 1 { "" } else { "s" },
+        files_with_mistakes,
+        files,
+        if files == 1 { "" } else { "s" }
+    )
 }
 
 /// Mode in which `cargo-spellcheck` operates.
@@ -235,6 +356,12 @@ pub enum Action {
     /// List all files in depth first sorted order in which they would be
     /// checked.
     ListFiles,
+
+    /// Write all misspelled words to the baseline file.
+    UpdateBaseline,
+
+    /// Print the chunks which would be checked.
+    DumpChunks,
 }
 
 impl Action {
@@ -246,6 +373,7 @@ impl Action {
     ) -> Result<()> {
         match origin {
             ContentOrigin::CargoManifestDescription(path) => self.correct_file(path, bandaids),
+            ContentOrigin::CargoConfig(path) => self.correct_file(path, bandaids),
             ContentOrigin::CommonMarkFile(path) => self.correct_file(path, bandaids),
             ContentOrigin::RustSourceFile(path) => self.correct_file(path, bandaids),
             ContentOrigin::RustDocTest(path, _span) => self.correct_file(path, bandaids),
@@ -265,10 +393,7 @@ impl Action {
     ) -> Result<()> {
         let path = fs::canonicalize(path.as_path())?;
         let path = path.as_path();
-        trace!("Attempting to open {} as read", path.display());
-        let ro = fs::OpenOptions::new().read(true).open(path)?;
-
-        let mut reader = std::io::BufReader::new(ro);
+        let (_original, corrected) = corrected_content(path, bandaids)?;
 
         const TEMPORARY: &str = ".spellcheck.tmp";
 
@@ -286,24 +411,15 @@ impl Action {
 
         let mut writer = std::io::BufWriter::with_capacity(1024, wr);
 
-        let mut content = String::with_capacity(2e6 as usize);
-        reader.get_mut().read_to_string(&mut content)?;
-
         {
             let th = crate::TinHat::on();
 
-            apply_patches(
-                bandaids.into_iter().map(|x| Patch::from(x)),
-                content.as_str(), // FIXME for efficiency, correct_lines should integrate with `BufRead` instead of a `String` buffer
-                &mut writer,
-            )?;
-
+            writer.write_all(corrected.as_bytes())?;
             writer.flush()?;
             // Required for windows support, which does not allow
             // to move a file while it is opened, see
             // <https://github.com/drahnr/cargo-spellcheck/issues/251>
             drop(writer);
-            drop(reader);
             fs::rename(tmp, path)?;
 
             // Writing for this file is done, unblock the signal handler.
@@ -332,20 +448,110 @@ impl Action {
         Ok(())
     }
     /// Run the requested action.
-    pub async fn run(self, documents: Documentation, config: Config) -> Result<Finish> {
+    ///
+    /// With `fix_count`, checking also applies the first replacement to at most
+    /// that many mistakes. With `max_errors`, checking stops after the file in
+    /// which that many mistakes were reached. With `dry_run`, fixing prints a
+    /// diff instead of asking. Unless `quiet`, checking prints a summary to
+    /// `stderr`.
+    pub async fn run(
+        self,
+        documents: Documentation,
+        config: Config,
+        fix_count: Option<usize>,
+        max_errors: Option<usize>,
+        dry_run: bool,
+        quiet: bool,
+    ) -> Result<Finish> {
         let fin = match self {
             Self::ListFiles { .. } => self.run_list_files(documents, &config).await?,
             Self::Reflow { .. } => self.run_reflow(documents, config).await?,
-            Self::Check { .. } => self.run_check(documents, config).await?,
+            Self::Check { .. } => {
+                self.run_check(documents, config, fix_count, max_errors, quiet)
+                    .await?
+            }
+            Self::Fix { .. } if dry_run => self.run_fix_dry_run(documents, config).await?,
             Self::Fix { .. } => self.run_fix_interactive(documents, config).await?,
+            Self::UpdateBaseline { .. } => self.run_update_baseline(documents, config).await?,
+            Self::DumpChunks { .. } => self.run_dump_chunks(documents, &config).await?,
         };
         Ok(fin)
     }
 
     /// Run the requested action.
-    async fn run_list_files(self, documents: Documentation, _config: &Config) -> Result<Finish> {
-        for (origin, _chunks) in documents.iter() {
-            println!("{}", origin.as_path().display())
+    async fn run_list_files(self, documents: Documentation, config: &Config) -> Result<Finish> {
+        let stdout = std::io::stdout();
+        let mut out = stdout.lock();
+        let paths = documents
+            .iter()
+            .map(|(origin, _chunks)| crate::util::relative_to_cwd(origin.as_path()));
+        match config.output_format {
+            OutputFormat::Json => writeln!(
+                out,
+                "{}",
+                serde_json::to_string(&paths.collect::<Vec<_>>())?
+            )?,
+            OutputFormat::Human
+            | OutputFormat::Compact
+            | OutputFormat::GitLabCodeQuality
+            | OutputFormat::AzureDevOps
+            | OutputFormat::Checkstyle
+            | OutputFormat::Rdjsonl => {
+                for path in paths {
+                    writeln!(out, "{}", path.display())?
+                }
+                for (path, pattern) in documents.excluded() {
+                    writeln!(
+                        out,
+                        "{} (excluded by `{}`)",
+                        crate::util::relative_to_cwd(path).display(),
+                        pattern
+                    )?
+                }
+            }
+        }
+        Ok(Finish::Success)
+    }
+
+    /// Print all chunks with their origin and source mapping.
+    async fn run_dump_chunks(self, documents: Documentation, config: &Config) -> Result<Finish> {
+        let mut origins = documents.iter().collect::<Vec<_>>();
+        origins.sort_by(|(a, _), (b, _)| a.cmp(b));
+        let stdout = std::io::stdout();
+        let mut out = stdout.lock();
+        match config.output_format {
+            OutputFormat::Json => {
+                let json = origins
+                    .into_iter()
+                    .flat_map(|(origin, chunks)| chunks.iter().map(|chunk| chunk.to_json(origin)))
+                    .collect::<Vec<_>>();
+                writeln!(out, "{}", serde_json::to_string_pretty(&json)?)?;
+            }
+            OutputFormat::Human
+            | OutputFormat::Compact
+            | OutputFormat::GitLabCodeQuality
+            | OutputFormat::AzureDevOps
+            | OutputFormat::Checkstyle
+            | OutputFormat::Rdjsonl => {
+                for (origin, chunks) in origins {
+                    for (idx, chunk) in chunks.iter().enumerate() {
+                        writeln!(
+                            out,
+                            "{} {} chunk #{} ({:?})",
+                            origin.kind(),
+                            crate::util::relative_to_cwd(origin.as_path()).display(),
+                            idx,
+                            chunk.variant()
+                        )?;
+                        for (range, span) in chunk.iter() {
+                            writeln!(out, "  {:?} => {}", range, span)?;
+                        }
+                        writeln!(out, "---")?;
+                        writeln!(out, "{}", chunk.as_str())?;
+                        writeln!(out, "---")?;
+                    }
+                }
+            }
         }
         Ok(Finish::Success)
     }
@@ -370,9 +576,11 @@ impl Action {
             .fuse();
 
         let mut collected_picks = UserPicked::default();
+        let mut reported = Reported::default();
         while let Some(result) = pick_stream.next().await {
             match result {
                 Ok((idx, origin, suggestions)) => {
+                    let suggestions = reported.retain_unreported(suggestions);
                     let (picked, user_sel) =
                         interactive::UserPicked::select_interactive(origin.clone(), suggestions)?;
 
@@ -408,33 +616,309 @@ impl Action {
         Ok(Finish::MistakeCount(total))
     }
 
-    /// Run the requested action.
-    async fn run_check(self, documents: Documentation, config: Config) -> Result<Finish> {
+    /// Print a unified diff of the files with the first replacement of every
+    /// mistake applied to `stdout`, without changing them.
+    ///
+    /// The resulting count is the number of files which would change.
+    async fn run_fix_dry_run(self, documents: Documentation, config: Config) -> Result<Finish> {
         let n_cpus = num_cpus::get();
+        let fix_threshold = config.fix_threshold();
 
         let checkers = Checkers::new(config)?;
 
-        // TODO per file clustering might make sense here
-        let mistakes_count = stream::iter(documents.iter().enumerate())
-            .map(move |(idx, (origin, chunks))| {
+        let mut sorted = documents.iter().collect::<Vec<_>>();
+        sorted.sort_by(|(origin, _), (other, _)| origin.cmp(other));
+
+        let (_reported, picked) = stream::iter(sorted.into_iter())
+            .map(|(origin, chunks)| {
                 let suggestions = checkers.check(origin, &chunks[..]);
-                async move { Ok::<_, color_eyre::eyre::Report>((idx, origin, suggestions?)) }
+                async move { Ok::<_, color_eyre::eyre::Report>((origin, suggestions?)) }
             })
             .buffered(n_cpus)
-            .try_fold(0_usize, |acc, (_idx, origin, suggestions)| async move {
-                let n = suggestions.len();
-                let path = origin.as_path();
-                if n == 0 {
-                    info!("✅ {}", path.display());
-                } else {
-                    info!("❌ {} : {}", path.display(), n);
-                }
-                for suggestion in suggestions {
-                    println!("{}", suggestion);
-                }
-                Ok::<_, color_eyre::eyre::Report>(acc + n)
-            })
+            .try_fold(
+                (Reported::default(), UserPicked::default()),
+                |(mut reported, mut picked), (origin, suggestions)| async move {
+                    let suggestions = reported.retain_unreported(suggestions);
+                    // mistakes without a confident replacement are not fixed
+                    let _unfixable = pick_first_replacements(
+                        origin,
+                        suggestions,
+                        usize::MAX,
+                        fix_threshold,
+                        &mut picked,
+                    );
+                    Ok::<_, color_eyre::eyre::Report>((reported, picked))
+                },
+            )
             .await?;
+
+        // doc tests share the file with the rest of the documentation
+        let mut by_path = indexmap::IndexMap::<PathBuf, Vec<BandAid>>::new();
+        for (origin, bandaids) in picked.bandaids {
+            by_path
+                .entry(origin.as_path().to_owned())
+                .or_default()
+                .extend(bandaids);
+        }
+        let mut changed = 0;
+        let stdout = std::io::stdout();
+        let mut out = stdout.lock();
+        for (path, mut bandaids) in by_path {
+            bandaids.sort_by_key(|bandaid| (bandaid.span.start.line, bandaid.span.start.column));
+            let (original, corrected) = corrected_content(&path, bandaids)?;
+            let name = crate::util::relative_to_cwd(&path).display().to_string();
+            let diff = diff::unified_diff(
+                &original,
+                &corrected,
+                &format!("a/{}", name),
+                &format!("b/{}", name),
+                3,
+            );
+            if !diff.is_empty() {
+                changed += 1;
+                write!(out, "{}", diff)?;
+            }
+        }
+        Ok(Finish::MistakeCount(changed))
+    }
+
+    /// Run the requested action.
+    ///
+    /// The first `fix_count` mistakes, in file order, are fixed with their
+    /// first replacement instead of being printed. Once `max_errors` mistakes
+    /// were printed, the remaining files are skipped. With `incremental`, files
+    /// which were clean before and did not change are skipped too.
+    async fn run_check(
+        self,
+        documents: Documentation,
+        config: Config,
+        fix_count: Option<usize>,
+        max_errors: Option<usize>,
+        quiet: bool,
+    ) -> Result<Finish> {
+        let n_cpus = num_cpus::get();
+        let max_suggestions = config.max_suggestions();
+        let display_width = config.display_width();
+        // the confidence of each replacement is only shown when running verbose
+        let show_confidence = log::log_enabled!(log::Level::Info);
+        let output_format = config.output_format;
+        let warn_threshold = config.warn_threshold();
+        let fix_threshold = config.fix_threshold();
+
+        let cache = if config.incremental {
+            let stamp = crate::cache::stamp(&config)?;
+            Some(Mutex::new(crate::cache::Cache::load(
+                crate::cache::path()?,
+                stamp,
+            )))
+        } else {
+            None
+        };
+        let cache = &cache;
+
+        let checkers = Checkers::new(config)?;
+
+        if output_format == OutputFormat::Checkstyle {
+            let stdout = std::io::stdout();
+            let mut out = stdout.lock();
+            writeln!(out, r#"<?xml version="1.0" encoding="UTF-8"?>"#)?;
+            writeln!(out, r#"<checkstyle version="8.0">"#)?;
+        }
+
+        // report in a deterministic order, independent of the discovery order,
+        // the suggestions of each file are sorted by their span already
+        let mut sorted = documents.iter().collect::<Vec<_>>();
+        sorted.sort_by(|(origin, _), (other, _)| origin.cmp(other));
+
+        // set once `max_errors` is reached, files checked ahead in parallel are
+        // discarded
+        let limit_reached = AtomicBool::new(false);
+        let limit_reached = &limit_reached;
+
+        // TODO per file clustering might make sense here
+        // the number of reported mistakes per origin
+        let (counts, _reported, json, picked, mistakes_count) =
+            stream::iter(sorted.into_iter().enumerate())
+                .take_while(move |_| futures::future::ready(!limit_reached.load(Ordering::Relaxed)))
+                .map(move |(idx, (origin, chunks))| {
+                    let checked = crate::cache::check_unless_clean(
+                        cache.as_ref(),
+                        &checkers,
+                        origin,
+                        &chunks[..],
+                    );
+                    async move {
+                        let (hash, suggestions) = checked?;
+                        Ok::<_, color_eyre::eyre::Report>((idx, origin, hash, suggestions))
+                    }
+                })
+                .buffered(n_cpus)
+                .try_fold(
+                    (
+                        Vec::new(),
+                        Reported::default(),
+                        Vec::new(),
+                        UserPicked::default(),
+                        0_usize,
+                    ),
+                    // `total` is the running sum of the mistakes in `acc`
+                    |(mut acc, mut reported, mut json, mut picked, mut total),
+                     (_, origin, hash, suggestions)| async move {
+                        if limit_reached.load(Ordering::Relaxed) {
+                            return Ok((acc, reported, json, picked, total));
+                        }
+                        let suggestions = match suggestions {
+                            Some(suggestions) => suggestions,
+                            None => {
+                                debug!("Skipping unchanged {}", origin.as_path().display());
+                                return Ok((acc, reported, json, picked, total));
+                            }
+                        };
+                        let mut suggestions = reported.retain_unreported(suggestions);
+                        if let Some(fix_count) = fix_count {
+                            let remaining = fix_count.saturating_sub(picked.total_count());
+                            suggestions = pick_first_replacements(
+                                origin,
+                                suggestions,
+                                remaining,
+                                fix_threshold,
+                                &mut picked,
+                            );
+                        }
+                        for suggestion in suggestions.iter_mut() {
+                            if is_warning(suggestion, warn_threshold) {
+                                suggestion.severity = Severity::Warning;
+                            }
+                        }
+                        let n = suggestions
+                            .iter()
+                            .filter(|suggestion| suggestion.severity == Severity::Error)
+                            .count();
+                        if let (Some(cache), Some(hash)) = (cache, hash) {
+                            let outcome = if n == 0 {
+                                crate::cache::Outcome::Clean
+                            } else {
+                                crate::cache::Outcome::Errors
+                            };
+                            cache.lock().unwrap().record(origin, hash, outcome);
+                        }
+                        let path = origin.as_path();
+                        let stdout = std::io::stdout();
+                        let mut out = stdout.lock();
+                        if n == 0 {
+                            info!("✅ {}", path.display());
+                        } else {
+                            info!("❌ {} : {}", path.display(), n);
+                        }
+                        if output_format == OutputFormat::Checkstyle {
+                            writeln!(
+                                out,
+                                r#"<file name="{}">"#,
+                                crate::util::xml_escape(
+                                    &crate::util::relative_to_cwd(path).display().to_string()
+                                )
+                            )?;
+                        }
+                        // reviewdog counts columns in bytes, which requires the file content
+                        let source = if output_format == OutputFormat::Rdjsonl {
+                            fs::read_to_string(path).ok()
+                        } else {
+                            None
+                        };
+                        for suggestion in suggestions {
+                            match output_format {
+                                OutputFormat::Human => writeln!(
+                                    out,
+                                    "{}",
+                                    suggestion
+                                        .display_limited(max_suggestions, display_width)
+                                        .with_confidence(show_confidence)
+                                )?,
+                                OutputFormat::Compact => writeln!(
+                                    out,
+                                    "{}",
+                                    suggestion.display_compact(max_suggestions)
+                                )?,
+                                OutputFormat::Json => {
+                                    json.push(suggestion.to_json(max_suggestions))
+                                }
+                                OutputFormat::GitLabCodeQuality => {
+                                    json.push(suggestion.to_gitlab_code_quality(max_suggestions))
+                                }
+                                OutputFormat::AzureDevOps => writeln!(
+                                    out,
+                                    "{}",
+                                    suggestion.to_azure_devops(max_suggestions)
+                                )?,
+                                OutputFormat::Checkstyle => {
+                                    writeln!(out, "{}", suggestion.to_checkstyle(max_suggestions))?
+                                }
+                                OutputFormat::Rdjsonl => writeln!(
+                                    out,
+                                    "{}",
+                                    suggestion.to_rdjson(max_suggestions, source.as_deref())
+                                )?,
+                            }
+                        }
+                        if output_format == OutputFormat::Checkstyle {
+                            writeln!(out, "</file>")?;
+                        }
+                        acc.push((path, n));
+                        total += n;
+                        if let Some(max_errors) = max_errors {
+                            if total >= max_errors {
+                                limit_reached.store(true, Ordering::Relaxed);
+                            }
+                        }
+                        Ok::<_, color_eyre::eyre::Report>((acc, reported, json, picked, total))
+                    },
+                )
+                .await?;
+        if let Some(cache) = cache {
+            cache.lock().unwrap().store()?;
+        }
+        if fix_count.is_some() {
+            info!("Fixed {} mistakes", picked.total_count());
+            self.write_user_pick_changes_to_disk(picked)?;
+        }
+        let stdout = std::io::stdout();
+        let mut out = stdout.lock();
+        if matches!(
+            output_format,
+            OutputFormat::Json | OutputFormat::GitLabCodeQuality
+        ) {
+            writeln!(out, "{}", serde_json::to_string_pretty(&json)?)?;
+        }
+        if output_format == OutputFormat::Checkstyle {
+            writeln!(out, "</checkstyle>")?;
+        }
+        if !documents.skipped().is_empty() {
+            warn!(
+                "Skipped {} files which could not be read or have binary content",
+                documents.skipped().len()
+            );
+        }
+        if !quiet {
+            // doc tests share the file with the rest of the documentation
+            let files = counts
+                .iter()
+                .map(|(path, _n)| path)
+                .collect::<HashSet<_>>()
+                .len();
+            let files_with_mistakes = counts
+                .iter()
+                .filter(|(_path, n)| *n > 0)
+                .map(|(path, _n)| path)
+                .collect::<HashSet<_>>()
+                .len();
+            eprintln!("{}", summary(mistakes_count, files_with_mistakes, files));
+        }
+        if !quiet && limit_reached.load(Ordering::Relaxed) {
+            eprintln!(
+                "(stopped after {} errors; rerun without --max-errors to see all)",
+                mistakes_count
+            );
+        }
         if mistakes_count > 0 {
             Ok(Finish::MistakeCount(mistakes_count))
         } else {
@@ -442,6 +926,27 @@ impl Action {
         }
     }
 
+    /// Run the checkers and replace the baseline with all found words.
+    async fn run_update_baseline(self, documents: Documentation, config: Config) -> Result<Finish> {
+        let n_cpus = num_cpus::get();
+
+        let checkers = Checkers::new(config)?;
+
+        let words = stream::iter(documents.iter())
+            .map(move |(origin, chunks)| {
+                let suggestions = checkers.check(origin, &chunks[..]);
+                async move { Ok::<_, color_eyre::eyre::Report>(suggestions?) }
+            })
+            .buffered(n_cpus)
+            .try_fold(BTreeSet::new(), |mut words, suggestions| async move {
+                words.extend(suggestions.iter().map(|suggestion| suggestion.word()));
+                Ok::<_, color_eyre::eyre::Report>(words)
+            })
+            .await?;
+        crate::baseline::store(crate::baseline::path()?, &words)?;
+        Ok(Finish::Success)
+    }
+
     /// Run the requested action.
     async fn run_reflow(self, documents: Documentation, config: Config) -> Result<Finish> {
         let reflow_config = config.reflow.clone().unwrap_or_default();
@@ -492,6 +997,168 @@ mod tests {
         };
     }
 
+    #[test]
+    fn summary_line() {
+        assert_eq!(
+            summary(42, 3, 156),
+            "Found 42 spelling errors in 3/156 checked files"
+        );
+        assert_eq!(
+            summary(1, 1, 1),
+            "Found 1 spelling error in 1/1 checked file"
+        );
+        assert_eq!(
+            summary(0, 0, 2),
+            "Found 0 spelling errors in 0/2 checked files"
+        );
+    }
+
+    #[test]
+    fn reported_once() {
+        let chunk = dyrck_chunk();
+        let suggestion = dyrck_suggestion(&chunk, &[]);
+
+        let mut reported = Reported::default();
+        assert_eq!(
+            reported
+                .retain_unreported(vec![suggestion.clone(), suggestion.clone()])
+                .len(),
+            1
+        );
+        assert!(reported.retain_unreported(vec![suggestion]).is_empty());
+    }
+
+    #[test]
+    fn warnings_below_threshold() {
+        let chunk = dyrck_chunk();
+        let mut suggestion = dyrck_suggestion(&chunk, &["dark"]);
+        suggestion.detector = Detector::Hunspell;
+        // two edits within five characters
+        assert!(!is_warning(&suggestion, 0.0));
+        assert!(!is_warning(&suggestion, 0.5));
+        assert!(is_warning(&suggestion, 0.7));
+
+        suggestion.replacements.clear();
+        assert!(is_warning(&suggestion, 0.1));
+
+        suggestion.detector = Detector::NlpRules;
+        assert!(!is_warning(&suggestion, 0.7));
+    }
+
+    #[test]
+    fn fix_count_picks_first_replacements() {
+        let chunk = CheckableChunk::from_str(
+            " Is it dyrck agian nwo?",
+            indexmap::indexmap! { 0..23 => Span {
+                    start: LineColumn { line: 1, column: 0 },
+                    end: LineColumn { line: 1, column: 22 },
+                }
+            },
+            CommentVariant::TripleSlash,
+        );
+        let suggestion = |range: Range, replacements: &[&str]| Suggestion {
+            detector: Detector::Dummy,
+            origin: ContentOrigin::TestEntityRust,
+            chunk: &chunk,
+            span: Span {
+                start: LineColumn {
+                    line: 1,
+                    column: range.start - 1,
+                },
+                end: LineColumn {
+                    line: 1,
+                    column: range.end - 2,
+                },
+            },
+            range,
+            replacements: replacements.iter().map(|r| r.to_string()).collect(),
+            description: None,
+            severity: Severity::Error,
+        };
+        let suggestions = vec![
+            suggestion(7..12, &[]),
+            suggestion(13..18, &["again", "agio"]),
+            suggestion(13..18, &["aging"]),
+            suggestion(19..22, &["now"]),
+        ];
+
+        let mut picked = UserPicked::default();
+        let remaining = pick_first_replacements(
+            &ContentOrigin::TestEntityRust,
+            suggestions.clone(),
+            1,
+            Confidence::default(),
+            &mut picked,
+        );
+        assert_eq!(picked.total_count(), 1);
+        assert_eq!(
+            picked.bandaids[&ContentOrigin::TestEntityRust][0].content,
+            "again"
+        );
+        // no replacement, overlapping and above the limit
+        assert_eq!(remaining.len(), 3);
+
+        let mut picked = UserPicked::default();
+        let remaining = pick_first_replacements(
+            &ContentOrigin::TestEntityRust,
+            suggestions.clone(),
+            10,
+            Confidence::default(),
+            &mut picked,
+        );
+        assert_eq!(picked.total_count(), 2);
+        assert_eq!(remaining.len(), 2);
+
+        // `now` is a single edit within a three letter word
+        let mut picked = UserPicked::default();
+        let remaining = pick_first_replacements(
+            &ContentOrigin::TestEntityRust,
+            suggestions,
+            10,
+            Confidence::new(0.8),
+            &mut picked,
+        );
+        assert_eq!(picked.total_count(), 1);
+        assert_eq!(remaining.len(), 3);
+        assert_eq!(remaining[2].word(), "nwo");
+    }
+
+    #[test]
+    fn text_format_roundtrip() {
+        let crlf = "\u{FEFF}/// Alpha\r\n/// beta\r\nstruct X;\r\n";
+        let format = TextFormat::detect(crlf);
+        assert_eq!(
+            format,
+            TextFormat {
+                crlf: true,
+                bom: true
+            }
+        );
+        let normalized = TextFormat::normalize(crlf);
+        assert_eq!(normalized, "\u{FEFF}/// Alpha\n/// beta\nstruct X;\n");
+        assert_eq!(format.denormalize(&normalized), crlf);
+        // i.e. a reflow which kept the line endings of the chunk
+        assert_eq!(
+            format.denormalize("\u{FEFF}/// Alpha\r\n/// beta gamma\nstruct X;\n"),
+            "\u{FEFF}/// Alpha\r\n/// beta gamma\r\nstruct X;\r\n"
+        );
+
+        let lf = "/// Alpha\nstruct X;\r\n";
+        let format = TextFormat::detect(lf);
+        assert_eq!(
+            format,
+            TextFormat {
+                crlf: false,
+                bom: false
+            }
+        );
+        assert_eq!(
+            format.denormalize(&TextFormat::normalize(lf)),
+            "/// Alpha\nstruct X;\n"
+        );
+        assert!(!TextFormat::detect("no newline\r").crlf);
+    }
+
     #[test]
     fn patch_full() {
         let _ = env_logger::Builder::new()
@@ -613,4 +1280,25 @@ Icecream truck"#
         }];
         verify_correction!("A🐢C", patches, "A🐢CQ");
     }
+
+    #[test]
+    fn patch_overlapping() {
+        let replace = |start: usize, end: usize| Patch::Replace {
+            replace_span: Span {
+                start: LineColumn {
+                    line: 1,
+                    column: start,
+                },
+                end: LineColumn {
+                    line: 1,
+                    column: end,
+                },
+            },
+            replacement: "Q".to_owned(),
+        };
+        let mut sink = Vec::new();
+        assert!(apply_patches(vec![replace(0, 2), replace(2, 3)], "A🐢CD", &mut sink).is_err());
+
+        verify_correction!("A🐢CD", vec![replace(0, 1), replace(2, 3)], "QQ");
+    }
 }
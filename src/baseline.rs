@@ -0,0 +1,76 @@
+//! A baseline of known misspelled words, which are not reported with
+//! `--only-new-words`.
+//!
+//! The baseline is a plain text file with one word per line, so it can be
+//! edited by hand, lines starting with `#` are comments.
+
+use crate::errors::*;
+
+use fs_err as fs;
+use std::collections::{BTreeSet, HashSet};
+use std::path::{Path, PathBuf};
+
+/// File name of the baseline, in the current working directory.
+const BASELINE_FILE: &str = ".cargo-spellcheck-baseline.txt";
+
+const HEADER: &str = "\
+# Known misspelled words, not reported with `--only-new-words`.
+# Generated by `cargo spellcheck update-baseline`.
+";
+
+/// Path of the baseline file in the current working directory.
+pub(crate) fn path() -> Result<PathBuf> {
+    Ok(crate::traverse::cwd()?.join(BASELINE_FILE))
+}
+
+/// Load the words of the baseline, a missing baseline is empty.
+pub(crate) fn load(path: impl AsRef<Path>) -> Result<HashSet<String>> {
+    let path = path.as_ref();
+    let content = match fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            log::warn!(
+                "Baseline {} does not exist, create it with `cargo spellcheck update-baseline`",
+                path.display()
+            );
+            return Ok(HashSet::new());
+        }
+        Err(e) => return Err(e.into()),
+    };
+    Ok(crate::config::parse_ignored_words(content.as_str()).collect())
+}
+
+/// Replace the baseline with `words`, sorted.
+pub(crate) fn store(path: impl AsRef<Path>, words: &BTreeSet<String>) -> Result<()> {
+    let path = path.as_ref();
+    let content = words.iter().fold(HEADER.to_owned(), |mut acc, word| {
+        acc.push_str(word);
+        acc.push('\n');
+        acc
+    });
+    fs::write(path, content)?;
+    log::info!("Wrote {} words to baseline {}", words.len(), path.display());
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn roundtrip() {
+        let path = std::env::temp_dir().join(uuid::Uuid::new_v4().to_string());
+        assert!(load(&path).unwrap().is_empty());
+
+        let words = ["dyrck", "Thisf"]
+            .iter()
+            .map(|word| word.to_string())
+            .collect::<BTreeSet<_>>();
+        store(&path, &words).unwrap();
+        let content = fs::read_to_string(&path).unwrap();
+        assert!(content.starts_with(HEADER));
+        assert!(content.ends_with("Thisf\ndyrck\n"));
+
+        assert_eq!(load(&path).unwrap(), words.into_iter().collect());
+    }
+}
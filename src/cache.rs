@@ -0,0 +1,307 @@
+//! A cache of the outcome of previous checks, with `--incremental` files which
+//! were checked without errors are not checked again unless they changed.
+//!
+//! The cache is a plain text file in the user's cache directory, one per
+//! working directory. The first line holds a stamp of the configuration and
+//! the modification times of the dictionaries, a different stamp invalidates
+//! all entries. Every other line holds the fingerprint of an origin, the hash
+//! of its content and whether it was `clean` or had `errors`.
+
+use crate::checker::Checker;
+use crate::errors::*;
+use crate::util::{fnv1a, FNV_OFFSET_BASIS};
+use crate::{CheckableChunk, Config, ContentOrigin, Suggestion};
+
+use fs_err as fs;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::UNIX_EPOCH;
+
+/// Outcome of checking an origin.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Outcome {
+    /// No errors were found, warnings are fine.
+    Clean,
+    /// At least one error was found, so the origin is always checked again.
+    Errors,
+}
+
+impl Outcome {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Self::Clean => "clean",
+            Self::Errors => "errors",
+        }
+    }
+}
+
+/// The outcomes of previous checks, by the fingerprint of the origin.
+#[derive(Debug, Clone)]
+pub(crate) struct Cache {
+    path: PathBuf,
+    stamp: u64,
+    entries: BTreeMap<u64, (u64, Outcome)>,
+}
+
+/// Path of the cache of the current working directory.
+pub(crate) fn path() -> Result<PathBuf> {
+    let cwd = crate::traverse::cwd()?;
+    let base = directories::BaseDirs::new()
+        .ok_or_else(|| eyre!("Failed to determine the cache directory, env HOME must be set"))?;
+    let key = fnv1a(FNV_OFFSET_BASIS, cwd.to_string_lossy().as_bytes());
+    Ok(base
+        .cache_dir()
+        .join("cargo-spellcheck")
+        .join(format!("incremental-{:016x}.txt", key)))
+}
+
+/// A stamp of everything besides the content which affects the outcome of a
+/// check, the version, the configuration and the modification times of all
+/// dictionaries and word lists.
+pub(crate) fn stamp(config: &Config) -> Result<u64> {
+    let mut stamp = fnv1a(FNV_OFFSET_BASIS, env!("CARGO_PKG_VERSION").as_bytes());
+    stamp = fnv1a(stamp, config.to_toml()?.as_bytes());
+
+    let mut dictionaries = Vec::new();
+    if let Some(ref hunspell) = config.hunspell {
+        for (dic, aff) in hunspell.available_dictionaries() {
+            dictionaries.push(dic);
+            dictionaries.push(aff);
+        }
+        dictionaries.extend(hunspell.extra_dictionaries().cloned());
+    }
+    dictionaries.extend(config.personal_dictionaries.iter().cloned());
+    dictionaries.extend(config.ignore_words_files.iter().cloned());
+    if config.only_new_words {
+        dictionaries.push(crate::baseline::path()?);
+    }
+    for path in dictionaries {
+        // a missing file is reported by the checkers
+        let modified = std::fs::metadata(&path)
+            .and_then(|meta| meta.modified())
+            .ok()
+            .and_then(|modified| modified.duration_since(UNIX_EPOCH).ok())
+            .map_or(0, |modified| modified.as_nanos());
+        stamp = fnv1a(stamp, path.to_string_lossy().as_bytes());
+        stamp = fnv1a(stamp, &modified.to_le_bytes());
+    }
+    Ok(stamp)
+}
+
+/// The hash of the content of the file `origin` refers to and of its chunks,
+/// `None` if there is no such file.
+///
+/// The chunks depend on the inputs to check, i.e. `--dev-comments`.
+pub(crate) fn content_hash(origin: &ContentOrigin, chunks: &[CheckableChunk]) -> Option<u64> {
+    let content = std::fs::read(origin.as_path()).ok()?;
+    let hash = chunks
+        .iter()
+        .fold(fnv1a(FNV_OFFSET_BASIS, &content), |hash, chunk| {
+            fnv1a(fnv1a(hash, chunk.as_str().as_bytes()), &[0])
+        });
+    Some(hash)
+}
+
+/// Check the `chunks` of `origin` with `checker`, unless the `cache` knows
+/// them as clean with the same content, then there are no suggestions.
+///
+/// Also returns the content hash, to record the outcome of the check with.
+pub(crate) fn check_unless_clean<'a, 's, C: Checker>(
+    cache: Option<&Mutex<Cache>>,
+    checker: &C,
+    origin: &ContentOrigin,
+    chunks: &'a [CheckableChunk],
+) -> Result<(Option<u64>, Option<Vec<Suggestion<'s>>>)>
+where
+    'a: 's,
+{
+    let hash = cache.and_then(|_| content_hash(origin, chunks));
+    let unchanged = match (cache, hash) {
+        (Some(cache), Some(hash)) => cache.lock().unwrap().is_clean(origin, hash),
+        _ => false,
+    };
+    if unchanged {
+        return Ok((hash, None));
+    }
+    Ok((hash, Some(checker.check(origin, chunks)?)))
+}
+
+impl Cache {
+    /// Load the cache, all entries are dropped if it was stored with a
+    /// different `stamp`. A missing or malformed cache is empty.
+    pub(crate) fn load(path: impl AsRef<Path>, stamp: u64) -> Self {
+        let path = path.as_ref().to_owned();
+        let mut cache = Self {
+            path,
+            stamp,
+            entries: BTreeMap::new(),
+        };
+        let content = match fs::read_to_string(&cache.path) {
+            Ok(content) => content,
+            Err(e) => {
+                log::debug!("No incremental cache loaded: {}", e);
+                return cache;
+            }
+        };
+        let mut lines = content.lines();
+        if lines.next() != Some(format!("stamp {:016x}", stamp).as_str()) {
+            log::info!("Configuration or dictionaries changed, checking all files");
+            return cache;
+        }
+        for line in lines {
+            let mut fields = line.split_whitespace();
+            let fingerprint = fields.next().and_then(|x| u64::from_str_radix(x, 16).ok());
+            let hash = fields.next().and_then(|x| u64::from_str_radix(x, 16).ok());
+            let outcome = match fields.next() {
+                Some("clean") => Some(Outcome::Clean),
+                Some("errors") => Some(Outcome::Errors),
+                _ => None,
+            };
+            match (fingerprint, hash, outcome) {
+                (Some(fingerprint), Some(hash), Some(outcome)) => {
+                    cache.entries.insert(fingerprint, (hash, outcome));
+                }
+                _ => log::warn!("Ignoring malformed incremental cache entry: {}", line),
+            }
+        }
+        cache
+    }
+
+    /// If `origin` was checked without errors before, with the same content.
+    pub(crate) fn is_clean(&self, origin: &ContentOrigin, hash: u64) -> bool {
+        self.entries.get(&origin.fingerprint()) == Some(&(hash, Outcome::Clean))
+    }
+
+    /// Record the outcome of checking `origin`.
+    pub(crate) fn record(&mut self, origin: &ContentOrigin, hash: u64, outcome: Outcome) {
+        self.entries.insert(origin.fingerprint(), (hash, outcome));
+    }
+
+    /// Write the cache, creating the cache directory if needed.
+    pub(crate) fn store(&self) -> Result<()> {
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let content = self.entries.iter().fold(
+            format!("stamp {:016x}\n", self.stamp),
+            |mut acc, (fingerprint, (hash, outcome))| {
+                acc.push_str(&format!(
+                    "{:016x} {:016x} {}\n",
+                    fingerprint,
+                    hash,
+                    outcome.as_str()
+                ));
+                acc
+            },
+        );
+        fs::write(&self.path, content)?;
+        log::debug!("Wrote incremental cache {}", self.path.display());
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn roundtrip() {
+        let path = std::env::temp_dir().join(uuid::Uuid::new_v4().to_string());
+        let clean = ContentOrigin::RustSourceFile(PathBuf::from("src/lib.rs"));
+        let dirty = ContentOrigin::CommonMarkFile(PathBuf::from("README.md"));
+
+        let mut cache = Cache::load(&path, 7);
+        assert!(!cache.is_clean(&clean, 1));
+        cache.record(&clean, 1, Outcome::Clean);
+        cache.record(&dirty, 2, Outcome::Errors);
+        cache.store().unwrap();
+
+        let cache = Cache::load(&path, 7);
+        assert!(cache.is_clean(&clean, 1));
+        assert!(!cache.is_clean(&clean, 3));
+        assert!(!cache.is_clean(&dirty, 2));
+
+        // i.e. a dictionary was modified
+        let cache = Cache::load(&path, 8);
+        assert!(!cache.is_clean(&clean, 1));
+        let _ = fs::remove_file(path);
+    }
+
+    #[test]
+    fn content_hash_of_file() {
+        let path = std::env::temp_dir().join(uuid::Uuid::new_v4().to_string());
+        let origin = ContentOrigin::RustSourceFile(path.clone());
+        assert_eq!(content_hash(&origin, &[]), None);
+
+        fs::write(&path, "/// Fine.\nstruct Fine;\n").unwrap();
+        let hash = content_hash(&origin, &[]).unwrap();
+        assert_eq!(content_hash(&origin, &[]), Some(hash));
+        let chunk = CheckableChunk::from_str(
+            " Fine.",
+            indexmap::IndexMap::new(),
+            crate::CommentVariant::TripleSlash,
+        );
+        assert_ne!(content_hash(&origin, &[chunk]), Some(hash));
+
+        fs::write(&path, "/// Fin.\nstruct Fine;\n").unwrap();
+        assert_ne!(content_hash(&origin, &[]), Some(hash));
+        let _ = fs::remove_file(path);
+    }
+
+    /// Finds nothing, but counts how often it was asked to.
+    #[derive(Default)]
+    struct CountingChecker(std::sync::atomic::AtomicUsize);
+
+    impl Checker for CountingChecker {
+        type Config = ();
+
+        fn detector() -> crate::Detector {
+            crate::Detector::Dummy
+        }
+
+        fn check<'a, 's>(
+            &self,
+            _origin: &ContentOrigin,
+            _chunks: &'a [CheckableChunk],
+        ) -> Result<Vec<Suggestion<'s>>>
+        where
+            'a: 's,
+        {
+            self.0.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            Ok(Vec::new())
+        }
+    }
+
+    #[test]
+    fn clean_file_is_checked_once() {
+        let cache_path = std::env::temp_dir().join(uuid::Uuid::new_v4().to_string());
+        let path = std::env::temp_dir().join(uuid::Uuid::new_v4().to_string());
+        fs::write(&path, "/// Fine.\nstruct Fine;\n").unwrap();
+        let origin = ContentOrigin::RustSourceFile(path.clone());
+        let checker = CountingChecker::default();
+
+        let cache = Mutex::new(Cache::load(&cache_path, 7));
+        let (hash, suggestions) = check_unless_clean(Some(&cache), &checker, &origin, &[]).unwrap();
+        assert_eq!(suggestions.map(|suggestions| suggestions.len()), Some(0));
+        cache
+            .lock()
+            .unwrap()
+            .record(&origin, hash.unwrap(), Outcome::Clean);
+        cache.lock().unwrap().store().unwrap();
+
+        let cache = Mutex::new(Cache::load(&cache_path, 7));
+        let (_hash, suggestions) =
+            check_unless_clean(Some(&cache), &checker, &origin, &[]).unwrap();
+        assert!(suggestions.is_none());
+        assert_eq!(checker.0.load(std::sync::atomic::Ordering::SeqCst), 1);
+
+        // without the cache, every run checks
+        let (hash, suggestions) = check_unless_clean(None, &checker, &origin, &[]).unwrap();
+        assert_eq!(hash, None);
+        assert!(suggestions.is_some());
+        assert_eq!(checker.0.load(std::sync::atomic::Ordering::SeqCst), 2);
+        let _ = fs::remove_file(cache_path);
+        let _ = fs::remove_file(path);
+    }
+}
@@ -0,0 +1,184 @@
+//! Reports all non-ASCII characters in comments, enabled by `ascii_only` of
+//! `[checks]`.
+//!
+//! Catches typographic characters which slip in via copy and paste, i.e. `’`
+//! or `—`, and suggests their ASCII equivalent where one exists.
+
+use super::Checker;
+
+use crate::suggestion::{Detector, Severity, Suggestion};
+use crate::{errors::*, CheckableChunk, CommentVariantCategory, ContentOrigin};
+
+/// Common non-ASCII characters, sorted by code point, with their Unicode name
+/// and ASCII equivalent.
+const KNOWN: &[(char, &str, Option<&str>)] = &[
+    ('\u{00A0}', "NO-BREAK SPACE", Some(" ")),
+    (
+        '\u{00AB}',
+        "LEFT-POINTING DOUBLE ANGLE QUOTATION MARK",
+        Some("\""),
+    ),
+    ('\u{00B4}', "ACUTE ACCENT", Some("'")),
+    (
+        '\u{00BB}',
+        "RIGHT-POINTING DOUBLE ANGLE QUOTATION MARK",
+        Some("\""),
+    ),
+    ('\u{00D7}', "MULTIPLICATION SIGN", Some("x")),
+    ('\u{2002}', "EN SPACE", Some(" ")),
+    ('\u{2003}', "EM SPACE", Some(" ")),
+    ('\u{2009}', "THIN SPACE", Some(" ")),
+    ('\u{200B}', "ZERO WIDTH SPACE", None),
+    ('\u{2010}', "HYPHEN", Some("-")),
+    ('\u{2011}', "NON-BREAKING HYPHEN", Some("-")),
+    ('\u{2012}', "FIGURE DASH", Some("-")),
+    ('\u{2013}', "EN DASH", Some("-")),
+    ('\u{2014}', "EM DASH", Some("--")),
+    ('\u{2018}', "LEFT SINGLE QUOTATION MARK", Some("'")),
+    ('\u{2019}', "RIGHT SINGLE QUOTATION MARK", Some("'")),
+    ('\u{201C}', "LEFT DOUBLE QUOTATION MARK", Some("\"")),
+    ('\u{201D}', "RIGHT DOUBLE QUOTATION MARK", Some("\"")),
+    ('\u{201E}', "DOUBLE LOW-9 QUOTATION MARK", Some("\"")),
+    ('\u{2022}', "BULLET", Some("*")),
+    ('\u{2026}', "HORIZONTAL ELLIPSIS", Some("...")),
+    ('\u{2032}', "PRIME", Some("'")),
+    ('\u{2033}', "DOUBLE PRIME", Some("\"")),
+    ('\u{2192}', "RIGHTWARDS ARROW", Some("->")),
+    ('\u{21D2}', "RIGHTWARDS DOUBLE ARROW", Some("=>")),
+    ('\u{2212}', "MINUS SIGN", Some("-")),
+    ('\u{2260}', "NOT EQUAL TO", Some("!=")),
+    ('\u{2264}', "LESS-THAN OR EQUAL TO", Some("<=")),
+    ('\u{2265}', "GREATER-THAN OR EQUAL TO", Some(">=")),
+    ('\u{FEFF}', "ZERO WIDTH NO-BREAK SPACE", None),
+];
+
+/// The code point of `c`, followed by its name if known, i.e.
+/// `U+2019 RIGHT SINGLE QUOTATION MARK`, and the ASCII equivalent.
+fn describe(c: char) -> (String, Option<&'static str>) {
+    let code_point = format!("U+{:04X}", u32::from(c));
+    match KNOWN.binary_search_by_key(&c, |&(known, _name, _ascii)| known) {
+        Ok(idx) => {
+            let (_c, name, ascii) = KNOWN[idx];
+            (format!("{} {}", code_point, name), ascii)
+        }
+        Err(_) => (code_point, None),
+    }
+}
+
+/// Reports every non-ASCII character of comments in rust sources.
+pub struct AsciiChecker;
+
+impl AsciiChecker {
+    pub fn new(_config: &<Self as Checker>::Config) -> Result<Self> {
+        Ok(Self)
+    }
+}
+
+impl Checker for AsciiChecker {
+    type Config = ();
+
+    fn detector() -> Detector {
+        Detector::Ascii
+    }
+
+    fn check<'a, 's>(
+        &self,
+        origin: &ContentOrigin,
+        chunks: &'a [CheckableChunk],
+    ) -> Result<Vec<Suggestion<'s>>>
+    where
+        'a: 's,
+    {
+        let mut acc = Vec::new();
+        // only comments are subject to the check, not string literals or
+        // common mark files
+        for chunk in chunks.iter().filter(|chunk| {
+            matches!(
+                chunk.variant().category(),
+                CommentVariantCategory::Doc | CommentVariantCategory::Dev
+            )
+        }) {
+            let plain = chunk.erase_cmark();
+            for (idx, c) in plain.as_str().chars().enumerate() {
+                if c.is_ascii() {
+                    continue;
+                }
+                let (name, ascii) = describe(c);
+                let description = match ascii {
+                    Some(ascii) => format!("Non-ASCII character {}, use `{}` instead", name, ascii),
+                    None => format!("Non-ASCII character {}", name),
+                };
+                for (range, span) in plain.find_spans(idx..idx + 1) {
+                    acc.push(Suggestion {
+                        detector: Detector::Ascii,
+                        range,
+                        span,
+                        origin: origin.clone(),
+                        replacements: ascii.map(ToOwned::to_owned).into_iter().collect(),
+                        chunk,
+                        description: Some(description.clone()),
+                        severity: Severity::Error,
+                    });
+                }
+            }
+        }
+        Ok(acc)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Documentation, ExtractOptions};
+
+    #[test]
+    fn known_is_sorted() {
+        assert!(KNOWN.windows(2).all(|pair| pair[0].0 < pair[1].0));
+    }
+
+    #[test]
+    fn reports_non_ascii() {
+        let docs = Documentation::load_from_str(
+            ContentOrigin::TestEntityRust,
+            "/// It’s a caf\u{e9}.\nstruct X;\n",
+            &ExtractOptions::default(),
+        );
+        let (origin, chunks) = docs.into_iter().next().expect("Contains exactly one file");
+        let suggestions = AsciiChecker.check(&origin, &chunks[..]).unwrap();
+        assert_eq!(suggestions.len(), 2);
+
+        assert_eq!(suggestions[0].word(), "’");
+        assert_eq!(suggestions[0].replacements, vec!["'".to_owned()]);
+        assert_eq!(
+            suggestions[0].description.as_deref(),
+            Some("Non-ASCII character U+2019 RIGHT SINGLE QUOTATION MARK, use `'` instead")
+        );
+        assert_eq!(suggestions[0].span.start.column, 6);
+
+        assert_eq!(suggestions[1].word(), "\u{e9}");
+        assert!(suggestions[1].replacements.is_empty());
+        assert_eq!(
+            suggestions[1].description.as_deref(),
+            Some("Non-ASCII character U+00E9")
+        );
+    }
+
+    #[test]
+    fn skips_string_literals() {
+        let docs = Documentation::load_from_str(
+            ContentOrigin::TestEntityRust,
+            "/// It\u{2019}s fine.\nfn x() -> &'static str {\n    \"caf\u{e9}\"\n}\n",
+            &ExtractOptions {
+                string_literals: true,
+                ..Default::default()
+            },
+        );
+        let (origin, chunks) = docs.into_iter().next().expect("Contains exactly one file");
+        assert!(chunks
+            .iter()
+            .any(|chunk| chunk.variant() == crate::CommentVariant::StringLiteral(0)));
+        let suggestions = AsciiChecker.check(&origin, &chunks[..]).unwrap();
+        assert_eq!(suggestions.len(), 1);
+        assert_eq!(suggestions[0].word(), "\u{2019}");
+    }
+}
@@ -5,7 +5,7 @@
 // use super::tokenize;
 use super::{apply_tokenizer, Checker};
 
-use crate::suggestion::{Detector, Suggestion};
+use crate::suggestion::{Detector, Severity, Suggestion};
 use crate::util::sub_chars;
 use crate::{errors::*, CheckableChunk, ContentOrigin};
 use log::trace;
@@ -61,6 +61,7 @@ impl Checker for DummyChecker {
                     replacements,
                     chunk,
                     description: None,
+                    severity: Severity::Error,
                 };
                 acc.push(suggestion);
             }
@@ -0,0 +1,151 @@
+//! Reports words which appear twice in a row, enabled by
+//! `duplicate_words` of `[checks]`.
+//!
+//! Catches slips like `the the problem`, which are correctly spelled and hence
+//! never reported by the dictionary based checkers.
+
+use super::Checker;
+
+use crate::suggestion::{Detector, Severity, Suggestion};
+use crate::{errors::*, CheckableChunk, ContentOrigin, Range};
+
+/// The character ranges of all words within `text`, where a word is a
+/// consecutive run of alphanumeric characters or apostrophes.
+fn words(text: &str) -> Vec<Range> {
+    let mut words = Vec::new();
+    let mut start = None;
+    let mut end = 0;
+    for (idx, c) in text.chars().enumerate() {
+        if c.is_alphanumeric() || c == '\'' {
+            start.get_or_insert(idx);
+        } else if let Some(start) = start.take() {
+            words.push(start..idx);
+        }
+        end = idx + 1;
+    }
+    if let Some(start) = start {
+        words.push(start..end);
+    }
+    words
+}
+
+/// Find repeated words, separated by nothing but whitespace and compared
+/// ignoring case.
+///
+/// Returns the character range of the second occurrence, including the
+/// whitespace before it, and the word.
+fn find_duplicates(text: &str) -> Vec<(Range, String)> {
+    let chars = text.chars().collect::<Vec<_>>();
+    let word = |range: &Range| chars[range.clone()].iter().collect::<String>();
+    words(text)
+        .windows(2)
+        .filter_map(|pair| {
+            let (first, second) = (&pair[0], &pair[1]);
+            if !chars[first.end..second.start]
+                .iter()
+                .all(|c| c.is_whitespace())
+            {
+                return None;
+            }
+            let first = word(first);
+            if !first.chars().any(char::is_alphabetic) {
+                return None;
+            }
+            if first.to_lowercase() != word(second).to_lowercase() {
+                return None;
+            }
+            Some((pair[0].end..second.end, first))
+        })
+        .collect()
+}
+
+/// Reports every word directly repeated in a chunk.
+pub struct DuplicateWordChecker;
+
+impl DuplicateWordChecker {
+    pub fn new(_config: &<Self as Checker>::Config) -> Result<Self> {
+        Ok(Self)
+    }
+}
+
+impl Checker for DuplicateWordChecker {
+    type Config = ();
+
+    fn detector() -> Detector {
+        Detector::DuplicateWord
+    }
+
+    fn check<'a, 's>(
+        &self,
+        origin: &ContentOrigin,
+        chunks: &'a [CheckableChunk],
+    ) -> Result<Vec<Suggestion<'s>>>
+    where
+        'a: 's,
+    {
+        let mut acc = Vec::new();
+        for chunk in chunks {
+            let plain = chunk.erase_cmark();
+            for (range, word) in find_duplicates(plain.as_str()) {
+                let description = Some(format!("duplicate word \"{}\"", word));
+                // removing the whitespace is only safe within a single line,
+                // otherwise only point to the second occurrence
+                let spans = plain.find_spans(range.clone());
+                let (spans, replacements) = if spans.len() == 1 {
+                    (spans, vec![String::new()])
+                } else {
+                    let word_start = range.end - word.chars().count();
+                    (plain.find_spans(word_start..range.end), Vec::new())
+                };
+                for (range, span) in spans {
+                    acc.push(Suggestion {
+                        detector: Detector::DuplicateWord,
+                        range,
+                        span,
+                        origin: origin.clone(),
+                        replacements: replacements.clone(),
+                        chunk,
+                        description: description.clone(),
+                        severity: Severity::Error,
+                    });
+                }
+            }
+        }
+        Ok(acc)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Documentation, ExtractOptions};
+
+    #[test]
+    fn duplicates() {
+        assert_eq!(
+            find_duplicates("Fix the the problem, it is Is a bug."),
+            vec![(7..11, "the".to_owned()), (26..29, "is".to_owned())]
+        );
+        assert!(find_duplicates("The end. The start, 1 1 and it's fine").is_empty());
+    }
+
+    #[test]
+    fn reports_duplicate() {
+        let docs = Documentation::load_from_str(
+            ContentOrigin::TestEntityRust,
+            "/// Solves the the problem.\nstruct X;\n",
+            &ExtractOptions::default(),
+        );
+        let (origin, chunks) = docs.into_iter().next().expect("Contains exactly one file");
+        let suggestions = DuplicateWordChecker.check(&origin, &chunks[..]).unwrap();
+        assert_eq!(suggestions.len(), 1);
+        assert_eq!(suggestions[0].word(), " the");
+        assert_eq!(suggestions[0].replacements, vec![String::new()]);
+        assert_eq!(
+            suggestions[0].description.as_deref(),
+            Some("duplicate word \"the\"")
+        );
+        assert_eq!(suggestions[0].span.start.column, 14);
+        assert_eq!(suggestions[0].span.end.column, 17);
+    }
+}
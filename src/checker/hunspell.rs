@@ -4,12 +4,12 @@
 //! the individual tokens against the dictionary using the defined affixes. Can
 //! handle multiple dictionaries.
 
-use super::{apply_tokenizer, Checker, Detector, Suggestion};
+use super::{apply_tokenizer, split_at_boundaries, Checker, Detector, Suggestion, WordSource};
 
-use crate::config::{Lang5, WrappedRegex};
+use crate::config::{DictionaryNotFoundError, Lang5, WrappedRegex};
 use crate::documentation::{CheckableChunk, ContentOrigin, PlainOverlay};
-use crate::util::sub_chars;
-use crate::Range;
+use crate::util::{fnv1a, sub_chars, FNV_OFFSET_BASIS};
+use crate::{Range, Severity};
 
 use fs_err as fs;
 use io::Write;
@@ -22,11 +22,14 @@ use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
 use hunspell_rs::Hunspell;
+use unicode_normalization::{is_nfc, UnicodeNormalization};
 
 use crate::errors::*;
 
+use super::phonetic::PhoneticIndex;
 use super::quirks::{
-    replacements_contain_dashed, replacements_contain_dashless, transform, Transformed,
+    find_emails, find_urls, hyphenated_components, is_numeric_like, replacements_contain_dashed,
+    replacements_contain_dashless, transform, Transformed,
 };
 
 static BUILTIN_HUNSPELL_AFF: &[u8] = include_bytes!(concat!(
@@ -39,6 +42,34 @@ static BUILTIN_HUNSPELL_DIC: &[u8] = include_bytes!(concat!(
     "/hunspell-data/en_US.dic"
 ));
 
+/// Phonetic suggestions are only added, if hunspell provides fewer
+/// replacement suggestions than this.
+const PHONETIC_SUGGESTIONS_THRESHOLD: usize = 3;
+
+/// Words with more characters are likely code identifiers.
+const IDENTIFIER_LIKE_MIN_CHARS: usize = 21;
+
+/// Words with a larger share of digits are likely code identifiers.
+const IDENTIFIER_LIKE_DIGIT_RATIO: f32 = 0.3;
+
+/// Describe a mistake, hinting at the likely cause if there are no
+/// `replacements` or the word looks like a code identifier.
+fn describe_mistake(word: &str, replacements: &[String]) -> String {
+    let mut description = "Possible spelling mistake found.".to_owned();
+    if replacements.is_empty() {
+        trace!("No suggestions for >{}<", word);
+        description.push_str(" [no suggestions - possible junk token?]");
+    }
+    let n = word.chars().count();
+    let digits = word.chars().filter(char::is_ascii_digit).count();
+    if n >= IDENTIFIER_LIKE_MIN_CHARS || digits as f32 > n as f32 * IDENTIFIER_LIKE_DIGIT_RATIO {
+        description.push_str(
+            " It might be a code identifier, consider wrapping it in backticks or adding it to `ignore_words`.",
+        );
+    }
+    description
+}
+
 // XXX hunspell does not provide an API for using in-memory dictionary or
 // XXX affix files
 // XXX https://github.com/hunspell/hunspell/issues/721
@@ -91,6 +122,55 @@ fn cache_builtin() -> Result<(PathBuf, PathBuf)> {
     Ok((path_dic, path_aff))
 }
 
+/// Hunspell compares raw bytes, so dictionary entries must use the same
+/// Unicode normalization form as the looked up words, which is NFC.
+///
+/// Returns the path of a NFC normalized copy in the cache directory, if the
+/// dictionary is not NFC normalized already, otherwise the path itself.
+fn normalize_dictionary(path: &Path) -> Result<PathBuf> {
+    let content = match String::from_utf8(fs::read(path)?) {
+        Ok(content) => content,
+        Err(_) => {
+            debug!(
+                "Dictionary {} is not UTF-8, skipping normalization",
+                path.display()
+            );
+            return Ok(path.to_owned());
+        }
+    };
+    if is_nfc(&content) {
+        return Ok(path.to_owned());
+    }
+    let normalized = content.nfc().collect::<String>();
+
+    // a stable hash, so the copy is found again by later builds
+    let hash = fnv1a(FNV_OFFSET_BASIS, path.to_string_lossy().as_bytes());
+    let hash = fnv1a(fnv1a(hash, &[0]), normalized.as_bytes());
+
+    let base = directories::BaseDirs::new()
+        .ok_or_else(|| eyre!("Failed to determine the cache directory, env HOME must be set"))?;
+    let cached = base.cache_dir().join(format!(
+        "cargo-spellcheck/{}/normalized/{:016x}.dic",
+        env!("CARGO_PKG_VERSION"),
+        hash
+    ));
+    if cached.is_file() {
+        return Ok(cached);
+    }
+    fs::create_dir_all(cached.parent().unwrap())?;
+    // write to a temporary file first, so concurrent instances never observe
+    // partial content
+    let tmp = cached.with_extension(format!("{}.tmp", uuid::Uuid::new_v4()));
+    fs::write(&tmp, normalized)?;
+    fs::rename(&tmp, &cached)?;
+    debug!(
+        "Using NFC normalized copy {} of dictionary {}",
+        cached.display(),
+        path.display()
+    );
+    Ok(cached)
+}
+
 /// The value is `true` if string is made of emoji's or Unicode
 /// `VULGAR FRACTION`.
 pub fn consists_of_vulgar_fractions_or_emojis(word: &str) -> bool {
@@ -129,17 +209,60 @@ impl From<Hunspell> for HunspellSafe {
 #[derive(Clone)]
 pub struct HunspellCheckerInner {
     hunspell: HunspellSafe,
+    dictionary: PathBuf,
     transform_regex: Vec<WrappedRegex>,
     allow_concatenated: bool,
     allow_dashed: bool,
     allow_emojis: bool,
+    min_word_length: usize,
+    allow_numeric: bool,
+    skip_emails: bool,
+    skip_urls: bool,
+    split_hyphenated: bool,
     ignorelist: String,
+    word_boundaries: String,
+    phonetic: Option<PhoneticIndex>,
+}
+
+/// Find the `.dic` and `.aff` files of the configured language in the search
+/// dirs, falling back to the builtin dictionary if enabled.
+fn find_dictionary(config: &<HunspellChecker as Checker>::Config) -> Result<(PathBuf, PathBuf)> {
+    config
+        .dictionary()
+        .map(|(dic, aff)| {
+            debug!("Using dic {} and aff {}", dic.display(), aff.display());
+            (dic, aff)
+        })
+        .ok_or_else(|| {
+            Error::from(DictionaryNotFoundError {
+                path: PathBuf::from(config.lang().to_string()).with_extension("dic"),
+                config_key: "Hunspell.lang",
+                search_dirs: config.search_dirs().cloned().collect(),
+            })
+        })
+        .or_else(|e| {
+            if config.use_builtin {
+                Ok(cache_builtin()?)
+            } else {
+                Err(e)
+            }
+        })
 }
 
 impl HunspellCheckerInner {
     fn new(config: &<HunspellChecker as Checker>::Config) -> Result<Self> {
         // TODO allow override
-        let (transform_regex, allow_concatenated, allow_dashed, allow_emojis) = {
+        let (
+            transform_regex,
+            allow_concatenated,
+            allow_dashed,
+            allow_emojis,
+            min_word_length,
+            allow_numeric,
+            skip_emails,
+            skip_urls,
+            split_hyphenated,
+        ) = {
             let quirks = &config.quirks;
             {
                 (
@@ -147,6 +270,11 @@ impl HunspellCheckerInner {
                     quirks.allow_concatenated(),
                     quirks.allow_dashed(),
                     quirks.allow_emojis(),
+                    quirks.min_word_length(),
+                    quirks.allow_numeric(),
+                    quirks.skip_emails(),
+                    quirks.skip_urls(),
+                    quirks.split_hyphenated(),
                 )
             }
         };
@@ -160,64 +288,12 @@ impl HunspellCheckerInner {
         debug_assert!(ignorelist.contains('?'));
 
         // setup hunspell:
-        let search_dirs = config.search_dirs();
-
         let lang = config.lang().to_string();
         let lang = lang.as_str();
 
-        // lookup paths are really just an attempt to provide a dictionary, so be more forgiving
-        // when encountering errors here
-        let (dic, aff): (PathBuf, PathBuf) = search_dirs
-            .into_iter()
-            .filter(|search_dir| {
-                let keep = search_dir.is_dir();
-                if !keep {
-                    // search_dir also contains the default paths, so just silently ignore these
-                    debug!(
-                        "Dictionary search path is not a directory {}",
-                        search_dir.display()
-                    );
-                } else {
-                    debug!(
-                        "Found dictionary search path {}",
-                        search_dir.display()
-                    );
-                }
-                keep
-            })
-            .find_map(|search_dir| {
-                let dic = search_dir.join(lang).with_extension("dic");
-                if !dic.is_file() {
-                    debug!(
-                        "Dictionary path dervied from search dir is not a file {}",
-                        dic.display()
-                    );
-                    return None;
-                }
-                let aff = search_dir.join(lang).with_extension("aff");
-                if !aff.is_file() {
-                    debug!(
-                        "Affixes path dervied from search dir is not a file {}",
-                        aff.display()
-                    );
-                    return None;
-                }
-                debug!("Using dic {} and aff {}", dic.display(), aff.display());
-                Some((dic, aff))
-            })
-            .ok_or_else(|| {
-                eyre!("Failed to find any {lang}.dic / {lang}.aff in any search dir or no search provided",
-                    lang = lang)
-            })
-            .or_else(|e| {
-                if config.use_builtin {
-                    Ok(cache_builtin()?)
-                } else {
-                    Err(e)
-                }
-            })?;
+        let (dictionary, aff) = find_dictionary(config)?;
 
-        let dic = dic.to_str().unwrap();
+        let dic = dictionary.to_str().unwrap();
         let aff = aff.to_str().unwrap();
 
         let mut hunspell = Hunspell::new(aff, dic);
@@ -236,9 +312,15 @@ impl HunspellCheckerInner {
         for extra_dic in config.extra_dictionaries() {
             debug!("Adding extra dictionary {}", extra_dic.display());
             if !extra_dic.is_file() {
-                bail!("Extra dictionary {} is not a file", extra_dic.display())
+                return Err(DictionaryNotFoundError {
+                    path: extra_dic.clone(),
+                    config_key: "Hunspell.extra_dictionaries",
+                    search_dirs: config.search_dirs().cloned().collect(),
+                }
+                .into());
             }
             is_valid_hunspell_dic_path(extra_dic)?;
+            let extra_dic = normalize_dictionary(extra_dic)?;
             if let Some(extra_dic) = extra_dic.to_str() {
                 if !hunspell.add_dictionary(extra_dic) {
                     bail!(
@@ -253,14 +335,30 @@ impl HunspellCheckerInner {
                 )
             }
         }
+        let phonetic = if config.phonetic_suggestions {
+            let dictionaries =
+                std::iter::once(PathBuf::from(dic)).chain(config.extra_dictionaries().cloned());
+            Some(PhoneticIndex::from_dictionaries(dictionaries)?)
+        } else {
+            None
+        };
+
         debug!("Dictionary setup completed successfully.");
         Ok(Self {
             hunspell: HunspellSafe::from(hunspell),
+            dictionary,
             transform_regex,
             allow_concatenated,
             allow_dashed,
             allow_emojis,
+            min_word_length,
+            allow_numeric,
+            skip_emails,
+            skip_urls,
+            split_hyphenated,
             ignorelist,
+            word_boundaries: config.word_boundary_chars.clone(),
+            phonetic,
         })
     }
 }
@@ -305,9 +403,35 @@ impl Checker for HunspellChecker {
             let plain = chunk.erase_cmark();
             trace!("{:?}", &plain);
             let txt = plain.as_str();
-            let hunspell = &*self.hunspell.0;
+            let emails = if self.skip_emails {
+                find_emails(txt)
+            } else {
+                Vec::new()
+            };
+            let urls = if self.skip_urls {
+                find_urls(txt)
+            } else {
+                Vec::new()
+            };
 
-            'tokenization: for range in apply_tokenizer(&self.1, txt) {
+            let chars = txt.chars().collect::<Vec<_>>();
+            let ranges = apply_tokenizer(&self.1, txt)
+                .flat_map(|range| split_at_boundaries(&chars, range, &self.word_boundaries));
+            'tokenization: for range in ranges {
+                if emails
+                    .iter()
+                    .any(|email| email.start < range.end && range.start < email.end)
+                {
+                    trace!(target: "quirks", "Token {:?} is part of an email address, skipping", &range);
+                    continue 'tokenization;
+                }
+                if urls
+                    .iter()
+                    .any(|url| url.start < range.end && range.start < url.end)
+                {
+                    trace!(target: "quirks", "Token {:?} is part of a URL, skipping", &range);
+                    continue 'tokenization;
+                }
                 let word = sub_chars(txt, range.clone());
                 if range.len() == 1
                     && word
@@ -319,47 +443,28 @@ impl Checker for HunspellChecker {
                     continue 'tokenization;
                 }
                 if self.transform_regex.is_empty() {
-                    obtain_suggestions(
-                        &plain,
-                        chunk,
-                        &hunspell,
-                        &origin,
-                        word,
-                        range,
-                        self.allow_concatenated,
-                        self.allow_dashed,
-                        self.allow_emojis,
-                        &mut acc,
-                    )
+                    self.obtain_suggestions(&plain, chunk, &origin, word, range, &mut acc)
                 } else {
                     match transform(&self.transform_regex[..], word.as_str(), range.clone()) {
                         Transformed::Fragments(word_fragments) => {
                             for (range, word_fragment) in word_fragments {
-                                obtain_suggestions(
+                                self.obtain_suggestions(
                                     &plain,
                                     chunk,
-                                    &hunspell,
                                     &origin,
                                     word_fragment.to_owned(),
                                     range,
-                                    self.allow_concatenated,
-                                    self.allow_dashed,
-                                    self.allow_emojis,
                                     &mut acc,
                                 );
                             }
                         }
                         Transformed::Atomic((range, word)) => {
-                            obtain_suggestions(
+                            self.obtain_suggestions(
                                 &plain,
                                 chunk,
-                                &hunspell,
                                 &origin,
                                 word.to_owned(),
                                 range,
-                                self.allow_concatenated,
-                                self.allow_dashed,
-                                self.allow_emojis,
                                 &mut acc,
                             );
                         }
@@ -372,41 +477,95 @@ impl Checker for HunspellChecker {
     }
 }
 
-fn obtain_suggestions<'s>(
-    plain: &PlainOverlay,
-    chunk: &'s CheckableChunk,
-    hunspell: &Hunspell,
-    origin: &ContentOrigin,
-    word: String,
-    range: Range,
-    allow_concatenated: bool,
-    allow_dashed: bool,
-    allow_emojis: bool,
-    acc: &mut Vec<Suggestion<'s>>,
-) {
-    if !hunspell.check(&word) {
+impl HunspellCheckerInner {
+    /// Look up `word` at the plain `range` of `chunk`, applying the configured
+    /// quirks, and add a suggestion to `acc` if it is not accepted.
+    fn obtain_suggestions<'s>(
+        &self,
+        plain: &PlainOverlay,
+        chunk: &'s CheckableChunk,
+        origin: &ContentOrigin,
+        word: String,
+        range: Range,
+        acc: &mut Vec<Suggestion<'s>>,
+    ) {
+        if word.chars().count() < self.min_word_length {
+            trace!(target: "quirks", "Word is shorter than {} characters, treating {} as ok", self.min_word_length, &word);
+            return;
+        }
+        if self.allow_numeric && is_numeric_like(&word) {
+            trace!(target: "quirks", "Found numeric literal or primitive type, treating {} as ok", &word);
+            return;
+        }
+        // dictionaries are commonly NFC normalized, the range still refers to
+        // the original content
+        let word = if is_nfc(&word) {
+            word
+        } else {
+            word.nfc().collect::<String>()
+        };
+        let hunspell = &*self.hunspell.0;
+        if hunspell.check(&word) {
+            trace!(
+                "Found a match for word (plain range: {:?}): >{}<",
+                &range,
+                word
+            );
+            debug!(
+                "Accepting `{}` due to the {}",
+                word,
+                WordSource::SystemDictionary(self.dictionary.clone())
+            );
+            return;
+        }
         trace!("No match for word (plain range: {:?}): >{}<", &range, &word);
-        // get rid of single character suggestions
-        let replacements = hunspell
-            .suggest(&word)
-            .into_iter()
-            .filter(|x| x.len() > 1) // single char suggestions tend to be useless
-            .collect::<Vec<_>>();
 
         // strings made of vulgar fraction or emoji
-        if allow_emojis && consists_of_vulgar_fractions_or_emojis(&word) {
+        if self.allow_emojis && consists_of_vulgar_fractions_or_emojis(&word) {
             trace!(target: "quirks", "Found emoji or vulgar fraction character, treating {} as ok", &word);
             return;
         }
 
-        if allow_concatenated && replacements_contain_dashless(&word, replacements.as_slice()) {
+        // get rid of single character suggestions
+        let mut replacements = hunspell
+            .suggest(&word)
+            .into_iter()
+            .filter(|x| x.len() > 1) // single char suggestions tend to be useless
+            .collect::<Vec<_>>();
+
+        // the dash quirks apply to the compound as a whole, before it is split
+        if self.allow_concatenated && replacements_contain_dashless(&word, replacements.as_slice())
+        {
             trace!(target: "quirks", "Found dashless word in replacement suggestions, treating {} as ok", &word);
             return;
         }
-        if allow_dashed && replacements_contain_dashed(&word, replacements.as_slice()) {
+        if self.allow_dashed && replacements_contain_dashed(&word, replacements.as_slice()) {
             trace!(target: "quirks", "Found dashed word in replacement suggestions, treating {} as ok", &word);
             return;
         }
+        if self.split_hyphenated && word.contains('-') {
+            trace!(target: "quirks", "Checking the components of hyphenated {} individually", &word);
+            for (range, component) in hyphenated_components(&word, range) {
+                self.obtain_suggestions(plain, chunk, origin, component.to_owned(), range, acc);
+            }
+            return;
+        }
+
+        if let Some(ref phonetic) = self.phonetic {
+            if replacements.len() < PHONETIC_SUGGESTIONS_THRESHOLD {
+                let missing = PHONETIC_SUGGESTIONS_THRESHOLD - replacements.len();
+                let additions = phonetic
+                    .lookup(&word)
+                    .into_iter()
+                    .filter(|candidate| !replacements.contains(candidate))
+                    .take(missing)
+                    .collect::<Vec<_>>();
+                trace!(target: "quirks", "Adding phonetic suggestions {:?} for {}", &additions, &word);
+                replacements.extend(additions);
+            }
+        }
+
+        let description = describe_mistake(&word, &replacements);
         for (range, span) in plain.find_spans(range.clone()) {
             acc.push(Suggestion {
                 detector: Detector::Hunspell,
@@ -415,15 +574,10 @@ fn obtain_suggestions<'s>(
                 origin: origin.clone(),
                 replacements: replacements.clone(),
                 chunk,
-                description: Some("Possible spelling mistake found.".to_owned()),
+                description: Some(description.clone()),
+                severity: Severity::Error,
             })
         }
-    } else {
-        trace!(
-            "Found a match for word (plain range: {:?}): >{}<",
-            &range,
-            word
-        );
     }
 }
 
@@ -462,10 +616,111 @@ fn is_valid_hunspell_dic(reader: impl BufRead) -> Result<()> {
     Ok(())
 }
 
+/// Print how `word` is treated, looking it up in each configured dictionary
+/// individually.
+pub(crate) fn explain(word: &str, config: &crate::Config) -> Result<()> {
+    let hunspell_config = config.hunspell.clone().unwrap_or_default();
+
+    println!("Search directories:");
+    for search_dir in hunspell_config.search_dirs() {
+        println!("  {}", search_dir.display());
+    }
+
+    let (dic, aff) = find_dictionary(&hunspell_config)?;
+    let aff = aff
+        .to_str()
+        .ok_or_else(|| eyre!("Failed to convert affix path to str {}", aff.display()))?;
+
+    let mut dictionaries = vec![("Hunspell.lang", dic)];
+    for extra_dic in hunspell_config.extra_dictionaries() {
+        dictionaries.push((
+            "Hunspell.extra_dictionaries",
+            normalize_dictionary(extra_dic)?,
+        ));
+    }
+
+    let mut sources = Vec::new();
+    let mut suggestions = Vec::new();
+    println!("Dictionaries:");
+    for (config_key, dic) in dictionaries {
+        let dic_str = dic
+            .to_str()
+            .ok_or_else(|| eyre!("Failed to convert dictionary path to str {}", dic.display()))?;
+        let hunspell = Hunspell::new(aff, dic_str);
+        let found = hunspell.check(word);
+        println!(
+            "  {} ({}): {}",
+            dic_str,
+            config_key,
+            if found { "found" } else { "not found" }
+        );
+        if found {
+            for analysis in hunspell.analyze(word) {
+                println!("    {}", analysis);
+            }
+            sources.push(WordSource::SystemDictionary(dic));
+        } else if suggestions.is_empty() {
+            suggestions = hunspell.suggest(word);
+        }
+    }
+
+    if config.load_ignored_words()?.contains(word) {
+        sources.push(WordSource::IgnoreList);
+    }
+    for (path, words) in config.load_personal_dictionaries_by_path()? {
+        if words.contains(word) {
+            sources.push(WordSource::PersonalDictionary(path.to_owned()));
+        }
+    }
+    if config.builtin_words().any(|builtin| builtin == word) {
+        sources.push(WordSource::RustWordlist);
+    }
+
+    if !sources.is_empty() {
+        println!("`{}` is accepted due to the", word);
+        for source in sources {
+            println!("  {}", source);
+        }
+    } else {
+        println!("`{}` is not accepted.", word);
+        if !suggestions.is_empty() {
+            println!("Did you mean {}?", suggestions.join(", "));
+        }
+        match config.personal_dictionaries.first() {
+            Some(personal) => println!(
+                "If the spelling is correct, add it to your personal dictionary {}",
+                personal.display()
+            ),
+            None => println!(
+                "If the spelling is correct, add it to a file listed in `personal_dictionaries`, \
+                or to a dictionary in `Hunspell.extra_dictionaries` to also support affixes."
+            ),
+        }
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn mistake_description() {
+        let replacements = vec!["receive".to_owned()];
+        assert_eq!(
+            describe_mistake("receeve", &replacements),
+            "Possible spelling mistake found."
+        );
+        assert!(describe_mistake("xqzt", &[]).ends_with("[no suggestions - possible junk token?]"));
+        assert!(describe_mistake("abc123", &replacements).contains("code identifier"));
+        assert!(describe_mistake("a1b2c3d", &[]).contains("junk token"));
+        assert!(
+            describe_mistake("supercalifragilisticexpialidocious", &replacements)
+                .contains("code identifier")
+        );
+        assert!(!describe_mistake("abcdefgh1", &replacements).contains("code identifier"));
+    }
+
     #[test]
     fn hunspell_dic_format() {
         const GOOD: &str = "2
@@ -543,6 +798,57 @@ bar
         }
     }
 
+    /// Check `content` with an additional dictionary consisting of `words`.
+    fn check_with_extra_dictionary(words: &[&str], content: &str) -> usize {
+        let dic = std::env::temp_dir().join(format!("{}.dic", uuid::Uuid::new_v4()));
+        fs::write(&dic, format!("{}\n{}\n", words.len(), words.join("\n"))).unwrap();
+
+        let config = crate::config::HunspellConfig {
+            skip_os_lookups: true,
+            use_builtin: true,
+            extra_dictionaries: vec![dic.clone()],
+            ..Default::default()
+        };
+        let checker = HunspellChecker::new(&config).expect("Checker creation works");
+        let docs = crate::Documentation::load_from_str(
+            ContentOrigin::TestEntityCommonMark,
+            content,
+            &crate::ExtractOptions::default(),
+        );
+        let (origin, chunks) = docs.into_iter().next().expect("Contains exactly one file");
+        let suggestions = checker.check(&origin, &chunks[..]).expect("Checking works");
+        let _ = fs::remove_file(dic);
+        suggestions.len()
+    }
+
+    #[test]
+    fn nfd_content_nfc_dictionary() {
+        assert_eq!(
+            check_with_extra_dictionary(&["caf\u{e9}"], "A cafe\u{301} visit."),
+            0
+        );
+    }
+
+    #[test]
+    fn nfc_content_nfd_dictionary() {
+        assert_eq!(
+            check_with_extra_dictionary(&["cafe\u{301}"], "A caf\u{e9} visit."),
+            0
+        );
+    }
+
+    #[test]
+    fn hyphenated_compounds() {
+        assert_eq!(
+            check_with_extra_dictionary(&["foo"], "A zero-cost, well-known foo-bar."),
+            0
+        );
+        assert_eq!(
+            check_with_extra_dictionary(&["foo"], "A zero-cosst foo-barr."),
+            2
+        );
+    }
+
     macro_rules! parametrized_vulgar_fraction_or_emoji {
         ($($name:ident: $value:expr,)*) => {
         $(
@@ -3,12 +3,21 @@
 //! Trait to handle additional trackers. Contains also helpers to avoid
 //! re-implementing generic algorithms again and again, i.e. tokenization.
 
-use crate::{CheckableChunk, Config, ContentOrigin, Detector, Suggestion};
+use crate::config::{FileTypeMatcher, WrappedRegex};
+use crate::util::sub_chars;
+use crate::{CheckableChunk, Confidence, Config, ContentOrigin, Detector, Suggestion};
 
 use crate::errors::*;
 
-use log::debug;
+use log::{debug, warn};
+use std::cmp;
+use std::collections::HashMap;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
 
+mod ascii;
+mod duplicate;
 mod tokenize;
 pub(crate) use self::hunspell::HunspellChecker;
 pub(crate) use self::nlprules::NlpRulesChecker;
@@ -16,6 +25,8 @@ pub(crate) use self::tokenize::*;
 
 #[cfg(feature = "hunspell")]
 mod hunspell;
+#[cfg(feature = "hunspell")]
+pub(crate) use self::hunspell::explain;
 
 #[cfg(feature = "nlprules")]
 mod nlprules;
@@ -23,6 +34,11 @@ mod nlprules;
 #[cfg(feature = "hunspell")]
 mod quirks;
 
+#[cfg(feature = "hunspell")]
+mod phonetic;
+
+mod stemming;
+
 /// Implementation for a checker
 pub trait Checker {
     type Config;
@@ -38,16 +54,184 @@ pub trait Checker {
         'a: 's;
 }
 
-/// Check a full document for violations using the tools we have.
+/// Where a word, that is not reported as a mistake, was found to be correct.
 ///
-/// Only configured checkers are used.
-pub struct Checkers {
+/// Only used for diagnostics, with `--verbose` and by `explain`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WordSource {
+    /// Found in the hunspell dictionary at the path, or in one of the extra
+    /// dictionaries loaded alongside.
+    #[cfg_attr(not(feature = "hunspell"), allow(dead_code))]
+    SystemDictionary(PathBuf),
+    /// Listed in the personal dictionary at the path.
+    PersonalDictionary(PathBuf),
+    /// Listed in `ignore_words`, an `ignore_words_files` entry, the baseline
+    /// or a `nocheck` annotation.
+    IgnoreList,
+    /// The line matches one of the `skip_patterns`.
+    SkipPattern,
+    /// Part of the builtin Rust ecosystem wordlist, see `rust_wordlist`.
+    RustWordlist,
+}
+
+impl fmt::Display for WordSource {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::SystemDictionary(path) => write!(formatter, "dictionary {}", path.display()),
+            Self::PersonalDictionary(path) => {
+                write!(formatter, "personal dictionary {}", path.display())
+            }
+            Self::IgnoreList => formatter.write_str("ignore list"),
+            Self::SkipPattern => formatter.write_str("skip pattern"),
+            Self::RustWordlist => formatter.write_str("builtin Rust wordlist"),
+        }
+    }
+}
+
+/// Confidence of `replacement` being the correct fix for `word`, in the range
+/// of `0.0` to `1.0` where `1.0` means identical.
+///
+/// Derived from the Damerau-Levenshtein edit distance, normalized by the
+/// number of characters of the longer of both, so a single edit within a five
+/// letter word yields `0.8`.
+pub fn replacement_confidence(word: &str, replacement: &str) -> Confidence {
+    let len = cmp::max(word.chars().count(), replacement.chars().count());
+    if len == 0 {
+        return Confidence::IDENTICAL;
+    }
+    let distance = strsim::damerau_levenshtein(word, replacement);
+    Confidence::new(1.0 - (cmp::min(distance, len) as f32 / len as f32))
+}
+
+/// Returns `true` if the chunk line the suggestion is located in matches any
+/// of the given skip patterns.
+///
+/// Only the first line is considered for suggestions spanning multiple lines.
+pub(crate) fn matches_skip_pattern(
+    suggestion: &Suggestion<'_>,
+    skip_patterns: &[WrappedRegex],
+) -> bool {
+    if skip_patterns.is_empty() {
+        return false;
+    }
+    let chunk = suggestion.chunk;
+    chunk
+        .find_covered_lines(suggestion.range.clone())
+        .first()
+        .map(|line_range| sub_chars(chunk.as_str(), line_range.clone()))
+        .filter(|line| {
+            skip_patterns
+                .iter()
+                .any(|skip_pattern| skip_pattern.is_match(line.as_str()).unwrap_or(false))
+        })
+        .is_some()
+}
+
+/// Returns `true` if the word the suggestion refers to was marked to be ignored
+/// for the literal it is located in, i.e. by a `// [nocheck: ..]` annotation.
+pub(crate) fn is_annotated_ignore(suggestion: &Suggestion<'_>) -> bool {
+    let chunk = suggestion.chunk;
+    let word = sub_chars(chunk.as_str(), suggestion.range.clone());
+    chunk.is_ignored_word(&suggestion.range, word.as_str())
+}
+
+/// Run both checkers concurrently on the same chunks and collect the
+/// suggestions of both, so a slow checker does not delay the other one.
+fn check_concurrently<'a, 's, A, B>(
+    origin: &ContentOrigin,
+    chunks: &'a [CheckableChunk],
+    first: Option<&A>,
+    second: Option<&B>,
+) -> Result<Vec<Suggestion<'s>>>
+where
+    'a: 's,
+    A: Checker + Sync,
+    B: Checker + Sync,
+{
+    let (first, second) = rayon::join(
+        || first.map(|checker| checker.check(origin, chunks)),
+        || second.map(|checker| checker.check(origin, chunks)),
+    );
+    let mut collective = Vec::<Suggestion<'s>>::with_capacity(chunks.len());
+    if let Some(suggestions) = first {
+        collective.extend(suggestions?);
+    }
+    if let Some(suggestions) = second {
+        collective.extend(suggestions?);
+    }
+    Ok(collective)
+}
+
+/// The checkers of a single configuration.
+struct CheckerSet {
     hunspell: Option<HunspellChecker>,
     nlprule: Option<NlpRulesChecker>,
+    ascii: Option<ascii::AsciiChecker>,
+    duplicate: Option<duplicate::DuplicateWordChecker>,
+    skip_patterns: Vec<WrappedRegex>,
+    ignored_words: HashMap<String, WordSource>,
+    /// The stemmer and the words of `ignored_words` by their stem, unless the
+    /// backends do their own stemming.
+    stemmed: Option<(
+        rust_stemmers::Stemmer,
+        HashMap<String, Vec<(String, WordSource)>>,
+    )>,
 }
 
-impl Checkers {
-    pub fn new(config: Config) -> Result<Self> {
+impl CheckerSet {
+    fn new(config: &Config) -> Result<Self> {
+        let skip_patterns = config.skip_patterns.clone();
+        let mut ignored_words = config
+            .load_ignored_words()?
+            .into_iter()
+            .map(|word| (word, WordSource::IgnoreList))
+            .collect::<HashMap<_, _>>();
+        if config.only_new_words {
+            ignored_words.extend(
+                crate::baseline::load(crate::baseline::path()?)?
+                    .into_iter()
+                    .map(|word| (word, WordSource::IgnoreList)),
+            );
+        }
+        for (path, words) in config.load_personal_dictionaries_by_path()? {
+            for word in words {
+                ignored_words
+                    .entry(word)
+                    .or_insert_with(|| WordSource::PersonalDictionary(path.to_owned()));
+            }
+        }
+        for word in config.builtin_words() {
+            ignored_words
+                .entry(word)
+                .or_insert(WordSource::RustWordlist);
+        }
+        let stemmed = if config.backend_does_stemming {
+            None
+        } else {
+            let lang = config
+                .hunspell
+                .as_ref()
+                .map(|hunspell| hunspell.lang())
+                .unwrap_or_default();
+            let stemmer = stemming::stemmer(lang);
+            if stemmer.is_none() {
+                warn!(
+                    "No stemmer available for {}, comparing words verbatim",
+                    lang
+                );
+            }
+            stemmer.map(|stemmer| {
+                let mut stems = HashMap::<String, Vec<(String, WordSource)>>::new();
+                for (word, source) in ignored_words.iter() {
+                    stems
+                        .entry(stemming::stem(&stemmer, word))
+                        .or_default()
+                        .push((word.clone(), source.clone()));
+                }
+                (stemmer, stems)
+            })
+        };
+
         macro_rules! create_checker {
             ($feature:literal, $checker:ty, $config:expr, $checker_config:expr) => {
                 if !cfg!(feature = $feature) {
@@ -73,16 +257,155 @@ impl Checkers {
         let hunspell = create_checker!(
             "hunspell",
             HunspellChecker,
-            &config,
+            config,
             config.hunspell.as_ref()
         );
         let nlprule = create_checker!(
             "nlprules",
             NlpRulesChecker,
-            &config,
+            config,
             config.nlprules.as_ref()
         );
-        Ok(Self { hunspell, nlprule })
+        let ascii = if config.is_enabled(Detector::Ascii) {
+            debug!("Enabling {} checks.", Detector::Ascii);
+            Some(ascii::AsciiChecker::new(&())?)
+        } else {
+            None
+        };
+        let duplicate = if config.is_enabled(Detector::DuplicateWord) {
+            debug!("Enabling {} checks.", Detector::DuplicateWord);
+            Some(duplicate::DuplicateWordChecker::new(&())?)
+        } else {
+            None
+        };
+        Ok(Self {
+            hunspell,
+            nlprule,
+            ascii,
+            duplicate,
+            skip_patterns,
+            ignored_words,
+            stemmed,
+        })
+    }
+
+    fn check<'a, 's>(
+        &self,
+        origin: &ContentOrigin,
+        chunks: &'a [CheckableChunk],
+    ) -> Result<Vec<Suggestion<'s>>>
+    where
+        'a: 's,
+    {
+        let mut collective = check_concurrently(
+            origin,
+            chunks,
+            self.hunspell.as_ref(),
+            self.nlprule.as_ref(),
+        )?;
+        if let Some(ref ascii) = self.ascii {
+            collective.extend(ascii.check(origin, chunks)?);
+        }
+        if let Some(ref duplicate) = self.duplicate {
+            collective.extend(duplicate.check(origin, chunks)?);
+        }
+
+        collective.retain(|suggestion| match self.accepted_by(suggestion) {
+            Some(source) => {
+                debug!("Accepting `{}` due to the {}", suggestion.word(), source);
+                false
+            }
+            None => true,
+        });
+        collective.sort();
+
+        Ok(collective)
+    }
+
+    /// Why the word of the suggestion is accepted regardless, if it is.
+    fn accepted_by(&self, suggestion: &Suggestion<'_>) -> Option<WordSource> {
+        if is_annotated_ignore(suggestion) {
+            return Some(WordSource::IgnoreList);
+        }
+        if matches_skip_pattern(suggestion, &self.skip_patterns) {
+            return Some(WordSource::SkipPattern);
+        }
+        self.ignored_word(&suggestion.word())
+    }
+
+    /// The list containing `word`, or an inflected form of it.
+    fn ignored_word(&self, word: &str) -> Option<WordSource> {
+        if self.ignored_words.is_empty() {
+            return None;
+        }
+        if let Some(source) = self.ignored_words.get(word) {
+            return Some(source.clone());
+        }
+        let (stemmer, stems) = self.stemmed.as_ref()?;
+        stems
+            .get(&stemming::stem(stemmer, word))?
+            .iter()
+            .find(|(ignored, _source)| stemming::is_inflection(word, ignored))
+            .map(|(_ignored, source)| source.clone())
+    }
+}
+
+/// Check a full document for violations using the tools we have.
+///
+/// Only configured checkers are used. Files matching `[[language_region]]` or
+/// `[[file_type]]` entries of the configuration are checked with a separate
+/// set of checkers per combination of matching entries, created on first use.
+pub struct Checkers {
+    config: Config,
+    language_regions: FileTypeMatcher,
+    file_types: FileTypeMatcher,
+    default: Arc<CheckerSet>,
+    per_file_type: Mutex<HashMap<(Option<usize>, Vec<usize>), Arc<CheckerSet>>>,
+}
+
+impl Checkers {
+    pub fn new(config: Config) -> Result<Self> {
+        let language_regions = FileTypeMatcher::for_language_regions(&config.language_regions)
+            .wrap_err(crate::ConfigurationError)?;
+        let file_types =
+            FileTypeMatcher::new(&config.file_types).wrap_err(crate::ConfigurationError)?;
+        let default = Arc::new(CheckerSet::new(&config).wrap_err(crate::ConfigurationError)?);
+        Ok(Self {
+            config,
+            language_regions,
+            file_types,
+            default,
+            per_file_type: Mutex::new(HashMap::new()),
+        })
+    }
+
+    /// The checkers to use for the file at `path`.
+    fn checker_set(&self, path: &Path) -> Result<Arc<CheckerSet>> {
+        let region = self.language_regions.first_match(path);
+        let matches = self.file_types.matches(path);
+        if region.is_none() && matches.is_empty() {
+            return Ok(Arc::clone(&self.default));
+        }
+        let key = (region, matches);
+        let mut per_file_type = self.per_file_type.lock().unwrap();
+        if let Some(set) = per_file_type.get(&key) {
+            return Ok(Arc::clone(set));
+        }
+        debug!(
+            "Creating checkers for language region {:?} and file type entries {:?}, first used by {}",
+            key.0,
+            key.1,
+            path.display()
+        );
+        let config = match region {
+            Some(region) => self.config.with_language_region(region),
+            None => self.config.clone(),
+        };
+        let set = Arc::new(
+            CheckerSet::new(&config.with_file_types(&key.1)).wrap_err(crate::ConfigurationError)?,
+        );
+        per_file_type.insert(key, Arc::clone(&set));
+        Ok(set)
     }
 }
 
@@ -101,17 +424,7 @@ impl Checker for Checkers {
     where
         'a: 's,
     {
-        let mut collective = Vec::<Suggestion<'s>>::with_capacity(chunks.len());
-        if let Some(ref hunspell) = self.hunspell {
-            collective.extend(hunspell.check(origin, chunks)?);
-        }
-        if let Some(ref nlprule) = self.nlprule {
-            collective.extend(nlprule.check(origin, chunks)?);
-        }
-
-        collective.sort();
-
-        Ok(collective)
+        self.checker_set(origin.as_path())?.check(origin, chunks)
     }
 }
 
@@ -124,9 +437,9 @@ pub mod tests {
     use crate::load_span_from;
     use crate::span::Span;
     use crate::ContentOrigin;
-    use crate::Documentation;
     use crate::LineColumn;
     use crate::Range;
+    use crate::{Documentation, ExtractOptions};
     use std::path::PathBuf;
 
     use crate::fluff_up;
@@ -159,8 +472,14 @@ pub mod tests {
             .is_test(true)
             .try_init();
         let dev_comments = false;
-        let docs =
-            Documentation::load_from_str(ContentOrigin::TestEntityRust, content, dev_comments);
+        let docs = Documentation::load_from_str(
+            ContentOrigin::TestEntityRust,
+            content,
+            &ExtractOptions {
+                dev_comments,
+                ..Default::default()
+            },
+        );
         let (origin, chunks) = docs.into_iter().next().expect("Contains exactly one file");
         let suggestions = dummy::DummyChecker
             .check(&origin, &chunks[..])
@@ -188,6 +507,92 @@ pub mod tests {
         }
     }
 
+    #[test]
+    fn confidence() {
+        assert_eq!(replacement_confidence("house", "house").value(), 1.0);
+        assert_eq!(replacement_confidence("hosue", "house").value(), 0.8);
+        assert_eq!(replacement_confidence("huse", "house").value(), 0.8);
+        assert_eq!(replacement_confidence("abc", "xyz").value(), 0.0);
+        assert_eq!(replacement_confidence("", "").value(), 1.0);
+        assert!(
+            replacement_confidence("receeve", "receive")
+                > replacement_confidence("receeve", "recipe")
+        );
+    }
+
+    #[test]
+    fn skip_pattern_drops_matching_line() {
+        const CONTENT: &str = fluff_up!("keep this", "ERR_NOT_FOUND drop all of it");
+        let docs = Documentation::load_from_str(
+            ContentOrigin::TestEntityRust,
+            CONTENT,
+            &ExtractOptions::default(),
+        );
+        let (origin, chunks) = docs.into_iter().next().expect("Contains exactly one file");
+        let suggestions = dummy::DummyChecker
+            .check(&origin, &chunks[..])
+            .expect("Dummy extraction must never fail");
+
+        let skip_patterns = vec![WrappedRegex::from(
+            fancy_regex::Regex::new(r"^\s*ERR_[A-Z_]+").unwrap(),
+        )];
+        let remaining = suggestions
+            .iter()
+            .filter(|suggestion| !matches_skip_pattern(suggestion, &skip_patterns))
+            .count();
+        assert_eq!(remaining, 2);
+    }
+
+    #[test]
+    fn nocheck_annotation_drops_listed_words() {
+        const CONTENT: &str = fluff_up!("keep fooo and barr // [nocheck: fooo, barr]");
+        let docs = Documentation::load_from_str(
+            ContentOrigin::TestEntityRust,
+            CONTENT,
+            &ExtractOptions::default(),
+        );
+        let (origin, chunks) = docs.into_iter().next().expect("Contains exactly one file");
+        assert_eq!(chunks[0].as_str(), " keep fooo and barr");
+        let suggestions = dummy::DummyChecker
+            .check(&origin, &chunks[..])
+            .expect("Dummy extraction must never fail");
+
+        let remaining = suggestions
+            .iter()
+            .filter(|suggestion| !is_annotated_ignore(suggestion))
+            .map(|suggestion| sub_chars(suggestion.chunk.as_str(), suggestion.range.clone()))
+            .collect::<Vec<_>>();
+        assert_eq!(remaining, vec!["keep".to_owned(), "and".to_owned()]);
+    }
+
+    #[test]
+    fn ignored_words_by_stem() {
+        let mut config = Config::parse(r#"ignore_words = ["tokenizer"]"#).unwrap();
+        config.hunspell = None;
+        config.nlprules = None;
+        let set = CheckerSet::new(&config).unwrap();
+        assert_eq!(set.ignored_word("tokenizer"), Some(WordSource::IgnoreList));
+        assert_eq!(set.ignored_word("tokenizers"), None);
+
+        config.backend_does_stemming = false;
+        let set = CheckerSet::new(&config).unwrap();
+        assert_eq!(set.ignored_word("tokenizer"), Some(WordSource::IgnoreList));
+        assert_eq!(set.ignored_word("tokenizers"), Some(WordSource::IgnoreList));
+        assert_eq!(set.ignored_word("tokenize"), Some(WordSource::IgnoreList));
+        assert_eq!(set.ignored_word("parser"), None);
+        // same stem, but derived rather than inflected
+        assert_eq!(set.ignored_word("token"), None);
+
+        let mut config = Config::parse(r#"ignore_words = ["token"]"#).unwrap();
+        config.hunspell = None;
+        config.nlprules = None;
+        config.backend_does_stemming = false;
+        let set = CheckerSet::new(&config).unwrap();
+        assert_eq!(set.ignored_word("tokens"), Some(WordSource::IgnoreList));
+        assert_eq!(set.ignored_word("tokenizer"), None);
+        assert_eq!(set.ignored_word("tokenizers"), None);
+    }
+
     #[test]
     fn extract_suggestions_simple() {
         const SIMPLE: &str = fluff_up!("two literals");
@@ -259,4 +664,74 @@ pub mod tests {
         ];
         extraction_test_body(dbg!(SIMPLE), EXPECTED_SPANS);
     }
+
+    /// Behaves like the dummy checker, once two checks are running at the
+    /// same time.
+    #[derive(Default)]
+    struct RendezvousChecker {
+        running: Mutex<usize>,
+        arrived: std::sync::Condvar,
+    }
+
+    impl Checker for RendezvousChecker {
+        type Config = ();
+
+        fn detector() -> Detector {
+            Detector::Dummy
+        }
+
+        fn check<'a, 's>(
+            &self,
+            origin: &ContentOrigin,
+            chunks: &'a [CheckableChunk],
+        ) -> Result<Vec<Suggestion<'s>>>
+        where
+            'a: 's,
+        {
+            let mut running = self.running.lock().unwrap();
+            *running += 1;
+            self.arrived.notify_all();
+            // a generous timeout, only hit if the checks run one after another
+            let (_running, timeout) = self
+                .arrived
+                .wait_timeout_while(running, std::time::Duration::from_secs(10), |running| {
+                    *running < 2
+                })
+                .unwrap();
+            if timeout.timed_out() {
+                bail!("Checks did not run concurrently");
+            }
+            dummy::DummyChecker.check(origin, chunks)
+        }
+    }
+
+    #[test]
+    fn checkers_run_concurrently() {
+        let docs = Documentation::load_from_str(
+            ContentOrigin::TestEntityRust,
+            fluff_up!("two words"),
+            &ExtractOptions::default(),
+        );
+        let (origin, chunks) = docs.into_iter().next().expect("Contains exactly one file");
+
+        // do not rely on the number of cores of the machine running the tests
+        let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(2)
+            .build()
+            .unwrap();
+        let checker = RendezvousChecker::default();
+        let suggestions = pool
+            .install(|| check_concurrently(&origin, &chunks[..], Some(&checker), Some(&checker)))
+            .unwrap();
+        assert_eq!(suggestions.len(), 4);
+
+        let suggestions = check_concurrently::<RendezvousChecker, dummy::DummyChecker>(
+            &origin,
+            &chunks[..],
+            None,
+            None,
+        )
+        .unwrap();
+        assert!(suggestions.is_empty());
+    }
 }
@@ -4,7 +4,7 @@
 //! splitting is done in hand-waving way. To be improved.
 
 use super::{Checker, Detector, Suggestion};
-use crate::{CheckableChunk, ContentOrigin};
+use crate::{CheckableChunk, ContentOrigin, Severity};
 
 use crate::errors::*;
 use log::{debug, trace};
@@ -147,6 +147,7 @@ fn check_chunk<'a>(
                     replacements: replacements.iter().map(|x| x.clone()).collect(),
                     chunk,
                     description: Some(message.to_owned()),
+                    severity: Severity::Error,
                 }),
         );
     }
@@ -0,0 +1,153 @@
+//! Phonetic suggestions based on the Soundex algorithm.
+//!
+//! Complements the edit distance based suggestions of hunspell, which tend to
+//! be poor for words that were spelled by sound, i.e. `receeve`.
+
+use crate::errors::*;
+
+use fs_err as fs;
+use std::collections::HashMap;
+use std::io::{self, BufRead};
+use std::path::Path;
+
+/// Calculate the American Soundex code of a word, i.e. `R210` for `receive`.
+///
+/// Returns `None` if the word does not start with an ASCII letter.
+pub(crate) fn soundex(word: &str) -> Option<String> {
+    fn digit(c: char) -> Option<char> {
+        match c {
+            'b' | 'f' | 'p' | 'v' => Some('1'),
+            'c' | 'g' | 'j' | 'k' | 'q' | 's' | 'x' | 'z' => Some('2'),
+            'd' | 't' => Some('3'),
+            'l' => Some('4'),
+            'm' | 'n' => Some('5'),
+            'r' => Some('6'),
+            _ => None,
+        }
+    }
+
+    let mut chars = word.chars().map(|c| c.to_ascii_lowercase());
+    let first = chars.next().filter(char::is_ascii_alphabetic)?;
+
+    let mut code = String::with_capacity(4);
+    code.push(first.to_ascii_uppercase());
+    let mut previous = digit(first);
+    for c in chars {
+        if code.len() == 4 {
+            break;
+        }
+        match c {
+            // `h` and `w` do not separate consonants with the same code
+            'h' | 'w' => continue,
+            c => {
+                let current = digit(c);
+                if let Some(d) = current {
+                    if current != previous {
+                        code.push(d);
+                    }
+                }
+                previous = current;
+            }
+        }
+    }
+    while code.len() < 4 {
+        code.push('0');
+    }
+    Some(code)
+}
+
+/// Lookup of dictionary words by their Soundex code.
+#[derive(Debug, Default, Clone)]
+pub(crate) struct PhoneticIndex {
+    index: HashMap<String, Vec<String>>,
+}
+
+impl PhoneticIndex {
+    /// Create the index from all words of the given hunspell `.dic` files.
+    pub(crate) fn from_dictionaries(
+        dictionaries: impl IntoIterator<Item = impl AsRef<Path>>,
+    ) -> Result<Self> {
+        let mut index = Self::default();
+        for dictionary in dictionaries {
+            let dictionary = dictionary.as_ref();
+            // dictionaries are not necessarily UTF-8 encoded, but only ASCII
+            // words have a Soundex code anyways
+            let content = fs::read(dictionary)?;
+            index.extend_from_reader(io::BufReader::new(content.as_slice()))?;
+        }
+        log::debug!("Phonetic index contains {} codes", index.index.len());
+        Ok(index)
+    }
+
+    /// Add all words of a `.dic` formatted reader.
+    ///
+    /// The first line contains the number of words and is skipped, affix flags
+    /// after `/` are stripped.
+    pub(crate) fn extend_from_reader(&mut self, reader: impl BufRead) -> Result<()> {
+        for line in reader.split(b'\n').skip(1) {
+            let line = String::from_utf8_lossy(&line?).into_owned();
+            let word = line.split('/').next().unwrap_or_default().trim();
+            if let Some(code) = soundex(word) {
+                self.index.entry(code).or_default().push(word.to_owned());
+            }
+        }
+        Ok(())
+    }
+
+    /// Find words which sound like `word`, ordered by their edit distance.
+    pub(crate) fn lookup(&self, word: &str) -> Vec<String> {
+        let mut candidates = soundex(word)
+            .and_then(|code| self.index.get(&code))
+            .map(|candidates| {
+                candidates
+                    .iter()
+                    .filter(|candidate| candidate.as_str() != word)
+                    .cloned()
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default();
+        candidates.sort_by_cached_key(|candidate| {
+            (
+                strsim::damerau_levenshtein(word, candidate),
+                candidate.clone(),
+            )
+        });
+        candidates.dedup();
+        candidates
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn soundex_codes() {
+        assert_eq!(soundex("Robert").as_deref(), Some("R163"));
+        assert_eq!(soundex("Rupert").as_deref(), Some("R163"));
+        assert_eq!(soundex("Ashcraft").as_deref(), Some("A261"));
+        assert_eq!(soundex("Tymczak").as_deref(), Some("T522"));
+        assert_eq!(soundex("Pfister").as_deref(), Some("P236"));
+        assert_eq!(soundex("Lee").as_deref(), Some("L000"));
+        assert_eq!(soundex("receeve"), soundex("receive"));
+        assert_eq!(soundex("42"), None);
+        assert_eq!(soundex(""), None);
+    }
+
+    #[test]
+    fn index_lookup() {
+        const DIC: &str = "4
+receive/DRSZGB
+recipe/MS
+deceive/DRSZG
+rosy/TRP
+";
+        let mut index = PhoneticIndex::default();
+        index.extend_from_reader(DIC.as_bytes()).unwrap();
+        assert_eq!(
+            index.lookup("receeve"),
+            vec!["receive".to_owned(), "recipe".to_owned()]
+        );
+        assert!(index.lookup("xylophone").is_empty());
+    }
+}
@@ -2,8 +2,93 @@
 
 use crate::Range;
 use fancy_regex::Regex;
+use lazy_static::lazy_static;
 use log::{trace, warn};
 
+/// Rust primitive types, which commonly appear in documentation.
+const PRIMITIVE_TYPES: &[&str] = &[
+    "u8", "u16", "u32", "u64", "u128", "usize", "i8", "i16", "i32", "i64", "i128", "isize", "f32",
+    "f64",
+];
+
+/// Returns `true` iff the word is a hex, binary or octal literal, i.e.
+/// `0xDEADBEEF`, a Rust primitive type name such as `u64`, or consists of more
+/// digits than other characters.
+pub(crate) fn is_numeric_like(word: &str) -> bool {
+    lazy_static! {
+        static ref NUMERIC_LITERAL: regex::Regex =
+            regex::Regex::new(r"^0[xXbBoO][0-9a-fA-F_]+$").expect("NUMERIC_LITERAL regex compiles");
+    };
+    if NUMERIC_LITERAL.is_match(word) {
+        return true;
+    }
+    if PRIMITIVE_TYPES.contains(&word) {
+        return true;
+    }
+    let (digits, total) = word.chars().fold((0usize, 0usize), |(digits, total), c| {
+        (digits + c.is_ascii_digit() as usize, total + 1)
+    });
+    digits * 2 > total
+}
+
+/// Find all email addresses of the form `user@domain.tld` within `text`.
+///
+/// Returns the character ranges of the addresses.
+pub(crate) fn find_emails(text: &str) -> Vec<Range> {
+    lazy_static! {
+        static ref EMAIL: regex::Regex =
+            regex::Regex::new(r"\b[\w.%+-]+@[\w-]+(?:\.[\w-]+)*\.\w{2,}\b")
+                .expect("EMAIL regex compiles");
+    };
+    EMAIL
+        .find_iter(text)
+        .map(|m| {
+            let start = text[..m.start()].chars().count();
+            Range {
+                start,
+                end: start + m.as_str().chars().count(),
+            }
+        })
+        .collect()
+}
+
+/// Find all URLs with a scheme, i.e. `https://docs.rs/regex`, within `text`.
+///
+/// Returns the character ranges of the URLs.
+pub(crate) fn find_urls(text: &str) -> Vec<Range> {
+    lazy_static! {
+        static ref URL: regex::Regex = regex::Regex::new(
+            r#"\b[a-zA-Z][a-zA-Z0-9+.-]*://[^\s<>"'`()\[\]]*[^\s<>"'`()\[\].,;:!?]"#
+        )
+        .expect("URL regex compiles");
+    };
+    URL.find_iter(text)
+        .map(|m| {
+            let start = text[..m.start()].chars().count();
+            Range {
+                start,
+                end: start + m.as_str().chars().count(),
+            }
+        })
+        .collect()
+}
+
+/// Split a hyphenated compound into its non-empty components, together with
+/// their character ranges, based on the character range of `word`.
+///
+/// Leading, trailing and repeated hyphens yield no components.
+pub(crate) fn hyphenated_components(word: &str, range: Range) -> Vec<(Range, &str)> {
+    let mut start = range.start;
+    word.split('-')
+        .filter_map(|component| {
+            let len = component.chars().count();
+            let component_range = start..(start + len);
+            start += len + 1;
+            (len > 0).then(|| (component_range, component))
+        })
+        .collect()
+}
+
 /// Returns `true` iff the replacements contains a variant of `word` without
 /// dashes.
 pub(crate) fn replacements_contain_dashless<T: AsRef<str>>(word: &str, replacements: &[T]) -> bool {
@@ -173,6 +258,61 @@ mod tests {
         assert!(replacements_contain_dashless(WORD, REPLACEMENTS));
     }
 
+    #[test]
+    fn numeric_like() {
+        for word in &[
+            "0xDEADBEEF",
+            "0b1010_1111",
+            "0o755",
+            "u8",
+            "i128",
+            "f32",
+            "usize",
+            "1024",
+            "v1234",
+        ] {
+            assert!(is_numeric_like(word), "{} must be numeric like", word);
+        }
+        for word in &["", "hex", "0xGG", "u256", "abc123", "i256", "Isize"] {
+            assert!(!is_numeric_like(word), "{} must not be numeric like", word);
+        }
+    }
+
+    #[test]
+    fn emails() {
+        const TEXT: &str = "Contact rust@rust-lang.org or ïnfo@example.com, but not @ or foo@bar.";
+        let emails = find_emails(TEXT);
+        assert_eq!(emails, vec![8..26, 30..46]);
+        assert_eq!(
+            crate::util::sub_chars(TEXT, emails[1].clone()),
+            "ïnfo@example.com"
+        );
+    }
+
+    #[test]
+    fn urls() {
+        const TEXT: &str = "See https://docs.rs/regex, or (ftp://example.com/a_b). Not foo://";
+        assert_eq!(find_urls(TEXT), vec![4..25, 31..52]);
+    }
+
+    #[test]
+    fn hyphenated() {
+        assert_eq!(
+            hyphenated_components("zero-cost", 10..19),
+            vec![(10..14, "zero"), (15..19, "cost")]
+        );
+        assert_eq!(
+            hyphenated_components("über-größe", 0..10),
+            vec![(0..4, "über"), (5..10, "größe")]
+        );
+        assert_eq!(hyphenated_components("-v", 3..5), vec![(4..5, "v")]);
+        assert_eq!(
+            hyphenated_components("trailing-", 0..9),
+            vec![(0..8, "trailing")]
+        );
+        assert_eq!(hyphenated_components("---", 0..3), vec![]);
+    }
+
     #[test]
     fn transformer() {
         let _ = env_logger::builder()
@@ -0,0 +1,85 @@
+//! Stemming of words, based on the Snowball stemmers.
+//!
+//! Word lists such as `ignore_words` have no affix flags, so only the exact
+//! word is accepted. Comparing stems accepts the inflected forms too, i.e.
+//! `tokenizers` for `tokenizer`. Stemmers also strip derivational suffixes,
+//! i.e. `tokenizer` and `token` share a stem, so the words must only differ by
+//! a short ending as well.
+
+use crate::config::Lang5;
+
+use rust_stemmers::{Algorithm, Stemmer};
+
+/// The stemmer for the language of `lang`, if there is one.
+pub(crate) fn stemmer(lang: Lang5) -> Option<Stemmer> {
+    let algorithm = match lang.lang.to_639_1()? {
+        "ar" => Algorithm::Arabic,
+        "da" => Algorithm::Danish,
+        "de" => Algorithm::German,
+        "el" => Algorithm::Greek,
+        "en" => Algorithm::English,
+        "es" => Algorithm::Spanish,
+        "fi" => Algorithm::Finnish,
+        "fr" => Algorithm::French,
+        "hu" => Algorithm::Hungarian,
+        "it" => Algorithm::Italian,
+        "nb" | "nn" | "no" => Algorithm::Norwegian,
+        "nl" => Algorithm::Dutch,
+        "pt" => Algorithm::Portuguese,
+        "ro" => Algorithm::Romanian,
+        "ru" => Algorithm::Russian,
+        "sv" => Algorithm::Swedish,
+        "ta" => Algorithm::Tamil,
+        "tr" => Algorithm::Turkish,
+        _ => return None,
+    };
+    Some(Stemmer::create(algorithm))
+}
+
+/// The stem of `word`, the stemmers expect lowercase input.
+pub(crate) fn stem(stemmer: &Stemmer, word: &str) -> String {
+    stemmer.stem(&word.to_lowercase()).into_owned()
+}
+
+/// The maximum length of an inflectional ending in characters, i.e. `ing` or
+/// the `e` dropped from `parse` in `parsing`.
+const MAX_INFLECTION_LEN: usize = 3;
+
+/// Whether `word` and `other`, which share a stem, are inflected forms of each
+/// other rather than derived from one another, i.e. `tokenizers` and
+/// `tokenizer` as opposed to `tokenizer` and `token`.
+///
+/// Both may only continue their common prefix with a short ending.
+pub(crate) fn is_inflection(word: &str, other: &str) -> bool {
+    let word = word.to_lowercase();
+    let other = other.to_lowercase();
+    let common = word
+        .chars()
+        .zip(other.chars())
+        .take_while(|(a, b)| a == b)
+        .count();
+    word.chars().count() - common <= MAX_INFLECTION_LEN
+        && other.chars().count() - common <= MAX_INFLECTION_LEN
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn english() {
+        let stemmer = stemmer(Lang5::en_US).expect("English has a stemmer");
+        assert_eq!(stem(&stemmer, "tokenizers"), stem(&stemmer, "tokenizer"));
+        assert_eq!(stem(&stemmer, "Parsing"), stem(&stemmer, "parse"));
+        assert_ne!(stem(&stemmer, "parse"), stem(&stemmer, "tokenizer"));
+    }
+
+    #[test]
+    fn inflections() {
+        assert!(is_inflection("tokenizers", "tokenizer"));
+        assert!(is_inflection("Parsing", "parse"));
+        assert!(is_inflection("parsed", "parse"));
+        assert!(!is_inflection("token", "tokenizer"));
+        assert!(!is_inflection("tokenizer", "token"));
+    }
+}
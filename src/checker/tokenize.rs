@@ -172,6 +172,25 @@ where
         .flatten()
 }
 
+/// Split the token at `range` of `chars` at all of the `boundaries`
+/// characters, which are dropped, so `CO2;H2O` yields `CO2` and `H2O`.
+pub(crate) fn split_at_boundaries(chars: &[char], range: Range, boundaries: &str) -> Vec<Range> {
+    let mut acc = Vec::with_capacity(1);
+    let mut start = range.start;
+    for idx in range.clone() {
+        if boundaries.contains(chars[idx]) {
+            if start < idx {
+                acc.push(start..idx);
+            }
+            start = idx + 1;
+        }
+    }
+    if start < range.end {
+        acc.push(start..range.end);
+    }
+    acc
+}
+
 #[cfg(test)]
 mod tests {
     use crate::sub_chars;
@@ -314,4 +333,18 @@ mod tests {
                 assert_eq!(is, expect);
             });
     }
+
+    #[test]
+    fn split_at_word_boundaries() {
+        let text = "CO2;H2O (int32_t) ;";
+        let chars = text.chars().collect::<Vec<_>>();
+        let boundaries = ",:;?()\"`";
+        assert_eq!(
+            split_at_boundaries(&chars, 0..7, boundaries),
+            vec![0..3, 4..7]
+        );
+        assert_eq!(split_at_boundaries(&chars, 8..17, boundaries), vec![9..16]);
+        assert!(split_at_boundaries(&chars, 18..19, boundaries).is_empty());
+        assert_eq!(split_at_boundaries(&chars, 0..7, ""), vec![0..7]);
+    }
 }
@@ -1,15 +1,16 @@
+use std::collections::{BTreeMap, BTreeSet};
 use std::path::{Path, PathBuf};
 
 use crate::errors::*;
 
 use fs_err as fs;
 use itertools::Itertools;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use std::str::FromStr;
 
 use crate::Action;
 
-use super::Config;
+use super::{Config, HunspellConfig, Lang5, OutputFormat};
 
 use log::{debug, warn};
 
@@ -26,10 +27,13 @@ pub struct ManifestMetadataSpellcheck {
 }
 
 /// Checker types to be derived from the stringly typed arguments.
-#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, Deserialize)]
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, Deserialize, Serialize)]
 pub enum CheckerType {
+    #[serde(alias = "hunspell")]
     Hunspell,
+    #[serde(alias = "nlprules")]
     NlpRules,
+    #[serde(alias = "reflow")]
     Reflow,
 }
 
@@ -86,18 +90,89 @@ impl FromStr for MultipleCheckerTypes {
 #[error("Unknown checker type variant: {0}")]
 pub struct UnknownCheckerTypeVariant(String);
 
+/// When to use colors for the output.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColorChoice {
+    /// Only if `stdout` is a terminal.
+    Auto,
+    /// Always, unless `NO_COLOR` is set.
+    Always,
+    /// Never.
+    Never,
+}
+
+impl FromStr for ColorChoice {
+    type Err = UnknownColorChoice;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.to_lowercase();
+        Ok(match s.as_str() {
+            "auto" => Self::Auto,
+            "always" => Self::Always,
+            "never" => Self::Never,
+            _other => return Err(UnknownColorChoice(s)),
+        })
+    }
+}
+
+#[derive(Debug, Clone, thiserror::Error)]
+#[error("Unknown color choice: {0}, expected one of `auto`, `always` or `never`")]
+pub struct UnknownColorChoice(String);
+
+/// The version, including the versions of the checker backends, i.e.
+/// `0.11.1 (hunspell bundled, nlprule 0.6.4)`.
+const VERSION: &str = concat!(
+    env!("CARGO_PKG_VERSION"),
+    " (",
+    env!("CARGO_SPELLCHECK_BACKENDS"),
+    ")"
+);
+
+/// Listed after the help text, keep in sync with `ExitCode`.
+const EXIT_CODES: &str = "EXIT CODES:
+    0         Success, no mistakes were found
+    1         Mistakes were found, unless overridden with `--code`
+    2         Invalid configuration, i.e. a missing dictionary
+    3         Internal error
+    130       Terminated by a signal";
+
 #[derive(clap::Parser, Debug)]
-#[clap(author, version, about, long_about = None)]
+#[clap(author, version = VERSION, about, long_about = None, after_help = EXIT_CODES)]
 #[clap(rename_all = "kebab-case")]
 #[clap(subcommand_negates_reqs(true))]
 pub struct Args {
-    #[clap(short, long, global(true))]
-    /// Provide a configuration.
+    #[clap(short, long, global(true), alias = "config")]
+    /// Provide a configuration, skipping the lookup of configuration files.
     pub cfg: Option<PathBuf>,
 
     #[clap(flatten)]
     pub verbosity: clap_verbosity_flag::Verbosity,
 
+    #[clap(long, global(true))]
+    /// When to use colors, one of `auto`, `always` or `never`. Colors are
+    /// never used if `NO_COLOR` is set.
+    pub color: Option<ColorChoice>,
+
+    #[clap(long, global(true))]
+    /// Warn about spans of extracted doc comments which exceed the bounds of
+    /// their source file, always enabled for debug builds.
+    pub paranoid: bool,
+
+    #[clap(long, global(true), value_name = "DIR")]
+    /// Search the directory for hunspell dictionaries before the configured
+    /// `search_dirs`. Can be repeated.
+    pub dict: Vec<PathBuf>,
+
+    #[clap(long, global(true), value_name = "LANG")]
+    /// Check with the hunspell dictionary of the given language, i.e. `de_DE`,
+    /// instead of the configured one. Overrides the languages of
+    /// `[[language_region]]` and `[[file_type]]` entries too.
+    pub language: Option<Lang5>,
+
+    #[clap(long, global(true), requires = "language")]
+    /// Keep the languages of `[[language_region]]` and `[[file_type]]`
+    /// entries when overriding the language with `--language`.
+    pub strict_per_file_lang: bool,
+
     // is required, but we use `subcommand_negates_reqs`, so it's not
     // when a command exists
     #[clap(flatten)]
@@ -134,18 +209,258 @@ pub struct Common {
     /// Also check developer comments besides documentation comments.
     pub dev_comments: bool,
 
+    #[clap(long)]
+    /// Also check the content of string literals in rust source files.
+    pub check_string_literals: bool,
+
+    #[clap(long)]
+    /// Also check the string values of attributes listed in
+    /// `attr_keys_to_check`, i.e. `#[error("..")]`.
+    pub check_attrs: bool,
+
+    #[clap(long)]
+    /// Report all non-ASCII characters in doc and developer comments, see
+    /// `ascii_only` of `[checks]`.
+    pub allow_only_ascii: bool,
+
+    #[clap(long)]
+    /// Skip the spell checks, see `checks.spelling`.
+    pub no_spelling: bool,
+
+    #[clap(long)]
+    /// Skip the grammar checks, see `checks.grammar`.
+    pub no_grammar: bool,
+
+    #[clap(long)]
+    /// Report misspellings below the `warn_threshold` as mistakes as well.
+    pub warnings_as_errors: bool,
+
+    #[clap(long)]
+    /// Do not skip files matched by `.gitignore`, `.ignore` or
+    /// `.git/info/exclude` when walking directories.
+    pub no_ignore: bool,
+
     #[clap(short, long)]
     /// The number of worker threads to spawn for the actual processing text.
     pub jobs: Option<usize>,
 
-    #[clap(short = 'm', long, default_value_t = 0_u8)]
+    #[clap(short = 'm', long, default_value_t = 1_u8)]
     /// Return code of the application iff spelling mistakes were found.
     pub code: u8,
 
+    #[clap(long)]
+    /// The maximum number of replacement suggestions shown per mistake,
+    /// overrides the configuration value.
+    pub suggestions: Option<usize>,
+
+    #[clap(
+        long,
+        ignore_case = true,
+        possible_values = OutputFormat::VARIANTS
+    )]
+    /// The output format of found mistakes, one of `human`, `compact`,
+    /// `json`, `gitlab-code-quality`, `azure-devops`, `checkstyle` or
+    /// `rdjsonl`, overrides the configuration value. Defaults to
+    /// `azure-devops` in Azure pipelines.
+    pub format: Option<OutputFormat>,
+
+    #[clap(long)]
+    /// Only print the files which would be checked, relative to the current
+    /// directory, and exit.
+    pub list_files: bool,
+
+    #[clap(long)]
+    /// Do not report words listed in the baseline file, see
+    /// `update-baseline`.
+    pub only_new_words: bool,
+
+    #[clap(long)]
+    /// Do not check files again which were checked without errors before and
+    /// did not change since.
+    pub incremental: bool,
+
+    #[clap(long)]
+    /// Apply the first replacement to at most N mistakes, in file order and
+    /// top to bottom within each file, and only print the remaining ones.
+    pub fix_count: Option<usize>,
+
+    #[clap(long)]
+    /// With `fix`, print a unified diff of the first replacement of every
+    /// mistake, unless below `fix_threshold`, to `stdout` instead of asking,
+    /// without changing any file. Exits with a non-zero code if any file would
+    /// change.
+    pub dry_run: bool,
+
+    #[clap(long, value_name = "N")]
+    /// Stop checking further files once N mistakes were found, the file in
+    /// progress is still reported in full. Exits with a non-zero code then.
+    pub max_errors: Option<usize>,
+
+    #[clap(long)]
+    /// Skip files and directories matching the glob, relative to the current
+    /// directory, in addition to the configured `exclude` globs. Can be
+    /// repeated.
+    pub exclude: Vec<String>,
+
+    #[clap(long)]
+    /// Check the docs of all items of a `rustdoc --output-format json` file
+    /// instead of the source files. Line numbers are approximate.
+    pub rustdoc_json: Option<PathBuf>,
+
+    #[clap(long, conflicts_with = "paths")]
+    /// Read the files to check from `stdin`, one path per line. Empty lines
+    /// and lines starting with `#` are ignored.
+    pub stdin_files: bool,
+
+    #[clap(long, value_name = "PATH", conflicts_with_all = &["paths", "stdin_files"])]
+    /// Read the files to check from the given file, or `stdin` for `-`, one
+    /// path per line. Empty lines and lines starting with `#` are ignored.
+    pub files_from: Option<PathBuf>,
+
+    #[clap(long, conflicts_with_all = &["paths", "stdin_files", "files_from", "rustdoc_json"])]
+    /// Read the output of `rustdoc --test` from `stdin` and only check the doc
+    /// comments of the failed doc tests, i.e. `rustdoc --test 2>&1 | cargo
+    /// spellcheck --rustdoc-output`.
+    pub rustdoc_output: bool,
+
     /// A list of files and directories to check. See `--recursive`.
     pub paths: Vec<PathBuf>,
 }
 
+impl Common {
+    /// The paths to check, read from `stdin` if `--stdin-files` is given or
+    /// from the file given by `--files-from`.
+    fn paths(&self) -> Result<Vec<PathBuf>> {
+        match self.files_from {
+            Some(ref path) if path.as_os_str() != "-" => {
+                let file = fs::File::open(path)?;
+                read_file_list(std::io::BufReader::new(file))
+                    .wrap_err_with(|| eyre!("Failed to read the file list from {}", path.display()))
+            }
+            Some(_) => read_stdin_file_list(),
+            None if self.stdin_files => read_stdin_file_list(),
+            None => Ok(self.paths.clone()),
+        }
+    }
+
+    /// The paths to check and, with `--rustdoc-output`, the lines of the failed
+    /// doc tests within them.
+    fn targets(&self) -> Result<(Vec<PathBuf>, Option<DocTestLines>)> {
+        if self.rustdoc_output {
+            let stdin = std::io::stdin();
+            let lines = parse_rustdoc_output(stdin.lock())
+                .wrap_err("Failed to read the `rustdoc --test` output from stdin")?;
+            if lines.is_empty() {
+                warn!("The `rustdoc --test` output contains no failed doc tests");
+            }
+            Ok((lines.keys().cloned().collect(), Some(lines)))
+        } else {
+            Ok((self.paths()?, None))
+        }
+    }
+}
+
+/// The lines of doc tests per file, 1-indexed, as reported by `rustdoc
+/// --test`.
+pub type DocTestLines = BTreeMap<PathBuf, BTreeSet<usize>>;
+
+/// Parse the failed doc tests from the output of `rustdoc --test`, which are
+/// reported as `test src/lib.rs - foo (line 12) ... FAILED` and as the header
+/// `---- src/lib.rs - foo (line 12) stdout ----` of their output.
+fn parse_rustdoc_output(reader: impl std::io::BufRead) -> Result<DocTestLines> {
+    lazy_static::lazy_static! {
+        static ref FAILED_DOC_TEST: regex::Regex = regex::Regex::new(
+            r"^(?:test|----) (.+?) - .*\(line (\d+)\).*(?: \.\.\. FAILED| stdout ----)$"
+        )
+        .expect("FAILED_DOC_TEST regex compiles");
+    };
+    let mut doc_tests = DocTestLines::new();
+    for line in reader.lines() {
+        let line = line?;
+        if let Some(captures) = FAILED_DOC_TEST.captures(line.trim_end()) {
+            let line = captures[2].parse::<usize>()?;
+            doc_tests
+                .entry(PathBuf::from(&captures[1]))
+                .or_default()
+                .insert(line);
+        }
+    }
+    Ok(doc_tests)
+}
+
+fn read_stdin_file_list() -> Result<Vec<PathBuf>> {
+    let stdin = std::io::stdin();
+    read_file_list(stdin.lock()).wrap_err("Failed to read the file list from stdin")
+}
+
+/// Parse a list of paths, one per line, skipping empty lines and `#` comments.
+fn read_file_list(reader: impl std::io::BufRead) -> Result<Vec<PathBuf>> {
+    let mut paths = Vec::new();
+    for line in reader.lines() {
+        let line = line?;
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        paths.push(PathBuf::from(line));
+    }
+    Ok(paths)
+}
+
+/// Warn if `dir`, given via `--dict`, is not a directory or contains neither
+/// `.dic` nor `.aff` files.
+fn warn_if_no_dictionaries(dir: &Path) {
+    match fs::read_dir(dir) {
+        Ok(entries) => {
+            let found = entries.filter_map(|entry| entry.ok()).any(|entry| {
+                matches!(
+                    entry.path().extension().and_then(|ext| ext.to_str()),
+                    Some("dic") | Some("aff")
+                )
+            });
+            if !found {
+                warn!(
+                    "Dictionary directory {} contains no `.dic` or `.aff` files",
+                    dir.display()
+                );
+            }
+        }
+        Err(_) => warn!("Dictionary directory {} does not exist", dir.display()),
+    }
+}
+
+/// Fail if there is no hunspell dictionary for `language`, given via
+/// `--language`, in the search directories.
+fn ensure_dictionary_available(hunspell: &HunspellConfig, language: Lang5) -> Result<()> {
+    if hunspell.use_builtin && language == Lang5::en_US {
+        return Ok(());
+    }
+    let available = hunspell
+        .available_dictionaries()
+        .into_iter()
+        .filter_map(|(dic, _aff)| {
+            dic.file_stem()
+                .map(|stem| stem.to_string_lossy().into_owned())
+        })
+        .collect::<Vec<_>>();
+    if available.iter().any(|stem| stem == &language.to_string()) {
+        return Ok(());
+    }
+    bail!(
+        "No hunspell dictionary for language {} is installed, searched {}. Available: {}",
+        language,
+        hunspell
+            .search_dirs()
+            .map(|dir| dir.display().to_string())
+            .join(", "),
+        if available.is_empty() {
+            "none".to_owned()
+        } else {
+            available.join(", ")
+        }
+    )
+}
+
 #[derive(Debug, PartialEq, Eq, clap::Subcommand)]
 #[clap(rename_all = "kebab-case")]
 pub enum Sub {
@@ -168,6 +483,34 @@ pub enum Sub {
         common: Common,
     },
 
+    /// Check and write all misspelled words to the baseline file
+    /// `.cargo-spellcheck-baseline.txt`, see `--only-new-words`.
+    UpdateBaseline {
+        #[clap(flatten)]
+        common: Common,
+    },
+
+    /// Print the extracted chunks, with their origin and source mapping, as
+    /// they would be checked.
+    DumpChunks {
+        #[clap(flatten)]
+        common: Common,
+    },
+
+    /// Explain how a word is treated by the configured dictionaries.
+    Explain {
+        /// The word to look up.
+        word: String,
+    },
+
+    /// Migrate the configuration file in use to the current format, printed
+    /// to `stdout` by default.
+    MigrateConfig {
+        #[clap(long)]
+        /// Overwrite the configuration file instead.
+        in_place: bool,
+    },
+
     /// Print the config being in use, default config if none.
     Config {
         #[clap(short, long)]
@@ -187,6 +530,16 @@ pub enum Sub {
         #[clap(alias = "checkers")]
         /// Limit checkers to enable in the generated configuration.
         filter: Option<MultipleCheckerTypes>,
+
+        #[clap(long, conflicts_with_all = &["user", "overwrite"])]
+        /// Print a configuration with a comment for every option to `stdout`,
+        /// to start a configuration file with.
+        generate: bool,
+
+        #[clap(long, conflicts_with_all = &["user", "overwrite", "generate", "filter"])]
+        /// Print the configuration in use with all flags applied to `stdout`,
+        /// preceded by the file it was loaded from and the dictionaries.
+        dump: bool,
     },
 
     /// List all files in depth-first-sorted-order in which they would be
@@ -200,18 +553,66 @@ pub enum Sub {
         /// Do not check the referenced key `readme=` or default `README.md`.
         skip_readme: bool,
 
+        #[clap(long)]
+        /// Do not skip files matched by `.gitignore`, `.ignore` or
+        /// `.git/info/exclude` when walking directories.
+        no_ignore: bool,
+
+        #[clap(long)]
+        /// Skip files and directories matching the glob, relative to the
+        /// current directory. Can be repeated.
+        exclude: Vec<String>,
+
         /// A list of files and directories to check. See `--recursive`.
         paths: Vec<PathBuf>,
     },
 
-    /// Print completions.
+    /// Manage the git `pre-commit` hook, which checks all staged files.
+    Hook {
+        #[clap(subcommand)]
+        /// Install or uninstall the hook.
+        action: HookAction,
+    },
+
+    /// Inspect the dictionaries used by the hunspell checker.
+    Dict {
+        #[clap(subcommand)]
+        /// The dictionary operation.
+        action: DictAction,
+    },
+
+    /// Print the completion script for the given shell to `stdout`.
     Completions {
-        #[clap(long, env="SHELL", parse(try_from_str = load_shell_name))]
-        /// Provide the `shell` for which to generate the completion script.
-        shell: Shell,
+        #[clap(env="SHELL", parse(try_from_str = load_shell_name))]
+        /// The `shell` for which to generate the completion script, one of
+        /// `bash`, `elvish`, `fish`, `powershell` or `zsh`. Defaults to the
+        /// current shell.
+        shell: Option<Shell>,
+
+        #[clap(long = "shell", value_name = "SHELL", parse(try_from_str = load_shell_name))]
+        /// Same as the positional `shell`, takes precedence.
+        shell_flag: Option<Shell>,
     },
 }
 
+/// Operations on the git `pre-commit` hook.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, clap::Subcommand)]
+#[clap(rename_all = "kebab-case")]
+pub enum HookAction {
+    /// Install the hook, appends to an existing `pre-commit` script.
+    Install,
+    /// Remove the lines of a previously installed hook.
+    Uninstall,
+}
+
+/// Operations on the hunspell dictionaries.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, clap::Subcommand)]
+#[clap(rename_all = "kebab-case")]
+pub enum DictAction {
+    /// List all dictionaries found in the search directories.
+    Available,
+}
+
 #[derive(thiserror::Error, Debug, Clone)]
 enum ShellErr {
     #[error("Unknown shell: {shell:?}")]
@@ -248,7 +649,9 @@ impl Args {
         match self.command {
             Some(Sub::Check { ref common, .. })
             | Some(Sub::Fix { ref common, .. })
-            | Some(Sub::Reflow { ref common, .. }) => Some(common),
+            | Some(Sub::Reflow { ref common, .. })
+            | Some(Sub::UpdateBaseline { ref common, .. })
+            | Some(Sub::DumpChunks { ref common, .. }) => Some(common),
             _ => None,
         }
     }
@@ -263,9 +666,25 @@ impl Args {
         derive_job_count(self.common().map(|common| common.jobs).flatten())
     }
 
-    /// Extract the verbosity level
-    pub fn verbosity(&self) -> log::LevelFilter {
-        self.verbosity.log_level_filter()
+    /// Extract the verbosity level, `None` if no verbosity flags are given and
+    /// the log filter is left to the environment.
+    pub fn verbosity(&self) -> Option<log::LevelFilter> {
+        // without flags, the verbosity defaults to errors only
+        if self.verbosity.log_level() == Some(log::Level::Error) {
+            None
+        } else {
+            Some(self.verbosity.log_level_filter())
+        }
+    }
+
+    /// Whether to use colors, based on `NO_COLOR`, `--color` and whether
+    /// `stdout` is a terminal, in that order of precedence.
+    pub fn should_use_color(&self) -> bool {
+        use_color(
+            std::env::var_os("NO_COLOR").map_or(false, |value| !value.is_empty()),
+            self.color,
+            console::user_attended(),
+        )
     }
 
     /// Extract the required action.
@@ -275,10 +694,24 @@ impl Args {
             None | Some(Sub::Check { .. }) => Action::Check,
             Some(Sub::Fix { .. }) => Action::Fix,
             Some(Sub::Reflow { .. }) => Action::Reflow,
+            Some(Sub::UpdateBaseline { .. }) => Action::UpdateBaseline,
+            Some(Sub::DumpChunks { .. }) => Action::DumpChunks,
             Some(Sub::Config { .. }) => unreachable!(),
             Some(Sub::ListFiles { .. }) => Action::ListFiles,
+            Some(Sub::Hook { .. }) => unreachable!(),
+            Some(Sub::Dict { .. }) => unreachable!(),
+            Some(Sub::Explain { .. }) => unreachable!(),
+            Some(Sub::MigrateConfig { .. }) => unreachable!(),
             Some(Sub::Completions { .. }) => unreachable!(),
         };
+        let action = match action {
+            Action::Check | Action::Fix | Action::Reflow
+                if self.common().unwrap_or(&self.common).list_files =>
+            {
+                Action::ListFiles
+            }
+            action => action,
+        };
         log::trace!("Derived action {:?} from flags/args/cmds", action);
         action
     }
@@ -382,8 +815,11 @@ impl Args {
     ///
     /// 1. explicitly specified cli flag, error if it does not exist or parse
     /// 2. `Cargo.toml` metadata (unimplemented), error if it does not exist or parse
-    /// 3. find a `Cargo.toml` and try to find `.config/spellcheck.toml` error if it does not parse
-    /// 4. Fallback to per-user config, error if it does not parse
+    /// 3. find a `Cargo.toml` and try to find `.config/spellcheck.toml` error if it does not parse,
+    ///    in the current working directory first and the workspace root second
+    /// 4. Fallback to per-user config, error if it does not parse, the
+    ///    platform specific `cargo-spellcheck/config.toml` first and the
+    ///    `Config::default_path` second
     /// 5. Default config, error if it does not parse
     ///
     // TODO split the IO operations and lookup dirs.
@@ -452,6 +888,32 @@ impl Args {
             return Ok((cfg, Some(config_path)));
         }
 
+        // 4b. the same for the workspace root, when running within a member crate
+        if let Some(root) = crate::traverse::workspace_root(&cwd).filter(|root| root != &cwd) {
+            if let Some(manifest_path) = look_for_cargo_manifest(&root)? {
+                if let Some((config, config_path)) = load_from_manifest_metadata(&manifest_path)? {
+                    return Ok((config, Some(config_path)));
+                }
+            }
+            let config_path = root.join(".config").join("spellcheck.toml");
+            if let Some(cfg) = Config::load_from(&config_path)? {
+                debug!(
+                    "Using workspace configuration file (4b) {}",
+                    config_path.display()
+                );
+                return Ok((cfg, Some(config_path)));
+            }
+        }
+
+        let user_config_path = Config::user_config_path()?;
+        if let Some(cfg) = Config::load_from(&user_config_path)? {
+            debug!(
+                "Using configuration file (5) {}",
+                user_config_path.display()
+            );
+            return Ok((cfg, Some(user_config_path)));
+        }
+
         let default_config_path = Config::default_path()?;
         if let Some(cfg) = Config::load_from(&default_config_path)? {
             debug!(
@@ -503,16 +965,93 @@ impl Args {
     /// Evaluate the configuration flags, overwrite config values as needed and
     /// provide a new, unified config struct.
     pub fn unified(self) -> Result<(UnifiedArgs, Config)> {
-        let (config, config_path) = self.load_config()?;
+        let (mut config, config_path) = self.load_config()?;
+        let quiet = self.verbosity.is_silent();
+        // `self.common` is only relevant without a sub-command
+        let common = self.common().unwrap_or(&self.common);
+        if let Some(suggestions) = common.suggestions {
+            config.max_suggestions = suggestions;
+        }
+        if let Some(format) = common.format {
+            config.output_format = format;
+        } else if config.output_format == OutputFormat::Human && running_in_azure_pipelines() {
+            config.output_format = OutputFormat::AzureDevOps;
+        }
+        if common.only_new_words {
+            config.only_new_words = true;
+        }
+        if common.incremental {
+            config.incremental = true;
+        }
+        if common.check_attrs {
+            config.check_attrs = true;
+        }
+        if common.allow_only_ascii {
+            config.checks.ascii_only = true;
+        }
+        if common.no_spelling {
+            config.checks.spelling = false;
+        }
+        if common.no_grammar {
+            config.checks.grammar = false;
+        }
+        if common.warnings_as_errors {
+            config.warn_threshold = 0.0;
+        }
+        for dir in &self.dict {
+            warn_if_no_dictionaries(dir);
+        }
+        if let Some(ref mut hunspell) = config.hunspell {
+            hunspell.search_dirs.prepend(self.dict.iter().cloned());
+        }
+        if let Some(language) = self.language {
+            if let Some(ref mut hunspell) = config.hunspell {
+                ensure_dictionary_available(hunspell, language)?;
+                hunspell.lang = language;
+            }
+            if !self.strict_per_file_lang {
+                config.language_regions.clear();
+                for file_type in config.file_types.iter_mut() {
+                    file_type.language = None;
+                }
+            }
+        }
+        if let Some(Sub::ListFiles { ref exclude, .. }) = self.command {
+            config.exclude.extend(exclude.iter().cloned());
+        } else {
+            config.exclude.extend(common.exclude.iter().cloned());
+        }
+        if let Some(Sub::UpdateBaseline { .. }) = self.command {
+            // the baseline must contain all words
+            config.only_new_words = false;
+        }
+        // computed once, it is logged and printed by `config --dump`
+        let dump = matches!(self.command, Some(Sub::Config { dump: true, .. }));
+        let summary = if dump || log::log_enabled!(log::Level::Info) {
+            let summary = config.summary(config_path.as_deref());
+            for line in summary.iter() {
+                log::info!("{}", line);
+            }
+            Some(summary)
+        } else {
+            None
+        };
         let unified = match self.command {
             Some(Sub::Config {
                 stdout,
                 user,
                 overwrite,
                 filter: checkers,
+                generate,
+                dump,
             }) => {
                 let dest_config = match self.cfg {
-                    None if stdout => ConfigWriteDestination::Stdout,
+                    // `--cfg` is the configuration to dump
+                    _ if dump => ConfigWriteDestination::Stdout,
+                    None if stdout || generate => ConfigWriteDestination::Stdout,
+                    _ if generate => {
+                        bail!("`--generate` only prints to `stdout`, redirect it instead.")
+                    }
                     Some(path) => ConfigWriteDestination::File { overwrite, path },
                     None if user => ConfigWriteDestination::File {
                         overwrite,
@@ -523,45 +1062,89 @@ impl Args {
                 UnifiedArgs::Config {
                     dest_config,
                     checker_filter_set: checkers,
+                    commented: generate,
+                    summary: summary.filter(|_| dump),
                 }
             }
+            Some(Sub::Dict { action }) => UnifiedArgs::Dict { action },
+            Some(Sub::Explain { ref word }) => UnifiedArgs::Explain { word: word.clone() },
+            Some(Sub::MigrateConfig { in_place }) => UnifiedArgs::MigrateConfig {
+                config_path,
+                in_place,
+            },
             Some(Sub::ListFiles {
                 ref paths,
                 recursive,
                 skip_readme,
+                no_ignore,
+                ..
             }) => UnifiedArgs::Operate {
                 action: self.action(),
                 config_path,
-                dev_comments: false, // not relevant
+                dev_comments: false,          // not relevant
+                check_string_literals: false, // not relevant
                 skip_readme,
+                no_ignore,
                 recursive,
                 paths: paths.clone(),
+                rustdoc_json: None,
+                doc_test_lines: None,
+                fix_count: None,
+                max_errors: None,
+                dry_run: false,
                 exit_code_override: 1,
+                quiet,
             },
             None => {
                 let common = &self.common;
+                let (paths, doc_test_lines) = common.targets()?;
                 UnifiedArgs::Operate {
-                    action: Action::Check,
+                    action: self.action(),
                     config_path,
                     dev_comments: common.dev_comments || config.dev_comments,
+                    check_string_literals: common.check_string_literals
+                        || config.check_string_literals,
                     skip_readme: common.skip_readme || config.skip_readme,
+                    no_ignore: common.no_ignore,
                     recursive: common.recursive,
-                    paths: common.paths.clone(),
+                    paths,
+                    rustdoc_json: common.rustdoc_json.clone(),
+                    doc_test_lines,
+                    fix_count: common.fix_count,
+                    max_errors: common.max_errors,
+                    dry_run: common.dry_run,
                     exit_code_override: common.code,
+                    quiet,
                 }
             }
             Some(Sub::Reflow { ref common, .. })
             | Some(Sub::Fix { ref common, .. })
-            | Some(Sub::Check { ref common, .. }) => UnifiedArgs::Operate {
-                action: self.action(),
-                config_path,
-                dev_comments: common.dev_comments || config.dev_comments,
-                skip_readme: common.skip_readme || config.skip_readme,
-                recursive: common.recursive,
-                paths: common.paths.clone(),
-                exit_code_override: common.code,
-            },
-            Some(Sub::Completions { .. }) => unreachable!("Was handled earlier. qed"),
+            | Some(Sub::Check { ref common, .. })
+            | Some(Sub::UpdateBaseline { ref common, .. })
+            | Some(Sub::DumpChunks { ref common, .. }) => {
+                let (paths, doc_test_lines) = common.targets()?;
+                UnifiedArgs::Operate {
+                    action: self.action(),
+                    config_path,
+                    dev_comments: common.dev_comments || config.dev_comments,
+                    check_string_literals: common.check_string_literals
+                        || config.check_string_literals,
+                    skip_readme: common.skip_readme || config.skip_readme,
+                    no_ignore: common.no_ignore,
+                    recursive: common.recursive,
+                    paths,
+                    rustdoc_json: common.rustdoc_json.clone(),
+                    doc_test_lines,
+                    fix_count: common.fix_count,
+                    max_errors: common.max_errors,
+                    dry_run: common.dry_run,
+                    exit_code_override: common.code,
+                    quiet,
+                }
+            }
+            Some(Sub::Hook { .. }) | Some(Sub::Completions { .. }) => {
+                unreachable!("Was handled earlier. qed")
+            }
         };
 
         Ok((unified, config))
@@ -583,15 +1166,37 @@ pub enum UnifiedArgs {
     Config {
         dest_config: ConfigWriteDestination,
         checker_filter_set: Option<MultipleCheckerTypes>,
+        commented: bool,
+        /// The summary of the configuration in use, which is printed instead
+        /// of the default configuration, set by `--dump`.
+        summary: Option<Vec<String>>,
+    },
+    Dict {
+        action: DictAction,
+    },
+    Explain {
+        word: String,
+    },
+    MigrateConfig {
+        config_path: Option<PathBuf>,
+        in_place: bool,
     },
     Operate {
         action: Action,
         config_path: Option<PathBuf>,
         dev_comments: bool,
+        check_string_literals: bool,
         skip_readme: bool,
+        no_ignore: bool,
         recursive: bool,
         paths: Vec<PathBuf>,
+        rustdoc_json: Option<PathBuf>,
+        doc_test_lines: Option<DocTestLines>,
+        fix_count: Option<usize>,
+        max_errors: Option<usize>,
+        dry_run: bool,
         exit_code_override: u8,
+        quiet: bool,
     },
 }
 
@@ -605,6 +1210,26 @@ impl UnifiedArgs {
     }
 }
 
+/// See <https://no-color.org/>, any non-empty value of `NO_COLOR` disables
+/// colors.
+fn use_color(no_color: bool, choice: Option<ColorChoice>, is_tty: bool) -> bool {
+    if no_color {
+        return false;
+    }
+    match choice.unwrap_or(ColorChoice::Auto) {
+        ColorChoice::Always => true,
+        ColorChoice::Never => false,
+        ColorChoice::Auto => is_tty,
+    }
+}
+
+/// Azure DevOps sets `TF_BUILD=True` for all pipeline tasks.
+fn running_in_azure_pipelines() -> bool {
+    std::env::var("TF_BUILD")
+        .map(|value| value.eq_ignore_ascii_case("true"))
+        .unwrap_or(false)
+}
+
 /// Try to find a cargo manifest, given a path, that can either be a directory
 /// or a path to a manifest.
 fn look_for_cargo_manifest(base: &Path) -> Result<Option<PathBuf>> {
@@ -702,6 +1327,7 @@ pub fn derive_job_count(jobs: impl Into<Option<usize>>) -> usize {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::Detector;
     use assert_matches::assert_matches;
 
     fn commandline_to_iter(s: &'static str) -> impl Iterator<Item = String> {
@@ -783,14 +1409,23 @@ mod tests {
                 action,
                 config_path: _,
                 dev_comments,
+                check_string_literals,
                 skip_readme,
+                no_ignore: _,
                 recursive,
                 paths,
+                rustdoc_json: _,
+                doc_test_lines: _,
+                fix_count: _,
+                max_errors: _,
+                dry_run: _,
                 exit_code_override,
+                quiet: _,
             } => {
                 assert_eq!(Action::Check, action);
                 assert_eq!(exit_code_override, 77);
                 assert_eq!(dev_comments, true);
+                assert_eq!(check_string_literals, false);
                 assert_eq!(skip_readme, true);
                 assert_eq!(recursive, false);
                 assert_eq!(paths, Vec::<PathBuf>::new());
@@ -798,6 +1433,331 @@ mod tests {
         );
     }
 
+    #[test]
+    fn unify_suggestions_override() {
+        let args = Args::parse(commandline_to_iter(
+            "cargo spellcheck check --suggestions=0",
+        ))
+        .unwrap();
+        let (_unified, config) = args.unified().unwrap();
+        assert_eq!(config.max_suggestions(), 0);
+    }
+
+    #[test]
+    fn unify_format_override() {
+        let args = Args::parse(commandline_to_iter("cargo spellcheck --format compact")).unwrap();
+        let (_unified, config) = args.unified().unwrap();
+        assert_eq!(config.output_format, OutputFormat::Compact);
+
+        assert!(Args::parse(commandline_to_iter("cargo spellcheck --format fancy")).is_err());
+    }
+
+    #[test]
+    fn baseline() {
+        let args = Args::parse(commandline_to_iter("cargo spellcheck --only-new-words")).unwrap();
+        let (_unified, config) = args.unified().unwrap();
+        assert!(config.only_new_words);
+
+        let args = Args::parse(commandline_to_iter(
+            "cargo spellcheck update-baseline --only-new-words",
+        ))
+        .unwrap();
+        assert_eq!(args.action(), Action::UpdateBaseline);
+        let (_unified, config) = args.unified().unwrap();
+        assert!(!config.only_new_words);
+    }
+
+    #[test]
+    fn version() {
+        assert!(VERSION.starts_with(env!("CARGO_PKG_VERSION")));
+        #[cfg(feature = "hunspell")]
+        assert!(VERSION.contains("hunspell bundled"));
+        #[cfg(feature = "nlprules")]
+        assert!(VERSION.contains("nlprule "));
+    }
+
+    #[test]
+    fn exclude() {
+        let args = Args::parse(commandline_to_iter(
+            "cargo spellcheck check --exclude src/gen/** --exclude tests/fixtures/**",
+        ))
+        .unwrap();
+        let (_unified, config) = args.unified().unwrap();
+        assert_eq!(
+            config.exclude,
+            vec!["src/gen/**".to_owned(), "tests/fixtures/**".to_owned()]
+        );
+    }
+
+    #[test]
+    fn color() {
+        let args =
+            Args::parse(commandline_to_iter("cargo spellcheck check --color never")).unwrap();
+        assert_eq!(args.color, Some(ColorChoice::Never));
+        assert!(Args::parse(commandline_to_iter("cargo spellcheck --color rainbow")).is_err());
+
+        assert!(use_color(false, None, true));
+        assert!(!use_color(false, None, false));
+        assert!(use_color(false, Some(ColorChoice::Always), false));
+        assert!(!use_color(false, Some(ColorChoice::Never), true));
+        assert!(!use_color(true, Some(ColorChoice::Always), true));
+    }
+
+    #[test]
+    fn check_attrs() {
+        let args = Args::parse(commandline_to_iter("cargo spellcheck check")).unwrap();
+        let (_unified, config) = args.unified().unwrap();
+        assert!(config.attr_keys().is_empty());
+
+        let args =
+            Args::parse(commandline_to_iter("cargo spellcheck check --check-attrs")).unwrap();
+        let (_unified, config) = args.unified().unwrap();
+        assert_eq!(config.attr_keys(), ["error", "description"]);
+    }
+
+    #[test]
+    fn dict_dirs() {
+        let args = Args::parse(commandline_to_iter(
+            "cargo spellcheck --dict /first --dict /second check src/",
+        ))
+        .unwrap();
+        assert_eq!(
+            args.dict,
+            vec![PathBuf::from("/first"), PathBuf::from("/second")]
+        );
+        let (unified, config) = args.unified().unwrap();
+        assert_matches!(unified, UnifiedArgs::Operate { ref paths, .. } => {
+            assert_eq!(paths, &vec![PathBuf::from("src/")]);
+        });
+        let search_dirs = config.hunspell.unwrap().search_dirs;
+        assert_eq!(
+            &search_dirs.as_ref()[..2],
+            &[PathBuf::from("/first"), PathBuf::from("/second")]
+        );
+    }
+
+    #[test]
+    fn allow_only_ascii() {
+        let args = Args::parse(commandline_to_iter("cargo spellcheck check")).unwrap();
+        let (_unified, config) = args.unified().unwrap();
+        assert!(!config.is_enabled(Detector::Ascii));
+
+        let args = Args::parse(commandline_to_iter(
+            "cargo spellcheck check --allow-only-ascii",
+        ))
+        .unwrap();
+        let (_unified, config) = args.unified().unwrap();
+        assert!(config.is_enabled(Detector::Ascii));
+    }
+
+    #[test]
+    fn max_errors() {
+        let args = Args::parse(commandline_to_iter(
+            "cargo spellcheck check --max-errors 100 src/",
+        ))
+        .unwrap();
+        let (unified, _config) = args.unified().unwrap();
+        assert_matches!(unified, UnifiedArgs::Operate { max_errors, .. } => {
+            assert_eq!(max_errors, Some(100));
+        });
+    }
+
+    #[test]
+    fn exit_code_of_mistakes() {
+        let args = Args::parse(commandline_to_iter("cargo spellcheck check src/")).unwrap();
+        let (unified, _config) = args.unified().unwrap();
+        assert_matches!(unified, UnifiedArgs::Operate { exit_code_override, .. } => {
+            assert_eq!(exit_code_override, 1);
+        });
+        let args =
+            Args::parse(commandline_to_iter("cargo spellcheck check --code 0 src/")).unwrap();
+        let (unified, _config) = args.unified().unwrap();
+        assert_matches!(unified, UnifiedArgs::Operate { exit_code_override, .. } => {
+            assert_eq!(exit_code_override, 0);
+        });
+    }
+
+    #[test]
+    fn no_spelling() {
+        let args = Args::parse(commandline_to_iter("cargo spellcheck check")).unwrap();
+        let (_unified, config) = args.unified().unwrap();
+        assert!(config.is_enabled(Detector::Hunspell));
+
+        let args = Args::parse(commandline_to_iter(
+            "cargo spellcheck check --no-spelling --no-grammar",
+        ))
+        .unwrap();
+        let (_unified, config) = args.unified().unwrap();
+        assert!(!config.is_enabled(Detector::Hunspell));
+        assert!(!config.checks.grammar);
+    }
+
+    #[test]
+    fn dry_run() {
+        let args = Args::parse(commandline_to_iter("cargo spellcheck fix --dry-run src/")).unwrap();
+        let (unified, _config) = args.unified().unwrap();
+        assert_matches!(unified, UnifiedArgs::Operate { action, dry_run, .. } => {
+            assert_eq!(action, Action::Fix);
+            assert!(dry_run);
+        });
+    }
+
+    #[test]
+    fn stdin_files() {
+        let args =
+            Args::parse(commandline_to_iter("cargo spellcheck check --stdin-files")).unwrap();
+        assert_matches!(
+            args.common(),
+            Some(Common {
+                stdin_files: true,
+                ..
+            })
+        );
+
+        assert!(Args::parse(commandline_to_iter(
+            "cargo spellcheck check --stdin-files src/lib.rs"
+        ))
+        .is_err());
+
+        const LIST: &str = "src/lib.rs\n\n# generated\n  README.md  \n";
+        assert_eq!(
+            read_file_list(LIST.as_bytes()).unwrap(),
+            vec![PathBuf::from("src/lib.rs"), PathBuf::from("README.md")]
+        );
+    }
+
+    #[test]
+    fn rustdoc_output() {
+        const OUTPUT: &str = r#"
+running 3 tests
+test src/lib.rs - foo (line 12) ... FAILED
+test src/lib.rs - bar (line 40) ... ok
+test src/parse.rs - parse::Parser::new (line 7) - compile fail ... FAILED
+
+failures:
+
+---- src/lib.rs - foo (line 12) stdout ----
+error[E0425]: cannot find value `x` in this scope
+"#;
+        let doc_tests = parse_rustdoc_output(OUTPUT.as_bytes()).unwrap();
+        assert_eq!(doc_tests.len(), 2);
+        assert_eq!(doc_tests[Path::new("src/lib.rs")], BTreeSet::from([12]));
+        assert_eq!(doc_tests[Path::new("src/parse.rs")], BTreeSet::from([7]));
+
+        let args = Args::parse(commandline_to_iter("cargo spellcheck --rustdoc-output")).unwrap();
+        assert!(args.common.rustdoc_output);
+        assert!(Args::parse(commandline_to_iter(
+            "cargo spellcheck check --rustdoc-output src/"
+        ))
+        .is_err());
+    }
+
+    #[test]
+    fn language_override() {
+        let dir = std::env::temp_dir().join(uuid::Uuid::new_v4().to_string());
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("de_DE.dic"), "1\nWort\n").unwrap();
+        fs::write(dir.join("de_DE.aff"), "SET UTF-8\n").unwrap();
+        let dict = dir.display().to_string();
+
+        let args = Args::parse(
+            commandline_to_iter("cargo spellcheck --language de_DE --dict")
+                .chain(Some(dict.clone()))
+                .chain(Some("check".to_owned())),
+        )
+        .unwrap();
+        assert_eq!(args.language, Some(Lang5::from_str("de_DE").unwrap()));
+        let (_unified, config) = args.unified().unwrap();
+        assert_eq!(config.hunspell.unwrap().lang(), "de_DE");
+
+        let args = Args::parse(
+            commandline_to_iter("cargo spellcheck --language fr_FR --dict")
+                .chain(Some(dict))
+                .chain(Some("check".to_owned())),
+        )
+        .unwrap();
+        let err = args.unified().unwrap_err().to_string();
+        assert!(err.contains("fr_FR"));
+        assert!(err.contains("de_DE"));
+
+        assert!(Args::parse(commandline_to_iter("cargo spellcheck --language german")).is_err());
+        assert!(Args::parse(commandline_to_iter(
+            "cargo spellcheck --strict-per-file-lang"
+        ))
+        .is_err());
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn files_from() {
+        let list = std::env::temp_dir().join(uuid::Uuid::new_v4().to_string());
+        fs::write(&list, "# version controlled\nsrc/lib.rs\n\nREADME.md\n").unwrap();
+        let args = Args::parse(
+            commandline_to_iter("cargo spellcheck check --files-from")
+                .chain(Some(list.display().to_string())),
+        )
+        .unwrap();
+        assert_eq!(
+            args.common().unwrap().paths().unwrap(),
+            vec![PathBuf::from("src/lib.rs"), PathBuf::from("README.md")]
+        );
+        fs::remove_file(&list).unwrap();
+
+        let args =
+            Args::parse(commandline_to_iter("cargo spellcheck check --files-from -")).unwrap();
+        assert_eq!(args.common().unwrap().files_from, Some(PathBuf::from("-")));
+
+        assert!(Args::parse(commandline_to_iter(
+            "cargo spellcheck check --files-from - src/lib.rs"
+        ))
+        .is_err());
+        assert!(Args::parse(commandline_to_iter(
+            "cargo spellcheck check --files-from - --stdin-files"
+        ))
+        .is_err());
+    }
+
+    #[test]
+    fn list_files_flag() {
+        let args = Args::parse(commandline_to_iter(
+            "cargo spellcheck check --list-files --format json",
+        ))
+        .unwrap();
+        assert_eq!(args.action(), Action::ListFiles);
+        let (_unified, config) = args.unified().unwrap();
+        assert_eq!(config.output_format, OutputFormat::Json);
+
+        let args = Args::parse(commandline_to_iter("cargo spellcheck check")).unwrap();
+        assert_eq!(args.action(), Action::Check);
+    }
+
+    #[test]
+    fn dump_chunks() {
+        let args = Args::parse(commandline_to_iter(
+            "cargo spellcheck dump-chunks --dev-comments --format json src/lib.rs",
+        ))
+        .unwrap();
+        assert_eq!(args.action(), Action::DumpChunks);
+        let (unified, config) = args.unified().unwrap();
+        assert_eq!(config.output_format, OutputFormat::Json);
+        assert_matches!(unified, UnifiedArgs::Operate {
+            dev_comments: true,
+            ref paths,
+            ..
+        } => {
+            assert_eq!(paths, &vec![PathBuf::from("src/lib.rs")]);
+        });
+    }
+
+    #[test]
+    fn config_flag_alias() {
+        let args = Args::parse(commandline_to_iter(
+            "cargo spellcheck --config .config/spellcheck.toml check",
+        ))
+        .unwrap();
+        assert_eq!(args.cfg, Some(PathBuf::from(".config/spellcheck.toml")));
+    }
+
     // FIXME checkers interpretation seems to have changed XXX
     #[test]
     fn unify_config() {
@@ -819,6 +1779,8 @@ mod tests {
             UnifiedArgs::Config {
                 dest_config: ConfigWriteDestination::File { overwrite, path },
                 checker_filter_set,
+                commented: false,
+                summary: None,
             } => {
                 assert_eq!(path, PathBuf::from(".config/spellcheck.toml"));
                 assert_eq!(checker_filter_set, Some(MultipleCheckerTypes(vec![CheckerType::NlpRules])));
@@ -827,6 +1789,109 @@ mod tests {
         );
     }
 
+    #[test]
+    fn config_generate() {
+        let args = Args::parse(commandline_to_iter("cargo spellcheck config --generate")).unwrap();
+        let (unified, _config) = args.unified().unwrap();
+        assert_matches!(
+            unified,
+            UnifiedArgs::Config {
+                dest_config: ConfigWriteDestination::Stdout,
+                commented: true,
+                ..
+            }
+        );
+        assert!(Args::parse(commandline_to_iter(
+            "cargo spellcheck config --generate --user"
+        ))
+        .is_err());
+    }
+
+    #[test]
+    fn config_dump() {
+        let args = Args::parse(commandline_to_iter("cargo spellcheck config --dump")).unwrap();
+        let (unified, _config) = args.unified().unwrap();
+        assert_matches!(
+            unified,
+            UnifiedArgs::Config {
+                dest_config: ConfigWriteDestination::Stdout,
+                summary: Some(summary),
+                ..
+            } => {
+                assert!(summary[0].contains("configuration"));
+                assert!(summary.iter().any(|line| line.starts_with("Language ")));
+            }
+        );
+        assert!(Args::parse(commandline_to_iter(
+            "cargo spellcheck config --dump --generate"
+        ))
+        .is_err());
+    }
+
+    #[test]
+    fn hook_subcommand() {
+        assert_matches!(
+            Args::parse(commandline_to_iter("cargo spellcheck hook install")),
+            Ok(Args {
+                command: Some(Sub::Hook {
+                    action: HookAction::Install
+                }),
+                ..
+            })
+        );
+        assert_matches!(
+            Args::parse(commandline_to_iter(
+                "cargo spellcheck migrate-config --in-place"
+            )),
+            Ok(Args {
+                command: Some(Sub::MigrateConfig { in_place: true }),
+                ..
+            })
+        );
+        assert_matches!(
+            Args::parse(commandline_to_iter("cargo spellcheck explain dyrck")),
+            Ok(Args {
+                command: Some(Sub::Explain { ref word }),
+                ..
+            }) if word == "dyrck"
+        );
+        assert_matches!(
+            Args::parse(commandline_to_iter("cargo spellcheck dict available")),
+            Ok(Args {
+                command: Some(Sub::Dict {
+                    action: DictAction::Available
+                }),
+                ..
+            })
+        );
+        assert_matches!(
+            Args::parse(commandline_to_iter("cargo-spellcheck hook uninstall")),
+            Ok(Args {
+                command: Some(Sub::Hook {
+                    action: HookAction::Uninstall
+                }),
+                ..
+            })
+        );
+    }
+
+    #[test]
+    fn completions_cover_subcommands_and_values() {
+        let mut sink = Vec::new();
+        generate_completions(Shell::Bash, &mut sink);
+        let script = String::from_utf8(sink).unwrap();
+        for expected in [
+            "check",
+            "fix",
+            "dict",
+            "config",
+            "--format",
+            "gitlab-code-quality",
+        ] {
+            assert!(script.contains(expected), "Missing {}", expected);
+        }
+    }
+
     #[test]
     fn shell_check_env() {
         assert_matches!(load_shell_name("/usr/bin/zsh"), Ok(Shell::Zsh));
@@ -835,12 +1900,20 @@ mod tests {
 
         static C1: &str = "cargo spellcheck completions --shell zsh";
         assert_matches!(Args::parse(commandline_to_iter(C1)), Ok(Args {
-            command: Some(Sub::Completions { shell }),
+            command: Some(Sub::Completions { shell_flag: Some(shell), .. }),
             ..
         }) => {
             assert_eq!(shell.to_string(), "zsh")
         });
 
+        static C3: &str = "cargo spellcheck completions powershell";
+        assert_matches!(Args::parse(commandline_to_iter(C3)), Ok(Args {
+            command: Some(Sub::Completions { shell: Some(shell), shell_flag: None }),
+            ..
+        }) => {
+            assert_eq!(shell, Shell::PowerShell)
+        });
+
         // `SHELL` is builtin, and hence cannot be unset
         // so a negative test is impossible, an override works though
         // `std::env::remove_var("SHELL");`
@@ -848,7 +1921,7 @@ mod tests {
 
         std::env::set_var("SHELL", "/bin/fish");
         assert_matches!(Args::parse(commandline_to_iter(C2)), Ok(Args {
-            command: Some(Sub::Completions { shell }),
+            command: Some(Sub::Completions { shell: Some(shell), .. }),
             ..
         }) => {
             assert_eq!(shell.to_string(), "fish")
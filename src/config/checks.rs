@@ -0,0 +1,40 @@
+//! Switches for each category of checks, the `[checks]` section.
+use super::hunspell::yes;
+use serde::{Deserialize, Serialize};
+
+/// Categories of checks, each can be disabled independently of the
+/// configuration of the checkers performing it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ChecksConfig {
+    /// Dictionary based spell checking, performed by `Hunspell`. Disabled by
+    /// `--no-spelling`.
+    #[serde(default = "yes")]
+    pub spelling: bool,
+
+    /// Grammar checks, performed by `NlpRules`. Disabled by `--no-grammar`.
+    #[serde(default = "yes")]
+    pub grammar: bool,
+
+    /// Report words which appear twice in a row, i.e. `the the`.
+    #[serde(default)]
+    #[serde(alias = "duplicate-words")]
+    pub duplicate_words: bool,
+
+    /// Report all non-ASCII characters in doc and developer comments.
+    /// Enabled by `--allow-only-ascii`.
+    #[serde(default)]
+    #[serde(alias = "ascii-only")]
+    pub ascii_only: bool,
+}
+
+impl Default for ChecksConfig {
+    fn default() -> Self {
+        Self {
+            spelling: true,
+            grammar: true,
+            duplicate_words: false,
+            ascii_only: false,
+        }
+    }
+}
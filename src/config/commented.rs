@@ -0,0 +1,415 @@
+//! A fully commented configuration, as printed by `config --generate`.
+//!
+//! The options and their defaults are taken from the serialized
+//! configuration, only the comments are kept here, so the generated file never
+//! misses an option. Options without a value by default are added as commented
+//! out examples.
+
+use super::Config;
+use crate::errors::*;
+
+/// Comments of the sections and options, by their dotted path.
+const COMMENTS: &[(&str, &str)] = &[
+    (
+        "config_version",
+        "The version of the configuration format, outdated files are updated
+with `cargo spellcheck migrate-config`.",
+    ),
+    (
+        "dev_comments",
+        "Also check developer comments, `//` and `/* */`, as well as the `#`
+comments of `.cargo/config.toml`. Enabled by `--dev-comments`.",
+    ),
+    (
+        "check_string_literals",
+        "Also check the content of string literals in rust sources.
+Enabled by `--check-string-literals`.",
+    ),
+    (
+        "check_attrs",
+        "Also check the string values of the attributes listed in
+`attr_keys_to_check`. Enabled by `--check-attrs`.",
+    ),
+    (
+        "attr_keys_to_check",
+        "Attribute names whose string values are checked with `check_attrs`,
+i.e. `error` for `#[error(\"..\")]`.",
+    ),
+    (
+        "check_macro_strings",
+        "Names of formatting macros whose string arguments are checked, i.e.
+\"println\" for `println!(\"..\")`. Format specifiers are ignored.",
+    ),
+    (
+        "skip_readme",
+        "Skip the README.md file as defined in the cargo manifest.",
+    ),
+    (
+        "exclude",
+        "Glob patterns of files and directories which are never checked,
+relative to the current working directory, i.e. \"src/gen/**\".",
+    ),
+    (
+        "follow_links",
+        "Descend into symbolic links to directories when walking directories.",
+    ),
+    (
+        "skip_patterns",
+        "Regular expressions matched against each line of a chunk, all
+mistakes on a matching line are ignored.",
+    ),
+    (
+        "max_suggestions",
+        "The maximum number of replacement suggestions shown per mistake,
+at most 20.",
+    ),
+    (
+        "warn_threshold",
+        "Misspellings whose best replacement has a lower confidence, from
+0.0 to 1.0, are reported as warnings, which do not fail the check.",
+    ),
+    (
+        "fix_threshold",
+        "Replacements with a lower confidence, from 0.0 to 1.0, are not
+applied by `--fix-count` and `fix --dry-run`.",
+    ),
+    (
+        "ignore_words_files",
+        "Plain text files with words that are always accepted, one word per
+line, relative to this configuration file.",
+    ),
+    ("ignore_words", "Words that are always accepted."),
+    (
+        "personal_dictionaries",
+        "Personal dictionaries, one word per line with optional affix flags
+after a `/`.",
+    ),
+    (
+        "rust_wordlist",
+        "Accept common terms of the Rust ecosystem, such as `rustdoc`.",
+    ),
+    (
+        "backend_does_stemming",
+        "With `false`, the words of `ignore_words`, the word list files and
+the personal dictionaries are also compared by their stem.",
+    ),
+    (
+        "only_new_words",
+        "Do not report the words of `.cargo-spellcheck-baseline.txt`,
+written by `cargo spellcheck update-baseline`. Enabled by `--only-new-words`.",
+    ),
+    (
+        "incremental",
+        "Skip files which were checked without errors before and did not
+change since. Enabled by `--incremental`.",
+    ),
+    (
+        "output_format",
+        "How mistakes are printed, one of \"human\", \"compact\", \"json\",
+\"gitlab-code-quality\", \"azure-devops\", \"checkstyle\" or \"rdjsonl\".
+Overridden by `--format`.",
+    ),
+    (
+        "display_width",
+        "The width in columns the context of a mistake is truncated to, the
+terminal width or `$COLUMNS` if unset.",
+    ),
+    ("checks", "Switches for each category of checks."),
+    (
+        "checks.spelling",
+        "Dictionary based spell checking by hunspell. Disabled by
+`--no-spelling`.",
+    ),
+    (
+        "checks.grammar",
+        "Grammar checks by nlprules, if enabled. Disabled by `--no-grammar`.",
+    ),
+    (
+        "checks.duplicate_words",
+        "Report words which appear twice in a row, i.e. `the the`.",
+    ),
+    (
+        "checks.ascii_only",
+        "Report every non-ASCII character in doc and developer comments.
+Enabled by `--allow-only-ascii`.",
+    ),
+    ("hunspell", "Dictionary based spell checking with hunspell."),
+    (
+        "hunspell.lang",
+        "Language and name of the `.dic` and `.aff` files, i.e. \"de_DE\".
+Replaced by `--language`.",
+    ),
+    (
+        "hunspell.search_dirs",
+        "Directories searched for dictionaries before the OS specific ones,
+i.e. `/usr/share/hunspell` on Linux.",
+    ),
+    (
+        "hunspell.skip_os_lookups",
+        "Only search the configured `search_dirs`, not the OS specific ones.",
+    ),
+    (
+        "hunspell.use_builtin",
+        "Use the builtin en_US dictionary if none was found.",
+    ),
+    (
+        "hunspell.tokenization_splitchars",
+        "Single character tokens which are never checked.",
+    ),
+    (
+        "hunspell.word_boundary_chars",
+        "Characters which split tokens into words, like whitespace does.",
+    ),
+    (
+        "hunspell.extra_dictionaries",
+        "Additional dictionaries for topic specific lingo, absolute or
+relative to the search dirs.",
+    ),
+    (
+        "hunspell.phonetic_suggestions",
+        "Complement few replacement suggestions with dictionary words that
+sound alike.",
+    ),
+    (
+        "hunspell.quirks",
+        "Additional rules besides dictionary lookups.",
+    ),
+    (
+        "hunspell.quirks.transform_regex",
+        "Regular expressions, words matching one are split into the
+fragments of its capture groups, or accepted without capture groups.",
+    ),
+    (
+        "hunspell.quirks.allow_concatenation",
+        "Accept `alphabeta` if `alpha-beta` is suggested.",
+    ),
+    (
+        "hunspell.quirks.allow_dashes",
+        "Accept `alpha-beta` if `alphabeta` is suggested.",
+    ),
+    ("hunspell.quirks.allow_emojis", "Accept emojis."),
+    (
+        "hunspell.quirks.min_word_length",
+        "Words with fewer characters are never looked up.",
+    ),
+    (
+        "hunspell.quirks.allow_numeric",
+        "Accept numeric literals such as `0xDEADBEEF` and types like `u64`.",
+    ),
+    (
+        "hunspell.quirks.skip_emails",
+        "Skip all words which are part of an email address.",
+    ),
+    (
+        "hunspell.quirks.skip_urls",
+        "Skip all words which are part of a URL with a scheme.",
+    ),
+    (
+        "hunspell.quirks.split_hyphenated",
+        "Check the components of hyphenated compounds such as `zero-cost`
+individually, if the compound is not in the dictionary.",
+    ),
+    (
+        "nlprules",
+        "Grammar checks based on the rules of LanguageTool.",
+    ),
+    (
+        "nlprules.override_rules",
+        "Binary encoded rules used instead of the builtin ones.",
+    ),
+    (
+        "nlprules.override_tokenizer",
+        "Binary encoded tokenizer data used instead of the builtin one.",
+    ),
+    ("reflow", "Settings of `cargo spellcheck reflow`."),
+    (
+        "reflow.max_line_length",
+        "The maximum line length reflown doc comments adhere to.",
+    ),
+    (
+        "language_region",
+        "The language of all files matching the glob `pattern`, the first
+matching entry wins.",
+    ),
+    (
+        "language_region.pattern",
+        "Glob pattern the path of a file is matched against.",
+    ),
+    ("language_region.lang", "Replaces `hunspell.lang`."),
+    (
+        "file_type",
+        "Overrides for all files matching the glob `pattern`, applied in
+order, so later entries win over earlier ones and over
+`[[language_region]]` entries.",
+    ),
+    (
+        "file_type.pattern",
+        "Glob pattern the path of a file is matched against.",
+    ),
+    ("file_type.language", "Replaces `hunspell.lang`."),
+    (
+        "file_type.backends",
+        "Replaces the enabled checkers, \"Hunspell\" and / or \"NlpRules\".",
+    ),
+    (
+        "file_type.ignore_words",
+        "Replaces the top level `ignore_words`.",
+    ),
+];
+
+/// Commented out examples of the options without a value by default, by the
+/// path of their section.
+const EXAMPLES: &[(&str, &str)] = &[
+    ("", "display_width = 80"),
+    (
+        "nlprules",
+        "override_rules = \"/path/to/rules_binencoded.bin\"",
+    ),
+    (
+        "nlprules",
+        "override_tokenizer = \"/path/to/tokenizer_binencoded.bin\"",
+    ),
+];
+
+/// The example of `[[language_region]]` entries, which must follow all
+/// tables.
+const LANGUAGE_REGION_EXAMPLE: &str = r#"[[language_region]]
+pattern = "docs/de/**/*.md"
+lang = "de_DE""#;
+
+/// The example of `[[file_type]]` entries, which must follow all tables.
+const FILE_TYPE_EXAMPLE: &str = r#"[[file_type]]
+pattern = "*.md"
+language = "en_GB"
+backends = ["Hunspell"]
+ignore_words = ["changelog"]"#;
+
+/// Append the comment of `path` to `acc`, fails if there is none.
+fn push_comment(acc: &mut String, path: &str) -> Result<()> {
+    let comment = COMMENTS
+        .iter()
+        .find(|(key, _comment)| *key == path)
+        .map(|(_key, comment)| comment)
+        .ok_or_else(|| eyre!("The option `{}` lacks a comment", path))?;
+    for line in comment.lines() {
+        acc.push_str("# ");
+        acc.push_str(line);
+        acc.push('\n');
+    }
+    Ok(())
+}
+
+/// Append the commented out examples of `section` to `acc`.
+fn push_examples(acc: &mut String, section: &str) -> Result<()> {
+    for (_section, example) in EXAMPLES.iter().filter(|(key, _)| *key == section) {
+        let key = example.split(" = ").next().unwrap_or_default();
+        let path = if section.is_empty() {
+            key.to_owned()
+        } else {
+            format!("{}.{}", section, key)
+        };
+        acc.push('\n');
+        push_comment(acc, &path)?;
+        acc.push_str("# ");
+        acc.push_str(example);
+        acc.push('\n');
+    }
+    Ok(())
+}
+
+impl Config {
+    /// The configuration as TOML, with a comment for every section and
+    /// option, and commented out examples of the options which are unset.
+    pub fn to_commented_toml(&self) -> Result<String> {
+        let toml = self.to_toml()?;
+        let mut acc = String::from(
+            "# Configuration of cargo-spellcheck, as generated by\n\
+             # `cargo spellcheck config --generate`. Options without a value by\n\
+             # default are commented out.\n",
+        );
+        let mut section = String::new();
+        for line in toml.lines() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            if line.starts_with('[') {
+                // tables as well as arrays of tables
+                let header = line.trim_start_matches('[').trim_end_matches(']');
+                push_examples(&mut acc, &section)?;
+                section = header.to_owned();
+                acc.push('\n');
+                push_comment(&mut acc, &section)?;
+            } else if let Some((key, _value)) = line.split_once(" = ") {
+                let path = if section.is_empty() {
+                    key.to_owned()
+                } else {
+                    format!("{}.{}", section, key)
+                };
+                acc.push('\n');
+                push_comment(&mut acc, &path)?;
+            }
+            acc.push_str(line);
+            acc.push('\n');
+        }
+        push_examples(&mut acc, &section)?;
+        for (section, is_empty, example) in [
+            (
+                "language_region",
+                self.language_regions.is_empty(),
+                LANGUAGE_REGION_EXAMPLE,
+            ),
+            ("file_type", self.file_types.is_empty(), FILE_TYPE_EXAMPLE),
+        ] {
+            if !is_empty {
+                continue;
+            }
+            acc.push('\n');
+            push_comment(&mut acc, section)?;
+            for line in example.lines() {
+                acc.push_str("# ");
+                acc.push_str(line);
+                acc.push('\n');
+            }
+        }
+        Ok(acc)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_option_is_commented() {
+        let commented = Config::full().to_commented_toml().unwrap();
+        let lines = commented.lines().collect::<Vec<_>>();
+        for (idx, line) in lines.iter().enumerate() {
+            if !line.is_empty() && !line.starts_with('#') {
+                assert!(
+                    lines[idx - 1].starts_with('#'),
+                    "`{}` lacks a comment",
+                    line
+                );
+            }
+        }
+        // no stale comments of removed options
+        for (path, _comment) in COMMENTS {
+            let key = path.rsplit('.').next().unwrap();
+            assert!(
+                commented.contains(&format!("{} = ", key))
+                    || commented.contains(&format!("[{}]", path))
+                    || commented.contains(&format!("[[{}]]", path)),
+                "`{}` is not an option",
+                path
+            );
+        }
+    }
+
+    #[test]
+    fn commented_is_valid() {
+        let config = Config::full();
+        let commented = config.to_commented_toml().unwrap();
+        let parsed = Config::parse(&commented).unwrap();
+        assert_eq!(parsed.to_toml().unwrap(), config.to_toml().unwrap());
+    }
+}
@@ -0,0 +1,228 @@
+use super::*;
+
+use super::args::CheckerType;
+use globset::{Glob, GlobSet, GlobSetBuilder};
+
+/// Overrides of the top level configuration, for all files matching
+/// `pattern`, configured as `[[file_type]]` entries.
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct FileTypeConfig {
+    /// Glob pattern the path of a file is matched against, i.e. `*.md`.
+    pub pattern: String,
+
+    /// Replaces the hunspell language.
+    #[serde(default)]
+    #[serde(alias = "lang")]
+    pub language: Option<Lang5>,
+
+    /// Replaces the set of enabled checkers.
+    #[serde(default)]
+    #[serde(alias = "checkers")]
+    pub backends: Option<Vec<CheckerType>>,
+
+    /// Replaces the top level `ignore_words`.
+    #[serde(default)]
+    #[serde(alias = "ignore-words")]
+    pub ignore_words: Option<Vec<String>>,
+}
+
+/// The language of all files matching `pattern`, configured as
+/// `[[language_region]]` entries.
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct LanguageRegionConfig {
+    /// Glob pattern the path of a file is matched against, i.e.
+    /// `docs/de/**/*.md`.
+    pub pattern: String,
+
+    /// Replaces the hunspell language.
+    #[serde(alias = "language")]
+    pub lang: Lang5,
+}
+
+/// Matches paths against the patterns of all `[[file_type]]` or
+/// `[[language_region]]` entries.
+#[derive(Debug, Clone)]
+pub struct FileTypeMatcher {
+    set: GlobSet,
+}
+
+impl FileTypeMatcher {
+    pub fn new(file_types: &[FileTypeConfig]) -> Result<Self> {
+        Self::from_patterns(
+            file_types
+                .iter()
+                .map(|file_type| file_type.pattern.as_str()),
+            "file_type",
+        )
+    }
+
+    pub fn for_language_regions(language_regions: &[LanguageRegionConfig]) -> Result<Self> {
+        Self::from_patterns(
+            language_regions
+                .iter()
+                .map(|region| region.pattern.as_str()),
+            "language_region",
+        )
+    }
+
+    fn from_patterns<'a>(patterns: impl Iterator<Item = &'a str>, section: &str) -> Result<Self> {
+        let mut builder = GlobSetBuilder::new();
+        for pattern in patterns {
+            let glob = Glob::new(pattern).wrap_err_with(|| {
+                eyre!("Invalid pattern `{}` of a `[[{}]]` entry", pattern, section)
+            })?;
+            builder.add(glob);
+        }
+        Ok(Self {
+            set: builder.build()?,
+        })
+    }
+
+    /// Indices of all entries matching `path`, in the order of the
+    /// configuration.
+    pub fn matches(&self, path: &Path) -> Vec<usize> {
+        let mut matches = self.set.matches(path);
+        matches.sort_unstable();
+        matches
+    }
+
+    /// Index of the first entry matching `path`.
+    pub fn first_match(&self, path: &Path) -> Option<usize> {
+        self.set.matches(path).into_iter().min()
+    }
+}
+
+impl Config {
+    /// The configuration for a file matching the `[[language_region]]` entry
+    /// with index `idx`, which replaces the hunspell language.
+    pub fn with_language_region(&self, idx: usize) -> Self {
+        let mut config = self.clone();
+        if let (Some(region), Some(hunspell)) =
+            (self.language_regions.get(idx), config.hunspell.as_mut())
+        {
+            hunspell.lang = region.lang;
+        }
+        config
+    }
+
+    /// The configuration for a file matching the `[[file_type]]` entries with
+    /// the given `indices`, which are applied in order, so later entries win.
+    pub fn with_file_types(&self, indices: &[usize]) -> Self {
+        let mut config = self.clone();
+        for file_type in indices.iter().filter_map(|&idx| self.file_types.get(idx)) {
+            if let Some(ref backends) = file_type.backends {
+                if !backends.contains(&CheckerType::Hunspell) {
+                    config.hunspell = None;
+                } else if config.hunspell.is_none() {
+                    config.hunspell = default_hunspell();
+                }
+                if !backends.contains(&CheckerType::NlpRules) {
+                    config.nlprules = None;
+                } else if config.nlprules.is_none() {
+                    config.nlprules = default_nlprules();
+                }
+            }
+            if let Some(language) = file_type.language {
+                if let Some(ref mut hunspell) = config.hunspell {
+                    hunspell.lang = language;
+                }
+            }
+            if let Some(ref ignore_words) = file_type.ignore_words {
+                config.ignore_words = ignore_words.clone();
+            }
+        }
+        config
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn last_entry_wins() {
+        let config = Config::parse(
+            r#"
+ignore_words = ["toplevel"]
+
+[[file_type]]
+pattern = "*.md"
+language = "de_DE"
+backends = ["Hunspell"]
+ignore_words = ["markdown"]
+
+[[file_type]]
+pattern = "**/README.md"
+ignore_words = ["readme"]
+"#,
+        )
+        .unwrap();
+        let matcher = FileTypeMatcher::new(&config.file_types).unwrap();
+
+        assert!(matcher.matches(Path::new("src/lib.rs")).is_empty());
+
+        let matches = matcher.matches(Path::new("/project/docs/guide.md"));
+        assert_eq!(matches, vec![0]);
+        let guide = config.with_file_types(&matches);
+        assert_eq!(guide.hunspell.as_ref().unwrap().lang(), "de_DE");
+        assert!(guide.nlprules.is_none());
+        assert_eq!(guide.ignore_words, vec!["markdown".to_owned()]);
+
+        let matches = matcher.matches(Path::new("/project/README.md"));
+        assert_eq!(matches, vec![0, 1]);
+        let readme = config.with_file_types(&matches);
+        assert_eq!(readme.hunspell.as_ref().unwrap().lang(), "de_DE");
+        assert_eq!(readme.ignore_words, vec!["readme".to_owned()]);
+
+        assert_eq!(config.ignore_words, vec!["toplevel".to_owned()]);
+    }
+
+    #[test]
+    fn language_regions() {
+        let config = Config::parse(
+            r#"
+[Hunspell]
+lang = "en_US"
+
+[[language_region]]
+pattern = "docs/de/**/*.md"
+lang = "de_DE"
+
+[[language_region]]
+pattern = "docs/**/*.md"
+lang = "en_GB"
+"#,
+        )
+        .unwrap();
+        assert!(config.file_types.is_empty());
+        let matcher = FileTypeMatcher::for_language_regions(&config.language_regions).unwrap();
+
+        // both entries match, the first one wins
+        let region = matcher.first_match(Path::new("docs/de/intro/start.md"));
+        assert_eq!(region, Some(0));
+        let german = config.with_language_region(0);
+        assert_eq!(german.hunspell.as_ref().unwrap().lang(), "de_DE");
+
+        let region = matcher.first_match(Path::new("docs/en/intro/start.md"));
+        assert_eq!(region, Some(1));
+        let british = config.with_language_region(1);
+        assert_eq!(british.hunspell.as_ref().unwrap().lang(), "en_GB");
+
+        assert_eq!(matcher.first_match(Path::new("src/lib.rs")), None);
+        assert_eq!(config.hunspell.as_ref().unwrap().lang(), "en_US");
+    }
+
+    #[test]
+    fn invalid_pattern() {
+        let config = Config::parse(
+            r#"
+[[file_type]]
+pattern = "[unclosed"
+"#,
+        )
+        .unwrap();
+        assert!(FileTypeMatcher::new(&config.file_types).is_err());
+    }
+}
@@ -7,10 +7,14 @@ use crate::errors::*;
 
 use serde::{Deserialize, Serialize};
 
-const fn yes() -> bool {
+pub(super) const fn yes() -> bool {
     true
 }
 
+const fn default_min_word_length() -> usize {
+    3
+}
+
 #[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct Quirks {
     /// A regular expression, whose capture groups will be checked, instead of
@@ -30,6 +34,26 @@ pub struct Quirks {
     /// Treats sequences of emojis as OK.
     #[serde(default = "yes")]
     pub allow_emojis: bool,
+    /// Tokens with fewer characters are never looked up in the dictionary.
+    /// Applies to the fragments obtained via `transform_regex` as well.
+    #[serde(default = "default_min_word_length")]
+    pub min_word_length: usize,
+    /// Treats numeric literals such as `0xDEADBEEF`, mostly numeric tokens and
+    /// Rust primitive type names such as `u64` as OK.
+    #[serde(default = "yes")]
+    pub allow_numeric: bool,
+    /// Skips all tokens which are part of an email address, i.e.
+    /// `contact@example.com`.
+    #[serde(default = "yes")]
+    pub skip_emails: bool,
+    /// Skips all tokens which are part of a URL with a scheme, i.e.
+    /// `https://docs.rs/`.
+    #[serde(default = "yes")]
+    pub skip_urls: bool,
+    /// Checks the components of hyphenated compounds, i.e. `zero-cost`,
+    /// individually if the compound is not in the dictionary.
+    #[serde(default = "yes")]
+    pub split_hyphenated: bool,
 }
 
 impl Default for Quirks {
@@ -39,6 +63,11 @@ impl Default for Quirks {
             allow_concatenation: false,
             allow_dashes: false,
             allow_emojis: true,
+            min_word_length: default_min_word_length(),
+            allow_numeric: true,
+            skip_emails: true,
+            skip_urls: true,
+            split_hyphenated: true,
         }
     }
 }
@@ -56,6 +85,26 @@ impl Quirks {
         self.allow_emojis
     }
 
+    pub(crate) const fn min_word_length(&self) -> usize {
+        self.min_word_length
+    }
+
+    pub(crate) const fn allow_numeric(&self) -> bool {
+        self.allow_numeric
+    }
+
+    pub(crate) const fn skip_emails(&self) -> bool {
+        self.skip_emails
+    }
+
+    pub(crate) const fn skip_urls(&self) -> bool {
+        self.skip_urls
+    }
+
+    pub(crate) const fn split_hyphenated(&self) -> bool {
+        self.split_hyphenated
+    }
+
     pub(crate) fn transform_regex(&self) -> &[WrappedRegex] {
         &self.transform_regex
     }
@@ -65,6 +114,12 @@ fn default_tokenization_splitchars() -> String {
     "\",;:.!?#(){}[]|/_-‒'`&@§¶…".to_owned()
 }
 
+/// `.` and `!` are kept out, to not split abbreviations like `e.g.`, file
+/// names and `ink!`, and `-` is covered by `split_hyphenated`.
+fn default_word_boundary_chars() -> String {
+    ",:;?()\"`".to_owned()
+}
+
 #[derive(Deserialize, Serialize, Debug, Clone)]
 #[serde(deny_unknown_fields)]
 pub struct HunspellConfig {
@@ -91,10 +146,22 @@ pub struct HunspellConfig {
     #[serde(default = "default_tokenization_splitchars")]
     pub tokenization_splitchars: String,
 
+    /// Characters which split a token of the tokenizer into multiple words,
+    /// like whitespace does.
+    #[serde(default = "default_word_boundary_chars")]
+    pub word_boundary_chars: String,
+
     /// Additional dictionaries for topic specific lingo.
     #[serde(default)]
     pub extra_dictionaries: Vec<PathBuf>,
+
+    /// Complement few replacement suggestions with dictionary words that
+    /// sound alike, based on their Soundex code.
+    #[serde(default)]
+    pub phonetic_suggestions: bool,
+
     /// Additional quirks besides dictionary lookups.
+    // must be last, tables follow all values in toml
     #[serde(default)]
     pub quirks: Quirks,
 }
@@ -106,7 +173,9 @@ impl Default for HunspellConfig {
             search_dirs: SearchDirs::default(),
             extra_dictionaries: Vec::default(),
             quirks: Quirks::default(),
+            phonetic_suggestions: false,
             tokenization_splitchars: default_tokenization_splitchars(),
+            word_boundary_chars: default_word_boundary_chars(),
             skip_os_lookups: false,
             use_builtin: true,
         }
@@ -126,6 +195,48 @@ impl HunspellConfig {
         self.extra_dictionaries.iter()
     }
 
+    /// The `.dic` and `.aff` files of the configured language, from the first
+    /// search dir which contains both.
+    pub fn dictionary(&self) -> Option<(PathBuf, PathBuf)> {
+        let lang = self.lang.to_string();
+
+        // lookup paths are really just an attempt to provide a dictionary, so be more forgiving
+        // when encountering errors here
+        self.search_dirs()
+            .filter(|search_dir| {
+                let keep = search_dir.is_dir();
+                if !keep {
+                    // search_dir also contains the default paths, so just silently ignore these
+                    log::debug!(
+                        "Dictionary search path is not a directory {}",
+                        search_dir.display()
+                    );
+                } else {
+                    log::debug!("Found dictionary search path {}", search_dir.display());
+                }
+                keep
+            })
+            .find_map(|search_dir| {
+                let dic = search_dir.join(&lang).with_extension("dic");
+                if !dic.is_file() {
+                    log::debug!(
+                        "Dictionary path dervied from search dir is not a file {}",
+                        dic.display()
+                    );
+                    return None;
+                }
+                let aff = search_dir.join(&lang).with_extension("aff");
+                if !aff.is_file() {
+                    log::debug!(
+                        "Affixes path dervied from search dir is not a file {}",
+                        aff.display()
+                    );
+                    return None;
+                }
+                Some((dic, aff))
+            })
+    }
+
     pub fn sanitize_paths(&mut self, base: &Path) -> Result<()> {
         self.search_dirs = self
             .search_dirs
@@ -178,12 +289,101 @@ impl HunspellConfig {
                     log::debug!("Failed to canonicalize {}", abspath.display());
                 }
             }
-            bail!(
-                "Could not find extra dictionary {} in any of the search paths",
-                extra_dic.display()
-            );
+            return Err(DictionaryNotFoundError {
+                path: extra_dic.clone(),
+                config_key: "Hunspell.extra_dictionaries",
+                search_dirs: self
+                    .search_dirs
+                    .iter(!self.skip_os_lookups)
+                    .cloned()
+                    .collect(),
+            }
+            .into());
         }
 
         Ok(())
     }
+
+    /// All dictionaries in the search directories, which have an affix file
+    /// next to them, as `(dic, aff)` pairs.
+    pub fn available_dictionaries(&self) -> Vec<(PathBuf, PathBuf)> {
+        let mut available = self
+            .search_dirs()
+            .filter_map(|search_dir| std::fs::read_dir(search_dir).ok())
+            .flat_map(|entries| entries.filter_map(|entry| entry.ok()))
+            .map(|entry| entry.path())
+            .filter(|path| path.extension().map(|ext| ext == "dic").unwrap_or(false))
+            .filter_map(|dic| {
+                let aff = dic.with_extension("aff");
+                aff.is_file().then(|| (dic, aff))
+            })
+            .collect::<Vec<_>>();
+        available.sort();
+        available.dedup();
+        available
+    }
+}
+
+/// A dictionary file referenced by the configuration does not exist.
+#[derive(Debug, Clone, thiserror::Error)]
+#[error(
+    "Dictionary file {} referenced by `{config_key}` was not found.\n{}\
+     Run `cargo spellcheck dict available` to list the installed dictionaries.",
+    .path.display(),
+    searched(.search_dirs)
+)]
+pub struct DictionaryNotFoundError {
+    /// The dictionary file which was looked for.
+    pub path: PathBuf,
+    /// The configuration key which references the dictionary.
+    pub config_key: &'static str,
+    /// All directories which were searched.
+    pub search_dirs: Vec<PathBuf>,
+}
+
+/// The lines listing the searched directories of a `DictionaryNotFoundError`.
+fn searched(search_dirs: &[PathBuf]) -> String {
+    if search_dirs.is_empty() {
+        return "No search directories were provided.\n".to_owned();
+    }
+    search_dirs.iter().fold(
+        String::from("Searched directories:\n"),
+        |mut acc, search_dir| {
+            acc.push_str(&format!("  {}\n", search_dir.display()));
+            acc
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dictionary_not_found_message() {
+        let err = DictionaryNotFoundError {
+            path: PathBuf::from("lingo.dic"),
+            config_key: "Hunspell.extra_dictionaries",
+            search_dirs: vec![PathBuf::from("/usr/share/hunspell")],
+        };
+        let msg = err.to_string();
+        assert!(msg.contains("lingo.dic"));
+        assert!(msg.contains("`Hunspell.extra_dictionaries`"));
+        assert!(msg.contains("  /usr/share/hunspell\n"));
+        assert!(msg.contains("cargo spellcheck dict available"));
+    }
+
+    #[test]
+    fn extra_dictionary_not_found() {
+        let mut config = HunspellConfig {
+            skip_os_lookups: true,
+            extra_dictionaries: vec![PathBuf::from("does-not-exist.dic")],
+            ..Default::default()
+        };
+        let err = config
+            .sanitize_paths(&crate::traverse::cwd().unwrap())
+            .unwrap_err();
+        let err = err.downcast_ref::<DictionaryNotFoundError>().unwrap();
+        assert_eq!(err.path, PathBuf::from("does-not-exist.dic"));
+    }
 }
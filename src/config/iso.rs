@@ -75,6 +75,28 @@ impl fmt::Display for Lang5 {
     }
 }
 
+impl FromStr for Lang5 {
+    type Err = String;
+
+    /// Parse a code in the form of `LL_CC`, i.e. `de_DE`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.len() != 5 || !s.is_ascii() {
+            return Err(format!(
+                "Expected a 5 digit lang and country code in the form of LL_CC, found \"{}\"",
+                s
+            ));
+        }
+        let lang = Language::from_639_1(&s[0..2])
+            .ok_or_else(|| format!("Unknown ISO 639-1 language code \"{}\"", &s[0..2]))?;
+        let c = s.chars().nth(2).unwrap();
+        if c != '_' {
+            return Err(Lang5SpacerError(c).to_string());
+        }
+        let country = Country::from_str(&s[3..5]).map_err(|e| e.to_string())?;
+        Ok(Lang5 { lang, country })
+    }
+}
+
 #[derive(Debug, Clone, thiserror::Error)]
 #[error("Wrong character, expected '_' found '{0}'")]
 struct Lang5SpacerError(char);
@@ -96,18 +118,7 @@ impl<'de> de::Visitor<'de> for Lang5Visitor {
     where
         E: serde::de::Error,
     {
-        if s.len() != 5 {
-            return Err(Lang5SpacerError('l')).map_err(serde::de::Error::custom);
-        }
-        let lang = Language::from_639_1(&s[0..2])
-            .ok_or_else(|| Lang5SpacerError('2'))
-            .map_err(serde::de::Error::custom)?;
-        let c = s.chars().nth(2).unwrap();
-        if c != '_' {
-            return Err(Lang5SpacerError(c)).map_err(serde::de::Error::custom)?;
-        }
-        let country = Country::from_str(&s[3..5]).map_err(serde::de::Error::custom)?;
-        Ok(Lang5 { lang, country })
+        Lang5::from_str(s).map_err(serde::de::Error::custom)
     }
 }
 
@@ -155,4 +166,14 @@ mod tests {
         assert!(EXPECTED == &S.to_owned());
         assert!(&EXPECTED == S);
     }
+
+    #[test]
+    fn from_str() {
+        assert_eq!(Lang5::from_str(S), Ok(EXPECTED));
+        assert_eq!(Lang5::from_str("en_US"), Ok(Lang5::en_US));
+        assert!(Lang5::from_str("de-AU").is_err());
+        assert!(Lang5::from_str("xx_AU").is_err());
+        assert!(Lang5::from_str("de_XX").is_err());
+        assert!(Lang5::from_str("de").is_err());
+    }
 }
@@ -0,0 +1,188 @@
+//! Migrations of outdated configuration files to the current schema.
+//!
+//! Every configuration carries a `config_version`, files without one are
+//! version `0`. Migrations are applied in order on the raw toml table, so
+//! keys which are not understood anymore can still be rewritten.
+
+use crate::errors::*;
+
+use toml::value::{Table, Value};
+
+/// The current version of the configuration schema.
+pub const CONFIG_VERSION: u32 = 2;
+
+/// A single step from one schema version to the next.
+struct Migration {
+    from: u32,
+    to: u32,
+    apply: fn(&mut Table),
+}
+
+const MIGRATIONS: &[Migration] = &[
+    Migration {
+        from: 0,
+        to: 1,
+        apply: v0_to_v1,
+    },
+    Migration {
+        from: 1,
+        to: 2,
+        apply: v1_to_v2,
+    },
+];
+
+/// Rename `old` to `new` within `table`, unless `new` exists already.
+fn rename(table: &mut Table, old: &str, new: &str) {
+    if table.contains_key(new) {
+        return;
+    }
+    if let Some(value) = table.remove(old) {
+        log::info!("Renaming `{}` to `{}`", old, new);
+        table.insert(new.to_owned(), value);
+    }
+}
+
+/// Replace the alternative spellings of keys and sections by their canonical
+/// snake case names.
+fn v0_to_v1(table: &mut Table) {
+    for (old, new) in [
+        ("dev-comments", "dev_comments"),
+        ("devcomments", "dev_comments"),
+        ("skip-readme", "skip_readme"),
+        ("skipreadme", "skip_readme"),
+        ("skip-patterns", "skip_patterns"),
+        ("skippatterns", "skip_patterns"),
+        ("Nlp", "NlpRules"),
+        ("NLP", "NlpRules"),
+        ("nlp", "NlpRules"),
+        ("ReFlow", "Reflow"),
+    ] {
+        rename(table, old, new);
+    }
+    for section in ["Reflow", "reflow"] {
+        if let Some(Value::Table(reflow)) = table.get_mut(section) {
+            rename(reflow, "max_line_width", "max_line_length");
+        }
+    }
+}
+
+/// Move the top level switches of checks into the `[checks]` section, unless
+/// set there already.
+fn v1_to_v2(table: &mut Table) {
+    for (old, alias, new) in [
+        ("require_ascii", "require-ascii", "ascii_only"),
+        (
+            "check_duplicate_words",
+            "check-duplicate-words",
+            "duplicate_words",
+        ),
+    ] {
+        let value = match table.remove(old).or_else(|| table.remove(alias)) {
+            Some(value) => value,
+            None => continue,
+        };
+        log::info!("Moving `{}` to `{}` of `[checks]`", old, new);
+        if let Value::Table(checks) = table
+            .entry("checks".to_owned())
+            .or_insert_with(|| Value::Table(Table::new()))
+        {
+            checks.entry(new.to_owned()).or_insert(value);
+        }
+    }
+}
+
+/// Migrate the toml `content` of a configuration file to the current schema
+/// version.
+pub fn migrate(content: &str) -> Result<String> {
+    let mut table = toml::from_str::<Table>(content)
+        .wrap_err_with(|| eyre!("Failed to parse configuration as toml"))?;
+    let mut version = match table.get("config_version") {
+        None => 0,
+        Some(Value::Integer(version)) if *version >= 0 => *version as u32,
+        Some(other) => bail!("Invalid `config_version` {}", other),
+    };
+    if version > CONFIG_VERSION {
+        bail!(
+            "Configuration version {} is newer than the supported version {}",
+            version,
+            CONFIG_VERSION
+        );
+    }
+    // migrations are sorted, so each one continues where the previous ended
+    for migration in MIGRATIONS.iter() {
+        if migration.from != version {
+            continue;
+        }
+        log::debug!(
+            "Migrating configuration from version {} to {}",
+            migration.from,
+            migration.to
+        );
+        (migration.apply)(&mut table);
+        version = migration.to;
+    }
+    table.insert(
+        "config_version".to_owned(),
+        Value::Integer(i64::from(version)),
+    );
+    Ok(toml::to_string(&table)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn v0_to_v1() {
+        const V0: &str = r#"
+dev-comments = true
+skipreadme = true
+
+[nlp]
+override_rules = "x"
+
+[ReFlow]
+max_line_width = 100
+"#;
+        let migrated = migrate(V0).unwrap();
+        let config = crate::Config::parse(&migrated).unwrap();
+        assert_eq!(config.config_version, CONFIG_VERSION);
+        assert!(config.dev_comments);
+        assert!(config.skip_readme);
+        assert_eq!(config.reflow.unwrap().max_line_length, 100);
+
+        let table = toml::from_str::<Table>(&migrated).unwrap();
+        assert!(table.contains_key("NlpRules"));
+        assert!(!table.contains_key("nlp"));
+
+        // idempotent
+        assert_eq!(migrate(&migrated).unwrap(), migrated);
+    }
+
+    #[test]
+    fn v1_to_v2() {
+        const V1: &str = r#"
+config_version = 1
+require_ascii = true
+check_duplicate_words = true
+
+[checks]
+duplicate_words = false
+"#;
+        let migrated = migrate(V1).unwrap();
+        let table = toml::from_str::<Table>(&migrated).unwrap();
+        assert!(!table.contains_key("require_ascii"));
+        assert!(!table.contains_key("check_duplicate_words"));
+
+        let config = crate::Config::parse(&migrated).unwrap();
+        assert_eq!(config.config_version, CONFIG_VERSION);
+        assert!(config.checks.ascii_only);
+        // `[checks]` takes precedence
+        assert!(!config.checks.duplicate_words);
+    }
+
+    #[test]
+    fn newer_version() {
+        assert!(migrate("config_version = 99").is_err());
+    }
+}
@@ -28,12 +28,27 @@ pub use search_dirs::*;
 mod iso;
 pub use iso::*;
 
+mod output;
+pub use output::*;
+
+mod migrate;
+pub use migrate::*;
+
+mod file_type;
+pub use file_type::*;
+
+mod checks;
+pub use checks::*;
+
+mod commented;
+
 use crate::errors::*;
-use crate::Detector;
+use crate::{Confidence, Detector};
 use fancy_regex::Regex;
 
 use fs_err as fs;
 use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
 use std::convert::AsRef;
 use std::fmt;
 use std::io::Read;
@@ -53,17 +68,157 @@ impl std::str::FromStr for CommonLang {
 #[derive(Deserialize, Serialize, Debug, Clone)]
 #[serde(deny_unknown_fields)]
 pub struct Config {
+    /// The schema version, see `cargo spellcheck migrate-config`.
+    #[serde(default)]
+    #[serde(alias = "config-version")]
+    pub config_version: u32,
+
     // Options that modify the inputs being picked up.
     #[serde(default)]
     #[serde(alias = "dev-comments")]
     #[serde(alias = "devcomments")]
     pub dev_comments: bool,
 
+    #[serde(default)]
+    #[serde(alias = "check-string-literals")]
+    pub check_string_literals: bool,
+
+    /// Also check the string values of attributes with one of the
+    /// `attr_keys_to_check`.
+    #[serde(default)]
+    #[serde(alias = "check-attrs")]
+    pub check_attrs: bool,
+
+    /// Attribute names whose string values are checked with `check_attrs`,
+    /// i.e. `error` for `#[error("..")]`.
+    #[serde(default = "default_attr_keys_to_check")]
+    #[serde(alias = "attr-keys-to-check")]
+    pub attr_keys_to_check: Vec<String>,
+
+    /// Names of formatting macros whose string arguments are checked, i.e.
+    /// `println` for `println!("..")`. Format specifiers such as `{}` are
+    /// ignored.
+    #[serde(default)]
+    #[serde(alias = "check-macro-strings")]
+    pub check_macro_strings: Vec<String>,
+
+    /// Deprecated, moved to `ascii_only` of `[checks]`.
+    #[serde(default, skip_serializing)]
+    #[serde(alias = "require-ascii")]
+    require_ascii: bool,
+
+    /// Deprecated, moved to `duplicate_words` of `[checks]`.
+    #[serde(default, skip_serializing)]
+    #[serde(alias = "check-duplicate-words")]
+    check_duplicate_words: bool,
+
     #[serde(default)]
     #[serde(alias = "skip-readme")]
     #[serde(alias = "skipreadme")]
     pub skip_readme: bool,
 
+    /// Glob patterns of files and directories which are never checked,
+    /// relative to the current working directory.
+    #[serde(default)]
+    pub exclude: Vec<String>,
+
+    /// Descend into symbolic links to directories when walking directories
+    /// recursively. Directories are visited once, so cyclic links terminate.
+    #[serde(default = "default_follow_links")]
+    #[serde(alias = "follow-links")]
+    pub follow_links: bool,
+
+    /// Regular expressions, each matched against every line of a chunk. All
+    /// mistakes found on a matching line are dropped.
+    #[serde(default)]
+    #[serde(alias = "skip-patterns")]
+    #[serde(alias = "skippatterns")]
+    pub skip_patterns: Vec<WrappedRegex>,
+
+    /// The maximum number of replacement suggestions shown per mistake.
+    #[serde(default = "default_max_suggestions")]
+    #[serde(alias = "max-suggestions")]
+    pub max_suggestions: usize,
+
+    /// Misspellings whose best replacement has a lower confidence, in the range
+    /// of `0.0` to `1.0`, are reported as warnings, which do not count as
+    /// mistakes.
+    #[serde(default)]
+    #[serde(alias = "warn-threshold")]
+    pub warn_threshold: f32,
+
+    /// The confidence a replacement must have at least, in the range of `0.0`
+    /// to `1.0`, to be applied without user interaction, by `--fix-count` and
+    /// `fix --dry-run`.
+    #[serde(default = "default_fix_threshold")]
+    #[serde(alias = "fix-threshold")]
+    pub fix_threshold: f32,
+
+    /// Plain text files listing words that are always accepted, one word per
+    /// line, lines starting with `#` are comments.
+    #[serde(default)]
+    #[serde(alias = "ignore-words-files")]
+    #[serde(alias = "ignore_words_file")]
+    #[serde(alias = "ignore-words-file")]
+    #[serde(deserialize_with = "one_or_many_paths")]
+    pub ignore_words_files: Vec<PathBuf>,
+
+    /// Words that are always accepted, in addition to those of the
+    /// `ignore_words_files`.
+    #[serde(default)]
+    #[serde(alias = "ignore-words")]
+    pub ignore_words: Vec<String>,
+
+    /// Personal dictionaries, one word per line with optional affix flags
+    /// after a `/`. The words of all files are accepted, missing files are
+    /// skipped with a warning.
+    #[serde(default)]
+    #[serde(alias = "personal-dictionaries")]
+    #[serde(alias = "personal_dictionary")]
+    #[serde(alias = "personal-dictionary")]
+    #[serde(deserialize_with = "one_or_many_paths")]
+    pub personal_dictionaries: Vec<PathBuf>,
+
+    /// Accept common terms of the Rust ecosystem, i.e. `rustdoc` or `impl`.
+    #[serde(default = "default_rust_wordlist")]
+    #[serde(alias = "rust-wordlist")]
+    pub rust_wordlist: bool,
+
+    /// Whether the backends handle morphological variants themselves. If
+    /// `false`, words are also compared by their stem against the words of
+    /// `ignore_words`, the word list files and the personal dictionaries, i.e.
+    /// `tokenizers` is accepted if `tokenizer` is listed.
+    #[serde(default = "default_backend_does_stemming")]
+    #[serde(alias = "backend-does-stemming")]
+    pub backend_does_stemming: bool,
+
+    /// Do not report words listed in the baseline file
+    /// `.cargo-spellcheck-baseline.txt` of the current working directory.
+    #[serde(default)]
+    #[serde(alias = "only-new-words")]
+    pub only_new_words: bool,
+
+    /// Do not check files again which were checked without errors before and
+    /// did not change since, neither did the configuration nor the
+    /// dictionaries.
+    #[serde(default)]
+    pub incremental: bool,
+
+    /// How found mistakes are printed.
+    #[serde(default)]
+    #[serde(alias = "output-format")]
+    pub output_format: OutputFormat,
+
+    /// The width in columns the context of a mistake is truncated to, the
+    /// terminal width or `$COLUMNS` if unset.
+    #[serde(default)]
+    #[serde(alias = "display-width")]
+    pub display_width: Option<usize>,
+
+    /// Switches for each category of checks.
+    #[serde(default)]
+    pub checks: ChecksConfig,
+
     #[serde(alias = "Hunspell")]
     #[serde(default = "default_hunspell")]
     pub hunspell: Option<HunspellConfig>,
@@ -78,6 +233,20 @@ pub struct Config {
     #[serde(alias = "ReFlow")]
     #[serde(alias = "Reflow")]
     pub reflow: Option<ReflowConfig>,
+
+    /// Languages of files matching a glob pattern, see
+    /// `LanguageRegionConfig`.
+    // arrays of tables must follow all tables, an empty array can not
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    #[serde(rename = "language_region")]
+    #[serde(alias = "language-region")]
+    pub language_regions: Vec<LanguageRegionConfig>,
+
+    /// Overrides for files matching a glob pattern, see `FileTypeConfig`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    #[serde(rename = "file_type")]
+    #[serde(alias = "file-type")]
+    pub file_types: Vec<FileTypeConfig>,
 }
 
 impl Config {
@@ -90,11 +259,158 @@ impl Config {
         if let Some(ref mut hunspell) = self.hunspell {
             hunspell.sanitize_paths(base)?;
         }
+        for path in self.ignore_words_files.iter_mut() {
+            if !path.is_absolute() {
+                *path = base.join(&path);
+            }
+        }
+        for path in self.personal_dictionaries.iter_mut() {
+            if let Ok(relative) = path.strip_prefix("~") {
+                if let Some(dirs) = directories::BaseDirs::new() {
+                    *path = dirs.home_dir().join(relative);
+                }
+            }
+            if !path.is_absolute() {
+                *path = base.join(&path);
+            }
+        }
         Ok(())
     }
 
+    /// Load the union of all words of the `personal_dictionaries`.
+    pub fn load_personal_dictionaries(&self) -> Result<HashSet<String>> {
+        Ok(self
+            .load_personal_dictionaries_by_path()?
+            .into_iter()
+            .flat_map(|(_path, words)| words)
+            .collect())
+    }
+
+    /// Load the words of each of the `personal_dictionaries`, together with
+    /// the path of the dictionary. Missing dictionaries are skipped.
+    pub fn load_personal_dictionaries_by_path(&self) -> Result<Vec<(&Path, HashSet<String>)>> {
+        let mut dictionaries = Vec::with_capacity(self.personal_dictionaries.len());
+        for path in self.personal_dictionaries.iter() {
+            let content = match fs::read_to_string(path) {
+                Ok(content) => content,
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                    log::warn!("Personal dictionary {} does not exist", path.display());
+                    continue;
+                }
+                Err(e) => {
+                    return Err(e).wrap_err_with(|| {
+                        eyre!("Failed to read personal dictionary {}", path.display())
+                    })
+                }
+            };
+            dictionaries.push((
+                path.as_path(),
+                parse_personal_dictionary(content.as_str()).collect(),
+            ));
+        }
+        Ok(dictionaries)
+    }
+
+    /// Load all words of the `ignore_words_files` and the `ignore_words`.
+    pub fn load_ignored_words(&self) -> Result<HashSet<String>> {
+        let mut words = HashSet::new();
+        for path in self.ignore_words_files.iter() {
+            let content = fs::read_to_string(path).wrap_err_with(|| {
+                eyre!(
+                    "Failed to read file listing ignored words {}",
+                    path.display()
+                )
+            })?;
+            words.extend(parse_ignored_words(content.as_str()));
+        }
+        words.extend(self.ignore_words.iter().cloned());
+        Ok(words)
+    }
+
+    /// The width in columns the context of a mistake is truncated to.
+    pub fn display_width(&self) -> usize {
+        self.display_width.unwrap_or_else(crate::get_terminal_size)
+    }
+
+    /// The attribute names whose string values are checked, empty unless
+    /// `check_attrs` is enabled.
+    pub fn attr_keys(&self) -> &[String] {
+        if self.check_attrs {
+            &self.attr_keys_to_check
+        } else {
+            &[]
+        }
+    }
+
+    /// The builtin Rust ecosystem words, empty if `rust_wordlist` is disabled.
+    pub fn builtin_words(&self) -> impl Iterator<Item = String> {
+        let words = if self.rust_wordlist {
+            RUST_WORDLIST
+        } else {
+            ""
+        };
+        parse_ignored_words(words)
+    }
+
     pub fn parse<S: AsRef<str>>(s: S) -> Result<Self> {
-        Ok(toml::from_str(s.as_ref())?)
+        let mut cfg: Self = toml::from_str(s.as_ref())?;
+        cfg.normalize_separators();
+        cfg.apply_deprecated_checks();
+        Ok(cfg)
+    }
+
+    /// Move the deprecated top level switches of checks into `[checks]`.
+    fn apply_deprecated_checks(&mut self) {
+        if std::mem::take(&mut self.require_ascii) {
+            log::warn!("`require_ascii` is deprecated, use `ascii_only` of `[checks]` instead");
+            self.checks.ascii_only = true;
+        }
+        if std::mem::take(&mut self.check_duplicate_words) {
+            log::warn!(
+                "`check_duplicate_words` is deprecated, use `duplicate_words` of `[checks]` instead"
+            );
+            self.checks.duplicate_words = true;
+        }
+    }
+
+    /// Use `/` as the path separator of all paths and glob patterns, so a
+    /// configuration written on Windows works on all platforms.
+    ///
+    /// A backslash is a valid file name character everywhere else, and an
+    /// escape in glob patterns, use `[*]` to match a literal `*` instead.
+    fn normalize_separators(&mut self) {
+        if let Some(ref mut hunspell) = self.hunspell {
+            hunspell
+                .search_dirs
+                .0
+                .iter_mut()
+                .chain(hunspell.extra_dictionaries.iter_mut())
+                .for_each(normalize_path_separators);
+        }
+        if let Some(ref mut nlprules) = self.nlprules {
+            nlprules
+                .override_rules
+                .iter_mut()
+                .chain(nlprules.override_tokenizer.iter_mut())
+                .for_each(normalize_path_separators);
+        }
+        self.ignore_words_files
+            .iter_mut()
+            .chain(self.personal_dictionaries.iter_mut())
+            .for_each(normalize_path_separators);
+        self.exclude
+            .iter_mut()
+            .chain(
+                self.language_regions
+                    .iter_mut()
+                    .map(|region| &mut region.pattern),
+            )
+            .chain(
+                self.file_types
+                    .iter_mut()
+                    .map(|file_type| &mut file_type.pattern),
+            )
+            .for_each(|pattern| *pattern = pattern.replace('\\', "/"));
     }
 
     pub fn load_from<P: AsRef<Path>>(path: P) -> Result<Option<Self>> {
@@ -113,6 +429,12 @@ impl Config {
                 )
             })
             .and_then(|mut cfg| {
+                if cfg.config_version < CONFIG_VERSION {
+                    log::info!(
+                        "Configuration file {} is outdated, update it with `cargo spellcheck migrate-config`",
+                        path.display()
+                    );
+                }
                 if let Some(base) = path.parent() {
                     cfg.sanitize_paths(base)?;
                 }
@@ -141,6 +463,73 @@ impl Config {
         }
     }
 
+    /// Describe the configuration in use, one line each for the file it was
+    /// loaded from, the workspace root, the dictionaries and the languages.
+    pub fn summary(&self, config_path: Option<&Path>) -> Vec<String> {
+        let mut lines = vec![match config_path {
+            Some(path) => format!("Using configuration file {}", path.display()),
+            None => "Using defaults, no configuration file found".to_owned(),
+        }];
+        lines.push(match crate::traverse::cwd() {
+            Ok(cwd) => match crate::traverse::workspace_root(&cwd) {
+                Some(root) => format!("Workspace root {}", root.display()),
+                None => format!(
+                    "No workspace root, {} is not within a workspace",
+                    cwd.display()
+                ),
+            },
+            Err(_) => "No workspace root, the working directory is unknown".to_owned(),
+        });
+
+        let mut dictionaries = Vec::new();
+        if let Some(ref hunspell) = self.hunspell {
+            match hunspell.dictionary() {
+                Some((dic, _aff)) => dictionaries.push(dic.display().to_string()),
+                None if hunspell.use_builtin => dictionaries.push("builtin en_US".to_owned()),
+                None => {}
+            }
+            dictionaries.extend(
+                hunspell
+                    .extra_dictionaries()
+                    .map(|dic| dic.display().to_string()),
+            );
+        }
+        dictionaries.extend(
+            self.personal_dictionaries
+                .iter()
+                .map(|dic| dic.display().to_string()),
+        );
+        lines.push(if dictionaries.is_empty() {
+            "No dictionary files".to_owned()
+        } else {
+            format!(
+                "{} dictionary files: {}",
+                dictionaries.len(),
+                dictionaries.join(", ")
+            )
+        });
+
+        lines.push(match self.hunspell {
+            Some(ref hunspell) if self.checks.spelling => format!("Language {}", hunspell.lang()),
+            _ => "No language, spell checking is disabled".to_owned(),
+        });
+        for region in self.language_regions.iter() {
+            lines.push(format!(
+                "Language {} for files matching `{}`",
+                region.lang, region.pattern
+            ));
+        }
+        for file_type in self.file_types.iter() {
+            if let Some(language) = file_type.language {
+                lines.push(format!(
+                    "Language {} for files matching `{}`",
+                    language, file_type.pattern
+                ));
+            }
+        }
+        lines
+    }
+
     pub fn to_toml(&self) -> Result<String> {
         toml::to_string(self).wrap_err_with(|| eyre!("Failed to convert to toml"))
     }
@@ -182,6 +571,19 @@ impl Config {
         Self::default().write_values_to_path(path)
     }
 
+    /// The per user configuration file in the platform specific configuration
+    /// directory, i.e. `~/.config/cargo-spellcheck/config.toml` on Linux.
+    pub fn user_config_path() -> Result<PathBuf> {
+        if let Some(base) = directories::BaseDirs::new() {
+            Ok(base
+                .config_dir()
+                .join("cargo-spellcheck")
+                .join("config.toml"))
+        } else {
+            bail!("No idea where your config directory is located. `$HOME` must be set.")
+        }
+    }
+
     pub fn default_path() -> Result<PathBuf> {
         if let Some(base) =
             directories::ProjectDirs::from(Self::QUALIFIER, Self::ORGANIZATION, Self::APPLICATION)
@@ -219,9 +621,11 @@ impl Config {
 
     pub fn is_enabled(&self, detector: Detector) -> bool {
         match detector {
-            Detector::Hunspell => self.hunspell.is_some(),
-            Detector::NlpRules => self.nlprules.is_some(),
+            Detector::Hunspell => self.checks.spelling && self.hunspell.is_some(),
+            Detector::NlpRules => self.checks.grammar && self.nlprules.is_some(),
             Detector::Reflow => self.reflow.is_some(),
+            Detector::Ascii => self.checks.ascii_only,
+            Detector::DuplicateWord => self.checks.duplicate_words,
             #[cfg(test)]
             Detector::Dummy => true,
         }
@@ -230,6 +634,128 @@ impl Config {
     pub fn full() -> Self {
         Default::default()
     }
+
+    /// The maximum number of replacement suggestions shown per mistake, capped
+    /// at `MAX_SUGGESTIONS_LIMIT`.
+    pub fn max_suggestions(&self) -> usize {
+        if self.max_suggestions > MAX_SUGGESTIONS_LIMIT {
+            log::warn!(
+                "Showing more than {} suggestions ({}) is not supported, capping at {}",
+                MAX_SUGGESTIONS_LIMIT,
+                self.max_suggestions,
+                MAX_SUGGESTIONS_LIMIT
+            );
+            MAX_SUGGESTIONS_LIMIT
+        } else {
+            self.max_suggestions
+        }
+    }
+
+    /// The confidence below which misspellings are warnings, clamped to the
+    /// range of `0.0` to `1.0`.
+    pub fn warn_threshold(&self) -> f32 {
+        if (0.0..=1.0).contains(&self.warn_threshold) {
+            self.warn_threshold
+        } else {
+            let clamped = self.warn_threshold.clamp(0.0, 1.0);
+            log::warn!(
+                "`warn_threshold` must be in the range of 0.0 to 1.0, using {} instead of {}",
+                clamped,
+                self.warn_threshold
+            );
+            clamped
+        }
+    }
+
+    /// The confidence a replacement needs to be applied without user
+    /// interaction, clamped to the range of `0.0` to `1.0`.
+    pub fn fix_threshold(&self) -> Confidence {
+        if !(0.0..=1.0).contains(&self.fix_threshold) {
+            log::warn!(
+                "`fix_threshold` must be in the range of 0.0 to 1.0, using {} instead of {}",
+                self.fix_threshold.clamp(0.0, 1.0),
+                self.fix_threshold
+            );
+        }
+        Confidence::new(self.fix_threshold)
+    }
+}
+
+/// Parse a plain text list of words, one per line. Empty lines and lines
+/// starting with `#` are skipped.
+pub(crate) fn parse_ignored_words(content: &str) -> impl Iterator<Item = String> + '_ {
+    content
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(ToOwned::to_owned)
+}
+
+/// Words of a personal dictionary, with affix flags stripped.
+fn parse_personal_dictionary(content: &str) -> impl Iterator<Item = String> + '_ {
+    parse_ignored_words(content).filter_map(|line| {
+        let word = line.split('/').next().unwrap_or_default().trim();
+        (!word.is_empty()).then(|| word.to_owned())
+    })
+}
+
+/// Replace all backslashes of `path` by `/`, which is a path separator on all
+/// platforms.
+fn normalize_path_separators(path: &mut PathBuf) {
+    // paths of the configuration are always valid UTF-8
+    if let Some(normalized) = path.to_str().map(|s| s.replace('\\', "/")) {
+        *path = PathBuf::from(normalized);
+    }
+}
+
+/// Accept either a single path or an array of paths.
+fn one_or_many_paths<'de, D>(deserializer: D) -> std::result::Result<Vec<PathBuf>, D::Error>
+where
+    D: serde::de::Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum OneOrMany {
+        One(PathBuf),
+        Many(Vec<PathBuf>),
+    }
+    Ok(match OneOrMany::deserialize(deserializer)? {
+        OneOrMany::One(path) => vec![path],
+        OneOrMany::Many(paths) => paths,
+    })
+}
+
+/// Upper bound for `max_suggestions`.
+const MAX_SUGGESTIONS_LIMIT: usize = 20;
+
+const fn default_max_suggestions() -> usize {
+    5
+}
+
+const fn default_fix_threshold() -> f32 {
+    0.8
+}
+
+fn default_attr_keys_to_check() -> Vec<String> {
+    ["error", "description"]
+        .iter()
+        .map(|&key| key.to_owned())
+        .collect()
+}
+
+/// Terms of the Rust ecosystem, in the format of `ignore_words_files`.
+const RUST_WORDLIST: &str = include_str!("rust-words.txt");
+
+const fn default_rust_wordlist() -> bool {
+    true
+}
+
+const fn default_backend_does_stemming() -> bool {
+    true
+}
+
+const fn default_follow_links() -> bool {
+    true
 }
 
 fn default_nlprules() -> Option<NlpRulesConfig> {
@@ -247,11 +773,36 @@ fn default_hunspell() -> Option<HunspellConfig> {
 impl Default for Config {
     fn default() -> Self {
         Self {
+            config_version: CONFIG_VERSION,
             dev_comments: false,
+            check_string_literals: false,
+            check_attrs: false,
+            require_ascii: false,
+            check_duplicate_words: false,
+            attr_keys_to_check: default_attr_keys_to_check(),
+            check_macro_strings: Vec::new(),
             skip_readme: false,
+            exclude: Vec::new(),
+            follow_links: default_follow_links(),
+            skip_patterns: Vec::new(),
+            max_suggestions: default_max_suggestions(),
+            warn_threshold: 0.0,
+            fix_threshold: default_fix_threshold(),
+            ignore_words_files: Vec::new(),
+            ignore_words: Vec::new(),
+            personal_dictionaries: Vec::new(),
+            rust_wordlist: default_rust_wordlist(),
+            backend_does_stemming: default_backend_does_stemming(),
+            only_new_words: false,
+            incremental: false,
+            output_format: OutputFormat::default(),
+            display_width: None,
+            checks: ChecksConfig::default(),
             hunspell: default_hunspell(),
             nlprules: default_nlprules(),
             reflow: Some(ReflowConfig::default()),
+            language_regions: Vec::new(),
+            file_types: Vec::new(),
         }
     }
 }
@@ -283,6 +834,115 @@ extra_dictionaries = ["/home/bernhard/test.dic"]
         .unwrap();
     }
 
+    #[test]
+    fn ignore_words_files() {
+        let config = Config::parse(
+            r#"
+ignore_words_file = "spellcheck-ignore.txt"
+"#,
+        )
+        .unwrap();
+        assert_eq!(
+            config.ignore_words_files,
+            vec![PathBuf::from("spellcheck-ignore.txt")]
+        );
+
+        let mut config = Config::parse(
+            r#"
+ignore_words_files = ["a.txt", "/b.txt"]
+"#,
+        )
+        .unwrap();
+        config
+            .sanitize_paths(Path::new("/project/.config"))
+            .unwrap();
+        assert_eq!(
+            config.ignore_words_files,
+            vec![
+                PathBuf::from("/project/.config/a.txt"),
+                PathBuf::from("/b.txt")
+            ]
+        );
+
+        assert_eq!(
+            parse_ignored_words("# comment\nfoo\n\n  bar \n#baz\n").collect::<Vec<_>>(),
+            vec!["foo".to_owned(), "bar".to_owned()]
+        );
+    }
+
+    #[test]
+    fn windows_separators() {
+        let config = Config::parse(
+            r#"
+exclude = ["src\\gen\\**"]
+ignore_words_file = "ci\\ignore.txt"
+personal_dictionaries = ["~\\dicts\\lingo.dic"]
+
+[hunspell]
+search_dirs = ["C:\\hunspell"]
+extra_dictionaries = ["dicts\\extra.dic"]
+
+[[file_type]]
+pattern = "docs\\*.md"
+"#,
+        )
+        .unwrap();
+        assert_eq!(config.exclude, vec!["src/gen/**".to_owned()]);
+        assert_eq!(
+            config.ignore_words_files,
+            vec![PathBuf::from("ci/ignore.txt")]
+        );
+        assert_eq!(
+            config.personal_dictionaries,
+            vec![PathBuf::from("~/dicts/lingo.dic")]
+        );
+        let hunspell = config.hunspell.as_ref().unwrap();
+        assert_eq!(
+            hunspell.search_dirs.as_ref(),
+            &vec![PathBuf::from("C:/hunspell")]
+        );
+        assert_eq!(
+            hunspell.extra_dictionaries,
+            vec![PathBuf::from("dicts/extra.dic")]
+        );
+        assert_eq!(config.file_types[0].pattern, "docs/*.md");
+    }
+
+    #[test]
+    fn personal_dictionaries() {
+        let dir = std::env::temp_dir().join(uuid::Uuid::new_v4().to_string());
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("base.dic"), "Tokio\nserde/S\n").unwrap();
+        fs::write(dir.join("project.dic"), "# lingo\nTokio\nrustdoc\n").unwrap();
+
+        let mut config = Config::parse(
+            r#"
+personal_dictionaries = ["base.dic", "project.dic", "missing.dic"]
+"#,
+        )
+        .unwrap();
+        config.sanitize_paths(&dir).unwrap();
+        assert_eq!(config.personal_dictionaries[0], dir.join("base.dic"));
+
+        let words = config.load_personal_dictionaries().unwrap();
+        assert_eq!(
+            words,
+            ["Tokio", "serde", "rustdoc"]
+                .iter()
+                .map(|word| word.to_string())
+                .collect::<HashSet<_>>()
+        );
+
+        let by_path = config.load_personal_dictionaries_by_path().unwrap();
+        assert_eq!(
+            by_path
+                .iter()
+                .map(|(path, words)| (path.file_name().unwrap().to_owned(), words.len()))
+                .collect::<Vec<_>>(),
+            vec![("base.dic".into(), 2), ("project.dic".into(), 2)]
+        );
+    }
+
     #[test]
     fn empty() {
         assert!(Config::parse(
@@ -366,6 +1026,21 @@ transform_regex = ["^'([^\\s])'$", "^[0-9]+x$"]
         let _hunspell = cfg.hunspell.expect("Must contain hunspell cfg");
     }
 
+    #[test]
+    fn quirks_min_word_length() {
+        let cfg = Config::parse(
+            r#"
+[Hunspell.quirks]
+min_word_length = 2
+"#,
+        )
+        .unwrap();
+        let hunspell = cfg.hunspell.expect("Must contain hunspell cfg");
+        assert_eq!(hunspell.quirks.min_word_length(), 2);
+
+        assert_eq!(Quirks::default().min_word_length(), 3);
+    }
+
     #[test]
     fn partial_8() {
         let cfg = Config::parse(
@@ -396,6 +1071,92 @@ skip_os_lookups = true
         assert!(search_dirs.iter(true).count() >= 3);
     }
 
+    #[test]
+    fn skip_patterns() {
+        let cfg = Config::parse(
+            r#"
+skip_patterns = ["^\\s*ERR_[A-Z_]+", "(?P<key>[a-z]+\\.[a-z]+)=.*"]
+"#,
+        )
+        .unwrap();
+        assert_eq!(cfg.skip_patterns.len(), 2);
+        assert!(cfg.skip_patterns[0].is_match(" ERR_NOT_FOUND").unwrap());
+    }
+
+    #[test]
+    fn skip_patterns_invalid() {
+        assert!(Config::parse(
+            r#"
+skip_patterns = ["(unclosed"]
+"#,
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn defaults() {
+        for config in [Config::default(), Config::parse("").unwrap()] {
+            assert_eq!(config.max_suggestions(), 5);
+            assert!(config.rust_wordlist);
+            assert!(config.backend_does_stemming);
+            assert!(config.builtin_words().any(|word| word == "rustdoc"));
+            let hunspell = config.hunspell.expect("Must contain hunspell cfg");
+            assert_eq!(hunspell.lang(), "en_US");
+            assert_eq!(hunspell.quirks.min_word_length(), 3);
+            assert!(hunspell.quirks.skip_urls());
+            assert!(!hunspell.quirks.allow_concatenated());
+        }
+
+        let config = Config::parse("rust_wordlist = false").unwrap();
+        assert_eq!(config.builtin_words().count(), 0);
+
+        let toml = Config::default().to_toml().unwrap();
+        assert!(Config::parse(toml).unwrap().rust_wordlist);
+    }
+
+    #[test]
+    fn checks() {
+        let config = Config::parse("").unwrap();
+        assert!(config.is_enabled(Detector::Hunspell));
+        assert!(!config.is_enabled(Detector::DuplicateWord));
+
+        let config = Config::parse(
+            r#"
+[checks]
+spelling = false
+duplicate_words = true
+ascii_only = true
+"#,
+        )
+        .unwrap();
+        assert!(!config.is_enabled(Detector::Hunspell));
+        assert!(config.is_enabled(Detector::DuplicateWord));
+        assert!(config.is_enabled(Detector::Ascii));
+        assert!(config.checks.grammar);
+
+        let config = Config::parse("require_ascii = true\ncheck_duplicate_words = true").unwrap();
+        assert!(config.checks.ascii_only);
+        assert!(config.checks.duplicate_words);
+        assert!(!config.to_toml().unwrap().contains("require_ascii"));
+    }
+
+    #[test]
+    fn max_suggestions() {
+        assert_eq!(Config::parse("").unwrap().max_suggestions(), 5);
+        assert_eq!(
+            Config::parse("max_suggestions = 0")
+                .unwrap()
+                .max_suggestions(),
+            0
+        );
+        assert_eq!(
+            Config::parse("max_suggestions = 42")
+                .unwrap()
+                .max_suggestions(),
+            20
+        );
+    }
+
     #[test]
     fn partial_9() {
         let cfg = Config::parse(
@@ -0,0 +1,109 @@
+use super::*;
+
+use std::str::FromStr;
+
+/// The format in which found mistakes are printed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OutputFormat {
+    /// Multi line, with context and highlighting.
+    Human,
+    /// One line per mistake `path:line:column: word (replacement, ..)`.
+    Compact,
+    /// A single JSON array, for consumption by other tools.
+    Json,
+    /// A GitLab code quality report, to annotate merge requests.
+    #[serde(rename = "gitlab-code-quality")]
+    GitLabCodeQuality,
+    /// Azure DevOps logging commands, to annotate pipeline runs.
+    #[serde(rename = "azure-devops")]
+    AzureDevOps,
+    /// A Checkstyle XML report, understood by many IDEs and build tools.
+    Checkstyle,
+    /// One reviewdog diagnostic per line, to annotate pull requests.
+    #[serde(rename = "rdjsonl", alias = "rdjson")]
+    Rdjsonl,
+}
+
+impl OutputFormat {
+    /// All accepted names, including aliases.
+    pub const VARIANTS: &'static [&'static str] = &[
+        "human",
+        "compact",
+        "json",
+        "gitlab-code-quality",
+        "azure-devops",
+        "checkstyle",
+        "rdjsonl",
+        "rdjson",
+    ];
+}
+
+impl Default for OutputFormat {
+    fn default() -> Self {
+        Self::Human
+    }
+}
+
+impl FromStr for OutputFormat {
+    type Err = UnknownOutputFormat;
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        let s = s.to_lowercase();
+        Ok(match s.as_str() {
+            "human" => Self::Human,
+            "compact" => Self::Compact,
+            "json" => Self::Json,
+            "gitlab-code-quality" => Self::GitLabCodeQuality,
+            "azure-devops" => Self::AzureDevOps,
+            "checkstyle" => Self::Checkstyle,
+            "rdjsonl" | "rdjson" => Self::Rdjsonl,
+            _other => return Err(UnknownOutputFormat(s)),
+        })
+    }
+}
+
+#[derive(Debug, Clone, thiserror::Error)]
+#[error("Unknown output format: {0}, expected one of `human`, `compact`, `json`, `gitlab-code-quality`, `azure-devops`, `checkstyle` or `rdjsonl`")]
+pub struct UnknownOutputFormat(String);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse() {
+        assert_eq!(
+            OutputFormat::from_str("compact").unwrap(),
+            OutputFormat::Compact
+        );
+        assert_eq!(
+            OutputFormat::from_str("Human").unwrap(),
+            OutputFormat::Human
+        );
+        assert_eq!(OutputFormat::from_str("json").unwrap(), OutputFormat::Json);
+        assert_eq!(
+            OutputFormat::from_str("gitlab-code-quality").unwrap(),
+            OutputFormat::GitLabCodeQuality
+        );
+        assert_eq!(
+            OutputFormat::from_str("azure-devops").unwrap(),
+            OutputFormat::AzureDevOps
+        );
+        assert_eq!(
+            OutputFormat::from_str("checkstyle").unwrap(),
+            OutputFormat::Checkstyle
+        );
+        assert_eq!(
+            OutputFormat::from_str("rdjsonl").unwrap(),
+            OutputFormat::Rdjsonl
+        );
+        assert_eq!(
+            OutputFormat::from_str("rdjson").unwrap(),
+            OutputFormat::Rdjsonl
+        );
+        assert!(OutputFormat::from_str("fancy").is_err());
+        for name in OutputFormat::VARIANTS {
+            assert!(OutputFormat::from_str(name).is_ok(), "{}", name);
+        }
+    }
+}
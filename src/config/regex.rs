@@ -69,7 +69,8 @@ impl<'de> serde::de::Visitor<'de> for RegexVisitor {
     where
         E: serde::de::Error,
     {
-        let re = Regex::new(value).map_err(E::custom)?;
+        let re = Regex::new(value)
+            .map_err(|e| E::custom(format!("Invalid regular expression >{}<: {}", value, e)))?;
         Ok(re)
     }
 
@@ -16,6 +16,15 @@ fn os_specific_search_dirs() -> &'static [PathBuf] {
                     // Arch Linux
                     PathBuf::from("/usr/share/myspell/dicts/"),
                 ]
+            } else if cfg!(target_os = "freebsd") || cfg!(target_os = "openbsd") {
+                vec![
+                    PathBuf::from("/usr/local/share/hunspell/"),
+                    PathBuf::from("/usr/local/share/myspell/"),
+                ]
+            } else if cfg!(target_os = "netbsd") {
+                vec![
+                    PathBuf::from("/usr/pkg/share/hunspell/"),
+                ]
             } else {
                 Vec::new()
             };
@@ -44,6 +53,11 @@ impl SearchDirs {
         };
         self.0.iter().chain(chained)
     }
+
+    /// Add `dirs` in front of the configured directories, in the given order.
+    pub fn prepend(&mut self, dirs: impl IntoIterator<Item = PathBuf>) {
+        self.0.splice(0..0, dirs);
+    }
 }
 
 impl std::convert::AsRef<Vec<PathBuf>> for SearchDirs {
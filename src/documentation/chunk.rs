@@ -17,6 +17,9 @@ use crate::{util::sub_chars, Range, Span};
 pub enum ContentOrigin {
     /// A `Cargo.toml` manifest that contains a `description` field.
     CargoManifestDescription(PathBuf),
+    /// A cargo configuration file, i.e. `.cargo/config.toml`, of which only the
+    /// comments are checked.
+    CargoConfig(PathBuf),
     /// A common mark file at given path.
     CommonMarkFile(PathBuf),
     /// A rustdoc comment, part of file reference by path in span.
@@ -41,6 +44,7 @@ impl ContentOrigin {
     pub fn as_path(&self) -> &Path {
         match self {
             Self::CargoManifestDescription(path) => path.as_path(),
+            Self::CargoConfig(path) => path.as_path(),
             Self::CommonMarkFile(path) => path.as_path(),
             Self::RustDocTest(path, _) => path.as_path(),
             Self::RustSourceFile(path) => path.as_path(),
@@ -60,11 +64,110 @@ impl ContentOrigin {
             }
         }
     }
+
+    /// The name of the variant, i.e. `RustSourceFile`.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Self::CargoManifestDescription(_) => "CargoManifestDescription",
+            Self::CargoConfig(_) => "CargoConfig",
+            Self::CommonMarkFile(_) => "CommonMarkFile",
+            Self::RustDocTest(_, _) => "RustDocTest",
+            Self::RustSourceFile(_) => "RustSourceFile",
+            #[cfg(test)]
+            Self::TestEntityRust => "TestEntityRust",
+            #[cfg(test)]
+            Self::TestEntityCommonMark => "TestEntityCommonMark",
+        }
+    }
+
+    /// A fingerprint of the origin, i.e. to be used as cache key.
+    ///
+    /// Unlike the `Hash` implementation, this is stable across platforms,
+    /// compiler versions and runs. Uses the 64 bit FNV-1a hash of the kind of
+    /// origin, the path with `/` as separator and, for `RustDocTest`, the span.
+    pub fn fingerprint(&self) -> u64 {
+        use crate::util::{fnv1a as feed, FNV_OFFSET_BASIS};
+
+        let kind: u8 = match self {
+            Self::CargoManifestDescription(_) => 0,
+            Self::CommonMarkFile(_) => 1,
+            Self::RustDocTest(_, _) => 2,
+            Self::RustSourceFile(_) => 3,
+            #[cfg(test)]
+            Self::TestEntityRust => 4,
+            #[cfg(test)]
+            Self::TestEntityCommonMark => 5,
+            Self::CargoConfig(_) => 6,
+        };
+        let path = self.as_path().to_string_lossy().replace('\\', "/");
+        let mut hash = feed(feed(FNV_OFFSET_BASIS, &[kind]), path.as_bytes());
+        if let Self::RustDocTest(_, span) = self {
+            for x in [
+                span.start.line,
+                span.start.column,
+                span.end.line,
+                span.end.column,
+            ] {
+                hash = feed(hash, &(x as u64).to_le_bytes());
+            }
+        }
+        hash
+    }
 }
 
+/// Rust sources first, then common mark files, manifest descriptions and cargo
+/// configuration files, each ordered by path. Doc tests of the same file are ordered by their span.
+impl Ord for ContentOrigin {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        /// Kind of content, and a tie breaker for variants of the same kind.
+        fn rank(origin: &ContentOrigin) -> (u8, u8) {
+            match origin {
+                ContentOrigin::RustSourceFile(_) => (0, 0),
+                ContentOrigin::RustDocTest(_, _) => (0, 1),
+                ContentOrigin::CommonMarkFile(_) => (1, 0),
+                ContentOrigin::CargoManifestDescription(_) => (2, 0),
+                ContentOrigin::CargoConfig(_) => (3, 0),
+                #[cfg(test)]
+                ContentOrigin::TestEntityRust => (0, 2),
+                #[cfg(test)]
+                ContentOrigin::TestEntityCommonMark => (1, 1),
+            }
+        }
+        fn span(origin: &ContentOrigin) -> Option<(LineColumn, LineColumn)> {
+            match origin {
+                ContentOrigin::RustDocTest(_, span) => Some((span.start, span.end)),
+                _ => None,
+            }
+        }
+        let (kind, variant) = rank(self);
+        let (other_kind, other_variant) = rank(other);
+        kind.cmp(&other_kind)
+            .then_with(|| self.as_path().cmp(other.as_path()))
+            .then_with(|| span(self).cmp(&span(other)))
+            .then_with(|| variant.cmp(&other_variant))
+    }
+}
+
+impl PartialOrd for ContentOrigin {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Displays as `path`, or `path:line:column` of the start of the span for
+/// `RustDocTest`.
 impl fmt::Display for ContentOrigin {
     fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(formatter, "{}", self.as_path().display())
+        match self {
+            Self::RustDocTest(path, span) => write!(
+                formatter,
+                "{}:{}:{}",
+                path.display(),
+                span.start.line,
+                span.start.column + 1
+            ),
+            origin => write!(formatter, "{}", origin.as_path().display()),
+        }
     }
 }
 
@@ -81,6 +184,9 @@ pub struct CheckableChunk {
     source_mapping: IndexMap<Range, Span>,
     /// Track what kind of comment the chunk is.
     variant: CommentVariant,
+    /// Words that must not be reported, mapped by the range within `content`
+    /// of the literal they were annotated on.
+    ignored_words: IndexMap<Range, Vec<String>>,
 }
 
 impl std::hash::Hash for CheckableChunk {
@@ -91,6 +197,9 @@ impl std::hash::Hash for CheckableChunk {
             t.hash(hasher);
         });
         self.variant.hash(hasher);
+        self.ignored_words.iter().for_each(|t| {
+            t.hash(hasher);
+        });
     }
 }
 
@@ -101,6 +210,41 @@ impl CheckableChunk {
         set.into_chunk()
     }
 
+    /// The spans which exceed the bounds of `source`, the content the chunk was
+    /// extracted from, each with the first offending position and the number
+    /// of characters of its line, or `None` if there is no such line.
+    ///
+    /// A column may refer to the line break, since the spans of empty literals
+    /// start right after the comment prefix.
+    pub(crate) fn spans_out_of_bounds(
+        &self,
+        source: &str,
+    ) -> Vec<(Span, LineColumn, Option<usize>)> {
+        let line_lengths = source
+            .lines()
+            .map(|line| line.chars().count())
+            .collect::<Vec<_>>();
+        let exceeds = |position: LineColumn| -> Option<(LineColumn, Option<usize>)> {
+            match position
+                .line
+                .checked_sub(1)
+                .and_then(|idx| line_lengths.get(idx))
+            {
+                Some(&len) if position.column <= len => None,
+                Some(&len) => Some((position, Some(len))),
+                None => Some((position, None)),
+            }
+        };
+        self.source_mapping
+            .values()
+            .filter_map(|span| {
+                exceeds(span.start)
+                    .or_else(|| exceeds(span.end))
+                    .map(|(position, line_length)| (*span, position, line_length))
+            })
+            .collect()
+    }
+
     /// Load content from string, may contain common mark content.
     pub fn from_str(
         content: &str,
@@ -120,9 +264,30 @@ impl CheckableChunk {
             content,
             source_mapping,
             variant,
+            ignored_words: IndexMap::new(),
         }
     }
 
+    /// Attach words which must not be reported, mapped by the range within the
+    /// chunk they apply to.
+    pub(crate) fn with_ignored_words(
+        mut self,
+        ignored_words: IndexMap<Range, Vec<String>>,
+    ) -> Self {
+        self.ignored_words = ignored_words;
+        self
+    }
+
+    /// Check if `word` located at `range` was explicitly marked as ignored,
+    /// i.e. by a `// [nocheck: ..]` annotation.
+    pub fn is_ignored_word(&self, range: &Range, word: &str) -> bool {
+        self.ignored_words.iter().any(|(ignore_range, words)| {
+            ignore_range.start <= range.start
+                && range.end <= ignore_range.end
+                && words.iter().any(|ignored| ignored == word)
+        })
+    }
+
     /// Find which part of the range maps to which span. Note that Range can
     /// very well be split into multiple fragments where each of them can be
     /// mapped to a potentially non-continuous span.
@@ -387,10 +552,35 @@ impl CheckableChunk {
     pub fn variant(&self) -> CommentVariant {
         self.variant.clone()
     }
+
+    /// A JSON object describing the chunk of `origin`, its content and the
+    /// source mapping with 1-indexed lines and columns.
+    pub fn to_json(&self, origin: &ContentOrigin) -> serde_json::Value {
+        serde_json::json!({
+            "origin": origin.kind(),
+            "path": crate::util::relative_to_cwd(origin.as_path()),
+            "variant": format!("{:?}", self.variant),
+            "content": self.content,
+            "source_mapping": self
+                .source_mapping
+                .iter()
+                .map(|(range, span)| {
+                    serde_json::json!({
+                        "range": [range.start, range.end],
+                        "start": { "line": span.start.line, "column": span.start.column + 1 },
+                        "end": { "line": span.end.line, "column": span.end.column + 1 },
+                    })
+                })
+                .collect::<Vec<_>>(),
+        })
+    }
 }
 
 /// Convert the clusters of one file into a source description as well as well
 /// as vector of checkable chunks.
+///
+/// The chunks carry no origin of their own, `Clusters` never spans multiple
+/// files, so the origin of the file applies to all of them.
 impl From<Clusters> for Vec<CheckableChunk> {
     fn from(clusters: Clusters) -> Vec<CheckableChunk> {
         clusters
@@ -5,11 +5,13 @@ use syn::LitStr;
 use syn::Macro;
 use syn::Token;
 
-use super::{trace, LiteralSet, TokenTree, TrimmedLiteral};
+use super::{trace, AdjacencyError, ExtractOptions, LiteralSet, TokenTree, TrimmedLiteral};
 use crate::documentation::developer::extract_developer_comments;
 use crate::errors::*;
 use crate::Span;
 
+use std::cell::RefCell;
+
 mod kw {
     syn::custom_keyword!(doc);
 }
@@ -54,29 +56,135 @@ impl syn::parse::Parse for DocComment {
     }
 }
 
+thread_local! {
+    /// The most recently parsed source and its token stream.
+    static LAST_STREAM: RefCell<Option<(String, proc_macro2::TokenStream)>> = RefCell::new(None);
+}
+
+/// Parse `source` to a token stream, reusing the result if the same source was
+/// parsed last.
+///
+/// Rust files are parsed for their `mod` declarations as well as for their
+/// documentation, in direct succession. Only the last file is retained, so the
+/// cache never grows beyond a single file.
+pub(crate) fn tokenize_cached(source: &str) -> syn::Result<proc_macro2::TokenStream> {
+    LAST_STREAM.with(|last| {
+        let mut last = last.borrow_mut();
+        if let Some((ref cached, ref stream)) = *last {
+            if cached == source {
+                return Ok(stream.clone());
+            }
+        }
+        let stream = syn::parse_str::<proc_macro2::TokenStream>(source)?;
+        *last = Some((source.to_owned(), stream.clone()));
+        Ok(stream)
+    })
+}
+
 /// Cluster comments together, such they appear as continuous text blocks.
+///
+/// Always holds the literal sets of a single file, the one whose source it was
+/// loaded from, so the chunks converted from it all share one `ContentOrigin`,
+/// which is tracked by the caller. Files included via `include_str!` are only
+/// recorded in `included` and added as origins of their own.
 #[derive(Debug)]
 pub struct Clusters {
     pub(super) set: Vec<LiteralSet>,
+    /// The fence of the code block of doc comments currently within, "```"
+    /// or "~~~".
+    in_code_block: Option<&'static str>,
+    /// The last line of the previously processed doc comment.
+    last_doc_line: Option<usize>,
+    /// Names of the attributes whose string values are checked.
+    attr_keys: Vec<String>,
+    /// Names of the formatting macros whose string arguments are checked.
+    macro_names: Vec<String>,
+    /// Paths of the files included via `#[doc = include_str!("..")]`, as
+    /// written.
+    pub(super) included: Vec<String>,
 }
 
 impl Clusters {
+    /// Track fenced code blocks across consecutive doc comment lines.
+    ///
+    /// Code blocks are compiled as doc tests, so neither the fences nor the
+    /// lines in between are prose, and must not become part of a
+    /// `LiteralSet`.
+    fn is_code_block_line(&mut self, literal: &TrimmedLiteral) -> bool {
+        let span = literal.span();
+        // a code block never spans across non-consecutive doc comments
+        if self
+            .last_doc_line
+            .map(|line| line + 1 != span.start.line)
+            .unwrap_or(true)
+        {
+            self.in_code_block = None;
+        }
+        self.last_doc_line = Some(span.end.line);
+
+        // the literal of a block comment spans multiple lines, it is only
+        // skipped if all of them are part of a code block
+        let mut all_code = true;
+        for line in literal.as_str().split('\n') {
+            let fence = ["```", "~~~"]
+                .into_iter()
+                .find(|fence| line.trim_start().starts_with(fence));
+            let is_code = match (self.in_code_block, fence) {
+                (None, Some(fence)) => {
+                    self.in_code_block = Some(fence);
+                    true
+                }
+                (Some(open), Some(fence)) if open == fence => {
+                    self.in_code_block = None;
+                    true
+                }
+                (Some(_), _) => true,
+                (None, None) => false,
+            };
+            all_code &= is_code;
+        }
+        all_code
+    }
+
     /// Only works if the file is processed line by line, otherwise requires a
     /// adjacency list.
     fn process_literal(&mut self, source: &str, comment: DocComment) -> Result<()> {
         let span = Span::from(comment.content.span());
         let trimmed_literal = match comment.content {
             DocContent::LitStr(_s) => TrimmedLiteral::load_from(source, span)?,
-            DocContent::Macro(_) => {
+            DocContent::Macro(ref mac) => {
+                if mac
+                    .path
+                    .segments
+                    .last()
+                    .map(|segment| segment.ident == "include_str")
+                    .unwrap_or(false)
+                {
+                    match mac.parse_body::<LitStr>() {
+                        Ok(path) => self.included.push(path.value()),
+                        Err(e) => {
+                            trace!(target: "documentation", "Skipping `include_str!` without a literal path: {}", e)
+                        }
+                    }
+                }
                 TrimmedLiteral::new_empty(source, span, crate::CommentVariant::MacroDocEqMacro)
             }
         };
+        if self.is_code_block_line(&trimmed_literal) {
+            trace!(target: "documentation", "Skipping code block line {:?}", &trimmed_literal);
+            return Ok(());
+        }
         if let Some(cls) = self.set.last_mut() {
-            if let Err(trimmed_literal) = cls.add_adjacent(trimmed_literal) {
+            if let Err(AdjacencyError {
+                literal: trimmed_literal,
+                reason,
+            }) = cls.add_adjacent(trimmed_literal)
+            {
                 trace!(target: "documentation",
-                    "appending, but failed to append: {:?} to set {:?}",
+                    "appending, but failed to append: {:?} to set {:?}: {}",
                     &trimmed_literal,
-                    &cls
+                    &cls,
+                    reason
                 );
                 self.set.push(LiteralSet::from(trimmed_literal))
             } else {
@@ -88,10 +196,117 @@ impl Clusters {
         Ok(())
     }
 
+    /// Add a string literal as a separate `LiteralSet`, string literals are
+    /// never merged with anything else.
+    ///
+    /// The format specifiers of string literals passed to one of the
+    /// `macro_names` are blanked.
+    fn process_string_literal(
+        &mut self,
+        source: &str,
+        literal: proc_macro2::Literal,
+        macro_arg: bool,
+    ) {
+        // only plain and raw string literals, no byte strings, chars or numbers
+        let repr = literal.to_string();
+        if !(repr.starts_with('"') || repr.starts_with("r\"") || repr.starts_with("r#")) {
+            return;
+        }
+        match TrimmedLiteral::load_string_literal(source, Span::from(literal.span())) {
+            Ok(trimmed_literal) if macro_arg => self
+                .set
+                .push(LiteralSet::from(trimmed_literal.blank_format_specifiers())),
+            Ok(trimmed_literal) => self.set.push(LiteralSet::from(trimmed_literal)),
+            Err(e) => {
+                trace!(target: "documentation", "Skipping string literal {}: {:?}", repr, e)
+            }
+        }
+    }
+
+    /// Add the values of `#[doc(alias = "..")]` attributes, each as a separate
+    /// `LiteralSet`, given the stream within the brackets of an attribute.
+    fn process_doc_aliases(&mut self, source: &str, stream: proc_macro2::TokenStream) {
+        let mut iter = stream.into_iter();
+        match (iter.next(), iter.next()) {
+            (Some(TokenTree::Ident(ident)), Some(TokenTree::Group(group)))
+                if ident == "doc" && group.delimiter() == proc_macro2::Delimiter::Parenthesis =>
+            {
+                let tokens = group.stream().into_iter().collect::<Vec<_>>();
+                for window in tokens.windows(3) {
+                    match window {
+                        [TokenTree::Ident(key), TokenTree::Punct(punct), TokenTree::Literal(literal)]
+                            if key == "alias" && punct.as_char() == '=' =>
+                        {
+                            self.process_string_literal(source, literal.clone(), false)
+                        }
+                        _ => {}
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Add the string values of attributes with one of the `attr_keys`, each as
+    /// a separate `LiteralSet`, given the stream within the brackets of an
+    /// attribute.
+    ///
+    /// Covers positional values `#[error("..")]` and `#[description = ".."]`,
+    /// but not nested key value pairs such as `#[error(fmt = "..")]`.
+    fn process_attr_values(&mut self, source: &str, stream: proc_macro2::TokenStream) {
+        let mut iter = stream.into_iter();
+        let key = match iter.next() {
+            Some(TokenTree::Ident(ident)) if self.attr_keys.iter().any(|key| ident == key) => ident,
+            _ => return,
+        };
+        match iter.next() {
+            Some(TokenTree::Group(group))
+                if group.delimiter() == proc_macro2::Delimiter::Parenthesis =>
+            {
+                let mut positional = true;
+                for tree in group.stream() {
+                    positional = match tree {
+                        TokenTree::Literal(literal) if positional => {
+                            self.process_string_literal(source, literal, false);
+                            false
+                        }
+                        TokenTree::Punct(punct) => punct.as_char() == ',',
+                        _ => false,
+                    };
+                }
+            }
+            Some(TokenTree::Punct(punct)) if punct.as_char() == '=' => {
+                if let Some(TokenTree::Literal(literal)) = iter.next() {
+                    self.process_string_literal(source, literal, false);
+                }
+            }
+            _ => {
+                trace!(target: "documentation", "Attribute {} has no string value", key);
+            }
+        }
+    }
+
     /// Helper function to parse a stream and associate the found literals.
-    fn parse_token_tree(&mut self, source: &str, stream: proc_macro2::TokenStream) -> Result<()> {
+    ///
+    /// With `macro_args`, the stream are the arguments of one of the
+    /// `macro_names`, such as `println!`, and its string literals are added.
+    fn parse_token_tree(
+        &mut self,
+        source: &str,
+        stream: proc_macro2::TokenStream,
+        string_literals: bool,
+        macro_args: bool,
+    ) -> Result<()> {
         let mut iter = stream.into_iter();
+        // string literals within attributes, i.e. `#[cfg(feature = "foo")]`
+        // are not prose
+        let mut attribute = false;
+        // the name of a macro in `macro_names`, and the `!` following it
+        let mut macro_name = false;
+        let mut macro_bang = false;
         while let Some(tree) = iter.next() {
+            let after_name = std::mem::take(&mut macro_name);
+            let macro_call = std::mem::take(&mut macro_bang);
             match tree {
                 TokenTree::Group(group) => {
                     if let Ok(comment) = syn::parse2::<DocComment>(group.stream()) {
@@ -103,10 +318,33 @@ impl Clusters {
                             continue;
                         }
                     } else {
-                        self.parse_token_tree(source, group.stream())?;
+                        let is_attribute =
+                            attribute && group.delimiter() == proc_macro2::Delimiter::Bracket;
+                        if is_attribute {
+                            self.process_doc_aliases(source, group.stream());
+                            self.process_attr_values(source, group.stream());
+                        }
+                        let string_literals = string_literals && !is_attribute;
+                        self.parse_token_tree(source, group.stream(), string_literals, macro_call)?;
                     }
+                    attribute = false;
+                }
+                TokenTree::Punct(punct) => {
+                    macro_bang = after_name && punct.as_char() == '!';
+                    // `#[..]` as well as `#![..]`
+                    attribute = punct.as_char() == '#' || (attribute && punct.as_char() == '!');
+                }
+                TokenTree::Literal(literal) if string_literals || macro_args => {
+                    self.process_string_literal(source, literal, macro_args);
+                    attribute = false;
+                }
+                TokenTree::Ident(ident) => {
+                    macro_name = self.macro_names.iter().any(|name| ident == name);
+                    attribute = false;
+                }
+                _ => {
+                    attribute = false;
                 }
-                _ => {}
             };
         }
         Ok(())
@@ -126,16 +364,21 @@ impl Clusters {
         self.set.sort_by(|ls1, ls2| ls1.coverage.cmp(&ls2.coverage));
     }
 
-    /// Load clusters from a `&str`. Optionally loads developer comments as
-    /// well.
-    pub(crate) fn load_from_str(source: &str, dev_comments: bool) -> Result<Self> {
+    /// Load clusters from a `&str`, with everything besides doc comments
+    /// selected by `options`.
+    pub(crate) fn load_from_str(source: &str, options: &ExtractOptions) -> Result<Self> {
         let mut chunk = Self {
             set: Vec::with_capacity(64),
+            in_code_block: None,
+            last_doc_line: None,
+            attr_keys: options.attr_keys.clone(),
+            macro_names: options.macro_names.clone(),
+            included: Vec::new(),
         };
-        let stream = syn::parse_str::<proc_macro2::TokenStream>(source)
-            .wrap_err_with(|| eyre!("Failed to parse content to stream"))?;
-        chunk.parse_token_tree(source, stream)?;
-        if dev_comments {
+        let stream =
+            tokenize_cached(source).wrap_err_with(|| eyre!("Failed to parse content to stream"))?;
+        chunk.parse_token_tree(source, stream, options.string_literals, false)?;
+        if options.dev_comments {
             chunk.parse_developer_comments(source);
         }
         chunk.ensure_sorted();
@@ -170,7 +413,7 @@ struct X;
 
 }
 "#####;
-        let clusters = Clusters::load_from_str(CONTENT, false).unwrap();
+        let clusters = Clusters::load_from_str(CONTENT, &ExtractOptions::default()).unwrap();
         assert_eq!(clusters.set.len(), 1);
         dbg!(&clusters.set[0]);
     }
@@ -185,8 +428,262 @@ struct X;
 // ```
 struct DefinitelyNotZ;
 "#####;
-        let clusters = Clusters::load_from_str(CONTENT, true).unwrap();
+        let clusters = Clusters::load_from_str(
+            CONTENT,
+            &ExtractOptions {
+                dev_comments: true,
+                ..Default::default()
+            },
+        )
+        .unwrap();
         assert_eq!(clusters.set.len(), 1);
         dbg!(&clusters.set[0]);
     }
+
+    #[test]
+    fn code_blocks_are_excluded() {
+        static CONTENT: &str = r#####"
+/// Prose before.
+///
+/// ```rust,no_run
+/// let fancy_identifyer = 1;
+/// ```
+///
+/// Prose after.
+struct X;
+
+/// ```
+/// unterminated();
+struct Y;
+
+/// Separate.
+struct Z;
+"#####;
+        let clusters = Clusters::load_from_str(CONTENT, &ExtractOptions::default()).unwrap();
+        let sets = clusters
+            .set
+            .iter()
+            .map(|set| {
+                set.literals()
+                    .into_iter()
+                    .map(|literal| literal.as_str().trim())
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            sets,
+            vec![
+                vec!["Prose before.", ""],
+                vec!["", "Prose after."],
+                vec!["Separate."]
+            ]
+        );
+    }
+
+    #[test]
+    fn code_blocks_of_block_comments_and_tildes() {
+        static CONTENT: &str = r#####"
+/** ```
+let fancy_identifyer = 1;
+```
+Prose after. */
+struct X;
+
+/// ~~~
+/// let fancy_identifyer = "```";
+/// ~~~
+/// Prose after tildes.
+struct Y;
+"#####;
+        let clusters = Clusters::load_from_str(CONTENT, &ExtractOptions::default()).unwrap();
+        let sets = clusters
+            .set
+            .iter()
+            .map(|set| {
+                set.literals()
+                    .into_iter()
+                    .map(|literal| literal.as_str().trim().to_owned())
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(sets.len(), 2);
+        assert!(sets[0][0].ends_with("Prose after."));
+        assert_eq!(sets[1], vec!["Prose after tildes.".to_owned()]);
+    }
+
+    #[test]
+    fn doc_aliases() {
+        static CONTENT: &str = r#####"
+/// A tuple.
+#[doc(alias = "pair")]
+#[doc(hidden, alias = "duo")]
+#[cfg_attr(feature = "nope", derive(Debug))]
+struct Tuple(u8, u8);
+"#####;
+        let clusters = Clusters::load_from_str(CONTENT, &ExtractOptions::default()).unwrap();
+        assert_eq!(clusters.set.len(), 3);
+        assert_eq!(clusters.set[0].literals()[0].as_str(), " A tuple.");
+        for (set, alias) in clusters.set[1..].iter().zip(["pair", "duo"]) {
+            let literals = set.literals();
+            assert_eq!(literals.len(), 1);
+            assert_eq!(literals[0].as_str(), alias);
+            assert_eq!(literals[0].pre(), 1);
+            assert_eq!(literals[0].post(), 1);
+            assert_eq!(
+                crate::util::load_span_from(CONTENT.as_bytes(), literals[0].span()).unwrap(),
+                alias
+            );
+        }
+    }
+
+    #[test]
+    fn string_literals() {
+        static CONTENT: &str = r#####"
+#[cfg(feature = "nope")]
+fn x() -> &'static str {
+    println!("Twas\tbrillig");
+    let _ = b"bytes";
+    r##"and "the" slithy"##
+}
+"#####;
+        let clusters = Clusters::load_from_str(CONTENT, &ExtractOptions::default()).unwrap();
+        assert!(clusters.set.is_empty());
+
+        let clusters = Clusters::load_from_str(
+            CONTENT,
+            &ExtractOptions {
+                string_literals: true,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        assert_eq!(clusters.set.len(), 2);
+        let literals = clusters.set[0].literals();
+        assert_eq!(literals[0].as_str(), "Twas  brillig");
+        assert_eq!(
+            literals[0].variant(),
+            crate::CommentVariant::StringLiteral(0)
+        );
+        assert_eq!(
+            crate::util::load_span_from(CONTENT.as_bytes(), literals[0].span()).unwrap(),
+            "Twas\\tbrillig"
+        );
+        let literals = clusters.set[1].literals();
+        assert_eq!(literals[0].as_str(), r#"and "the" slithy"#);
+        assert_eq!(
+            literals[0].variant(),
+            crate::CommentVariant::StringLiteral(3)
+        );
+        assert_eq!(
+            crate::util::load_span_from(CONTENT.as_bytes(), literals[0].span()).unwrap(),
+            r#"and "the" slithy"#
+        );
+    }
+
+    #[test]
+    fn attr_values() {
+        static CONTENT: &str = r#####"
+#[derive(Debug, thiserror::Error)]
+#[description = "Failures"]
+enum Error {
+    #[error("Invalid {0} given", fmt = "nope")]
+    Invalid(String),
+    #[error(transparent)]
+    Io(std::io::Error),
+    #[cfg(feature = "nope")]
+    Other,
+}
+"#####;
+        let clusters = Clusters::load_from_str(CONTENT, &ExtractOptions::default()).unwrap();
+        assert!(clusters.set.is_empty());
+
+        let keys = ["error".to_owned(), "description".to_owned()];
+        let clusters = Clusters::load_from_str(
+            CONTENT,
+            &ExtractOptions {
+                attr_keys: keys.to_vec(),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        let values = clusters
+            .set
+            .iter()
+            .map(|set| set.literals()[0].as_str().to_owned())
+            .collect::<Vec<_>>();
+        assert_eq!(values, vec!["Failures", "Invalid {0} given"]);
+        assert_eq!(
+            crate::util::load_span_from(CONTENT.as_bytes(), clusters.set[1].literals()[0].span())
+                .unwrap(),
+            "Invalid {0} given"
+        );
+    }
+
+    #[test]
+    fn macro_strings() {
+        static CONTENT: &str = r#####"
+fn x(f: &mut Formatter) -> fmt::Result {
+    println!("Got {} and {:?}, {{escaped}}", a, b);
+    std::eprintln!("Fialed to {verb}", verb = "parse");
+    let _ = other!("not checked");
+    writeln!(f, r#"See {0}"#, "here")
+}
+"#####;
+        let clusters = Clusters::load_from_str(CONTENT, &ExtractOptions::default()).unwrap();
+        assert!(clusters.set.is_empty());
+
+        let names = ["println", "eprintln", "writeln"].map(ToOwned::to_owned);
+        let clusters = Clusters::load_from_str(
+            CONTENT,
+            &ExtractOptions {
+                macro_names: names.to_vec(),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        let values = clusters
+            .set
+            .iter()
+            .map(|set| set.literals()[0].as_str().to_owned())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            values,
+            vec![
+                "Got    and     , {{escaped}}",
+                "Fialed to       ",
+                "parse",
+                "See    ",
+                "here"
+            ]
+        );
+        assert_eq!(
+            crate::util::load_span_from(CONTENT.as_bytes(), clusters.set[1].literals()[0].span())
+                .unwrap(),
+            "Fialed to {verb}"
+        );
+    }
+
+    #[test]
+    fn tokenize_cached_retains_last_source() {
+        let cached = |source: &str| {
+            LAST_STREAM.with(|last| {
+                last.borrow()
+                    .as_ref()
+                    .map_or(false, |(cached, _stream)| cached == source)
+            })
+        };
+        let stream = tokenize_cached("mod a;").unwrap();
+        assert!(cached("mod a;"));
+        assert_eq!(
+            tokenize_cached("mod a;").unwrap().to_string(),
+            stream.to_string()
+        );
+
+        tokenize_cached("mod b;").unwrap();
+        assert!(cached("mod b;"));
+        assert!(!cached("mod a;"));
+
+        assert!(tokenize_cached("mod {").is_err());
+        assert!(cached("mod b;"));
+    }
 }
@@ -3,6 +3,7 @@ use std::fmt::{Display, Formatter};
 
 use ra_ap_syntax::{ast, AstToken};
 
+use once_cell::sync::Lazy;
 use regex::Regex;
 
 use super::*;
@@ -25,36 +26,59 @@ const LINE_COMMENT_POSTFIX: &str = "";
 /// Postfix string for any other token type (i.e. we don't care)
 const OTHER_POSTFIX: &str = "";
 
-lazy_static::lazy_static! {
-  static ref BLOCK_COMMENT: Regex = Regex::new(r"^/\*(?s)(?P<content>.*)\*/$")
-      .expect("Failed to create regular expression to identify (closed) developer block comments. \
-          Please check this regex!");
-  static ref LINE_COMMENT: Regex = Regex::new(r"^//([^[/|!]].*)?$")
-      .expect("Failed to create regular expression to identify developer line comments. \
-          Please check this regex!");
-}
+static BLOCK_COMMENT: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^/\*(?s)(?P<content>.*)\*/$").expect(
+        "Failed to create regular expression to identify (closed) developer block comments. \
+          Please check this regex!",
+    )
+});
+/// A `//` comment, but neither a `///` outer nor a `//!` inner doc comment.
+/// Four or more slashes are a plain comment again, just like for `rustc`.
+static LINE_COMMENT: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^//(?:[^/!].*|//.*)?$").expect(
+        "Failed to create regular expression to identify developer line comments. \
+          Please check this regex!",
+    )
+});
+static BLOCK_COMMENT_LINE_PREFIX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^\s*\*\s?").expect(
+        "Failed to create regular expression to identify the leading `*` of block \
+          comment lines. Please check this regex!",
+    )
+});
+/// A `// noinspection SpellCheckingInspection` annotation as used by JetBrains
+/// IDEs, possibly among other inspections, separated by commas.
+static NOINSPECTION: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^\s*noinspection\s+(?:\w+\s*,\s*)*SpellCheckingInspection\b").expect(
+        "Failed to create regular expression to identify `noinspection` annotations. \
+          Please check this regex!",
+    )
+});
 
 /// A string token from a source string with the location at which it occurs in
 /// the source string as line on which it occurs (1 indexed) and the column of
 /// its first character (0 indexed)
 #[derive(Debug)]
-struct TokenWithLineColumn {
+pub struct TokenWithLineColumn {
     /// The full contents of this token, including pre/post characters (like
     /// '//')
-    content: String,
+    pub content: String,
     /// The first line on which the token appears in the source file (1 indexed)
-    line: usize,
+    pub line: usize,
     /// The column where the first character of this token appears in the source
     /// file (0 indexed)
-    column: usize,
+    pub column: usize,
 }
 
 /// Is a token of type (developer) block comment, (developer) line comment or
 /// something else
-#[derive(Debug, Eq, PartialEq)]
-enum TokenType {
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum TokenType {
+    /// A developer block comment `/* .. */`
     BlockComment,
+    /// A developer line comment `// ..`
     LineComment,
+    /// Anything else, including documentation comments
     Other,
 }
 
@@ -70,6 +94,16 @@ impl Display for TokenType {
 }
 
 impl TokenType {
+    /// Classify the full content of a token, including the comment markers.
+    fn classify(content: &str) -> Self {
+        if BLOCK_COMMENT.is_match(content) {
+            TokenType::BlockComment
+        } else if LINE_COMMENT.is_match(content) {
+            TokenType::LineComment
+        } else {
+            TokenType::Other
+        }
+    }
     /// The prefix string for this type of token
     fn pre(&self) -> &str {
         match self {
@@ -100,10 +134,10 @@ impl TokenType {
 /// column on which it occurs according to the description for
 /// `TokenWithLineColumn`
 #[derive(Debug)]
-struct TokenWithType {
+pub struct TokenWithType {
     /// Is the token a block developer comment, line developer comment or
     /// something else
-    kind: TokenType,
+    pub kind: TokenType,
     /// The full contents of this token, including pre/post characters (like
     /// '//')
     pub content: String,
@@ -120,15 +154,8 @@ impl TokenWithType {
     /// out from the content by checking against the developer block comment &
     /// line comment regexps.
     fn from(token: TokenWithLineColumn) -> Self {
-        let kind = if BLOCK_COMMENT.is_match(&token.content) {
-            TokenType::BlockComment
-        } else if LINE_COMMENT.is_match(&token.content) {
-            TokenType::LineComment
-        } else {
-            TokenType::Other
-        };
         Self {
-            kind,
+            kind: TokenType::classify(&token.content),
             content: token.content,
             line: token.line,
             column: token.column,
@@ -136,19 +163,113 @@ impl TokenWithType {
     }
 }
 
+/// Location and type of a token within the source, the pipeline's internal
+/// representation which avoids allocating the content of each token.
+#[derive(Debug, Clone, Copy)]
+struct TokenDescriptor {
+    /// Byte offset of the first character in the source
+    offset: u32,
+    /// Length in bytes, including pre/post characters (like '//')
+    length: u32,
+    /// The first line on which the token appears in the source file (1 indexed)
+    line: u32,
+    /// The column where the first character of this token appears in the
+    /// source file (0 indexed)
+    column: u32,
+    kind: TokenType,
+}
+
+impl TokenDescriptor {
+    /// The full contents of this token within `source`.
+    fn content<'s>(&self, source: &'s str) -> &'s str {
+        let offset = self.offset as usize;
+        &source[offset..(offset + self.length as usize)]
+    }
+    /// The first line as `usize`.
+    fn line(&self) -> usize {
+        self.line as usize
+    }
+    /// The column as `usize`.
+    fn column(&self) -> usize {
+        self.column as usize
+    }
+}
+
+/// Classify a single token as developer block comment, developer line comment
+/// or something else, without running the complete pipeline.
+pub fn identify_token_type(token: TokenWithLineColumn) -> TokenWithType {
+    TokenWithType::from(token)
+}
+
 /// A convenience method that runs the complete 'pipeline' from string `source`
 /// file to all `LiteralSet`s that can be created from developer comments in the
 /// source
 pub fn extract_developer_comments(source: &str) -> Vec<LiteralSet> {
-    let tokens = source_to_iter(source).collect::<Vec<_>>();
-    let comments = construct_literal_sets(tokens);
-    comments
+    // Avoid parsing the whole source if there can't be any comments. String
+    // literals containing `//` or `/*` only cause the regular path to be taken.
+    if !source.contains(LINE_COMMENT_PREFIX) && !source.contains(BLOCK_COMMENT_PREFIX) {
+        return Vec::new();
+    }
+    construct_literal_sets(source, source_to_iter(source))
+}
+
+/// Extracts the `#` comments of a TOML file, such as `.cargo/config.toml`, to
+/// `LiteralSet`s, treating them like developer line comments.
+///
+/// Only lines starting with `#` are comments, a trailing comment after a value
+/// is not picked up, which avoids having to tell apart a `#` within a string.
+pub(crate) fn extract_toml_comments(source: &str) -> Vec<LiteralSet> {
+    let mut sets: Vec<LiteralSet> = Vec::new();
+    for (idx, line) in source.lines().enumerate() {
+        let indent = line.len() - line.trim_start().len();
+        let comment = line[indent..].trim_end_matches('\r');
+        if !comment.starts_with('#') {
+            continue;
+        }
+        let literal = match TrimmedLiteral::from(
+            CommentVariant::TomlComment,
+            comment,
+            1,
+            0,
+            idx + 1,
+            line[..indent].chars().count(),
+        ) {
+            Ok(literal) => literal,
+            Err(err) => {
+                log::trace!(
+                    "Failed to create literal from comment with content \"{}\" due to \"{}\"",
+                    comment,
+                    err
+                );
+                continue;
+            }
+        };
+        if let Some(set) = sets.last_mut() {
+            if let Err(AdjacencyError { literal, reason }) = set.add_adjacent(literal) {
+                log::trace!("Starting a new literal set: {}", reason);
+                sets.push(LiteralSet::from(literal));
+            }
+        } else {
+            sets.push(LiteralSet::from(literal));
+        }
+    }
+    sets
 }
 
-/// Creates a series of `TokenWithType`s from a source string
-fn source_to_iter<'a>(source: &'a str) -> impl Iterator<Item = TokenWithType> + 'a {
+/// Creates a series of `TokenDescriptor`s from a source string
+///
+/// Lines are looked up in a precomputed index of newline offsets, columns are
+/// tracked incrementally from the previous token on the same line, so each
+/// character of the source is only counted once. Tokens whose location does
+/// not fit into `u32` are skipped.
+fn source_to_iter<'a>(source: &'a str) -> impl Iterator<Item = TokenDescriptor> + 'a {
     let parse = ast::SourceFile::parse(source);
     let node = parse.syntax_node();
+    let lines = LineIndex::new(source);
+    let mut previous = 0_usize;
+    // in characters, as used by `Span`, byte columns are derived on demand
+    // with `Span::byte_columns`
+    let mut column = 0_usize;
     node.descendants_with_tokens()
         .filter_map(|nort| {
             nort.into_token()
@@ -157,45 +278,110 @@ fn source_to_iter<'a>(source: &'a str) -> impl Iterator<Item = TokenWithType> +
             // for now until it's clear whether #[doc=foo!()]
             // is possible with `ra_ap_syntax`
         })
-        .map(move |comment| {
-            let location = usize::from(comment.syntax().text_range().start());
-            TokenWithType::from(TokenWithLineColumn {
-                content: comment.text().to_owned(),
-                line: count_lines(&source[..location]),
-                column: calculate_column(&source[..location]),
-            })
+        .filter_map(move |comment| {
+            let range = comment.syntax().text_range();
+            let location = usize::from(range.start());
+            let line = lines.line(location);
+            let line_start = lines.line_start(line);
+            if previous < line_start {
+                column = 0;
+            }
+            let skipped = &source[previous.max(line_start)..location];
+            column += skipped.chars().count();
+            previous = location;
+            log::trace!("Comment token at {}:{}", line, column);
+            match (
+                u32::try_from(location),
+                u32::try_from(line),
+                u32::try_from(column),
+            ) {
+                (Ok(offset), Ok(line), Ok(column)) => Some(TokenDescriptor {
+                    offset,
+                    length: u32::from(range.len()),
+                    line,
+                    column,
+                    kind: TokenType::classify(comment.text()),
+                }),
+                _ => {
+                    log::warn!(
+                        "Skipping comment at byte {}, its location exceeds the supported range",
+                        location
+                    );
+                    None
+                }
+            }
         })
 }
 
-/// Given a string, calculates the 1 indexed line number of the line on which
-/// the final character of the string appears
-fn count_lines(fragment: &str) -> usize {
-    fragment.chars().into_iter().filter(|c| *c == '\n').count() + 1
+/// Byte offsets of all newlines of a source string, to look up the line of a
+/// byte offset in `O(log n)`.
+#[derive(Debug, Clone)]
+struct LineIndex {
+    newlines: Vec<usize>,
 }
 
-/// Given a string, calculates the 0 indexed column number of the character
-/// *just after* the final character in the string
-fn calculate_column(fragment: &str) -> usize {
-    match fragment.rfind('\n') {
-        Some(p) => fragment.chars().count() - fragment[..p].chars().count() - 1,
-        None => fragment.chars().count(),
+impl LineIndex {
+    fn new(source: &str) -> Self {
+        Self {
+            newlines: source
+                .bytes()
+                .enumerate()
+                .filter(|&(_, byte)| byte == b'\n')
+                .map(|(idx, _)| idx)
+                .collect(),
+        }
+    }
+
+    /// The 1 indexed line on which the byte at `offset` appears.
+    fn line(&self, offset: usize) -> usize {
+        self.newlines.partition_point(|&newline| newline < offset) + 1
+    }
+
+    /// The byte offset of the first character of the 1 indexed `line`.
+    fn line_start(&self, line: usize) -> usize {
+        match line {
+            0 | 1 => 0,
+            line => self.newlines[line - 2] + 1,
+        }
     }
 }
 
+/// The number of `*` at the start of `s`, which extend the `/*` of a block
+/// comment, as in `/***` or `/**/`. Those are no doc comments.
+fn leading_stars(s: &str) -> usize {
+    s.chars().take_while(|&c| c == '*').count()
+}
+
+/// The number of `*` at the end of `s`, which extend the `*/` of a block
+/// comment, as in `**/`.
+fn trailing_stars(s: &str) -> usize {
+    s.chars().rev().take_while(|&c| c == '*').count()
+}
+
 /// Attempts to create a `LiteralSet` from a token assuming it is block comment.
 /// Returns `None` if the token kind is not `TokenKind::BlockComment`, if the
 /// token content does not match the block comment regex, or if any line cannot
 /// be added by `LiteralSet::add_adjacent`
-fn literal_set_from_block_comment(token: &TokenWithType) -> Result<LiteralSet, String> {
-    let number_of_lines = token.content.split("\n").count();
-    let mut lines = token.content.split("\n");
+fn literal_set_from_block_comment(
+    token: &TokenDescriptor,
+    source: &str,
+) -> Result<LiteralSet, String> {
+    let content = token.content(source);
+    let number_of_lines = content.split("\n").count();
+    let mut lines = content.split("\n");
     if number_of_lines == 1 {
+        let inner = content
+            .strip_prefix(BLOCK_COMMENT_PREFIX)
+            .and_then(|inner| inner.strip_suffix(BLOCK_COMMENT_POSTFIX))
+            .unwrap_or_default();
+        let leading = leading_stars(inner);
+        let trailing = trailing_stars(&inner[leading..]);
         let literal = match TrimmedLiteral::from(
-        CommentVariant::SlashStar, &token.content, token.kind.pre_in_chars(),
-        token.kind.post_in_chars(), token.line, token.column) {
+        CommentVariant::SlashStar, content, token.kind.pre_in_chars() + leading,
+        token.kind.post_in_chars() + trailing, token.line(), token.column()) {
       Err(s) => return Err(format!(
           "Failed to create literal from single line block comment, content \"{}\" - caused by \"{}\"",
-          token.content, s)),
+          content, s)),
       Ok(l) => l
     };
         Ok(LiteralSet::from(literal))
@@ -204,18 +390,21 @@ fn literal_set_from_block_comment(token: &TokenWithType) -> Result<LiteralSet, S
             None => {
                 return Err(format!(
                     "BUG! Expected block comment \"{}\" to have at least two lines",
-                    token.content
+                    content
                 ))
             }
             Some(l) => l,
         };
+        let leading = next_line
+            .strip_prefix(BLOCK_COMMENT_PREFIX)
+            .map_or(0, leading_stars);
         let literal = match TrimmedLiteral::from(
             CommentVariant::SlashStar,
             next_line,
-            token.kind.pre_in_chars(),
+            token.kind.pre_in_chars() + leading,
             0,
-            token.line,
-            token.column,
+            token.line(),
+            token.column(),
         ) {
             Err(s) => {
                 return Err(format!(
@@ -227,18 +416,27 @@ fn literal_set_from_block_comment(token: &TokenWithType) -> Result<LiteralSet, S
             Ok(l) => l,
         };
         let mut literal_set = LiteralSet::from(literal);
-        let mut line_number = token.line;
+        let mut line_number = token.line();
+        let last_line_number = token.line() + number_of_lines - 1;
         while let Some(next_line) = lines.next() {
             line_number += 1;
-            let post = if next_line.ends_with(BLOCK_COMMENT_POSTFIX) {
-                TokenType::BlockComment.post_in_chars()
+            let post = match next_line.strip_suffix(BLOCK_COMMENT_POSTFIX) {
+                Some(inner) => TokenType::BlockComment.post_in_chars() + trailing_stars(inner),
+                None => 0,
+            };
+            // Javadoc style interior lines ` * foo`, the `*` is not content
+            let pre = if line_number < last_line_number {
+                BLOCK_COMMENT_LINE_PREFIX
+                    .find(next_line)
+                    .map(|prefix| prefix.as_str().chars().count())
+                    .unwrap_or(0)
             } else {
                 0
             };
             let literal = match TrimmedLiteral::from(
                 CommentVariant::SlashStar,
                 next_line,
-                0,
+                pre,
                 post,
                 line_number,
                 0,
@@ -253,10 +451,10 @@ fn literal_set_from_block_comment(token: &TokenWithType) -> Result<LiteralSet, S
             };
             match literal_set.add_adjacent(literal) {
                 Ok(_) => (),
-                Err(_) => {
+                Err(e) => {
                     return Err(format!(
-                        "Failed to add line with content {} to literal set",
-                        next_line
+                        "Failed to add line with content {} to literal set: {}",
+                        next_line, e.reason
                     ))
                 }
             }
@@ -268,15 +466,18 @@ fn literal_set_from_block_comment(token: &TokenWithType) -> Result<LiteralSet, S
 /// Attempt to create a literal from a developer line comment token. Returns
 /// `None` if the token's kind is not `TokenType::LineComment` or if the call to
 /// `TrimmedLiteral::from` fails.
-fn literal_from_line_comment(token: &TokenWithType) -> Result<TrimmedLiteral, String> {
+fn literal_from_line_comment(
+    token: &TokenDescriptor,
+    source: &str,
+) -> Result<TrimmedLiteral, String> {
     match token.kind {
         TokenType::LineComment => TrimmedLiteral::from(
             CommentVariant::DoubleSlash,
-            &token.content,
+            token.content(source),
             token.kind.pre_in_chars(),
             token.kind.post_in_chars(),
-            token.line,
-            token.column,
+            token.line(),
+            token.column(),
         ),
         _ => Err(format!(
             "Expected a token of type {}, got {}",
@@ -289,13 +490,21 @@ fn literal_from_line_comment(token: &TokenWithType) -> Result<TrimmedLiteral, St
 /// Converts a vector of tokens into a vector of `LiteralSet`s based on the
 /// developer line comments in the input, ignoring all other tokens in the
 /// input.
-fn construct_literal_sets(tokens: impl IntoIterator<Item = TokenWithType>) -> Vec<LiteralSet> {
+///
+/// A `// noinspection SpellCheckingInspection` line and all line comments
+/// directly following it are skipped.
+fn construct_literal_sets(
+    source: &str,
+    tokens: impl IntoIterator<Item = TokenDescriptor>,
+) -> Vec<LiteralSet> {
     let mut sets = vec![];
+    // the last line skipped due to a `noinspection` annotation
+    let mut suppressed = None;
     'loopy: for token in tokens {
         let res = match token.kind {
-            TokenType::LineComment => literal_from_line_comment(&token),
+            TokenType::LineComment => literal_from_line_comment(&token, source),
             TokenType::BlockComment => {
-                if let Ok(set) = literal_set_from_block_comment(&token) {
+                if let Ok(set) = literal_set_from_block_comment(&token, source) {
                     sets.push(set)
                 }
                 continue 'loopy;
@@ -306,17 +515,33 @@ fn construct_literal_sets(tokens: impl IntoIterator<Item = TokenWithType>) -> Ve
             Err(err) => {
                 log::trace!(
                     "Failed to create literal from comment with content \"{}\" due to \"{}\"",
-                    token.content,
+                    token.content(source),
                     err
                 );
                 continue 'loopy;
             }
             Ok(l) => l,
         };
+        if NOINSPECTION.is_match(literal.as_str()) {
+            log::trace!(
+                "Skipping the remaining lines of the comment, starting with line {}",
+                literal.span().start.line
+            );
+            suppressed = Some(literal.span().end.line);
+            continue 'loopy;
+        }
+        match suppressed {
+            Some(line) if literal.span().start.line == line + 1 => {
+                suppressed = Some(literal.span().end.line);
+                continue 'loopy;
+            }
+            _ => suppressed = None,
+        }
         match sets.pop() {
             None => sets.push(LiteralSet::from(literal)),
             Some(mut s) => match s.add_adjacent(literal) {
-                Err(literal) => {
+                Err(AdjacencyError { literal, reason }) => {
+                    log::trace!("Starting a new literal set: {}", reason);
                     sets.push(s);
                     sets.push(LiteralSet::from(literal))
                 }
@@ -324,6 +549,11 @@ fn construct_literal_sets(tokens: impl IntoIterator<Item = TokenWithType>) -> Ve
             },
         }
     }
+    debug_assert!(
+        sets.windows(2)
+            .all(|pair| pair[0].coverage.0 <= pair[1].coverage.0),
+        "BUG: Literal sets of developer comments must be in line order"
+    );
     sets
 }
 
@@ -332,31 +562,75 @@ mod tests {
     use crate::documentation::developer::*;
     use assert_matches::assert_matches;
 
+    /// Materialize the tokens of the pipeline for easier matching.
+    fn tokens(source: &str) -> impl Iterator<Item = TokenWithType> + '_ {
+        source_to_iter(source).map(move |token| TokenWithType {
+            kind: token.kind,
+            content: token.content(source).to_owned(),
+            line: token.line(),
+            column: token.column(),
+        })
+    }
+
     #[test]
-    fn test_count_lines_correctly_counts_lines() {
+    fn toml_comments() {
+        let source = "# Build\n# settings\n[build]\ntarget = \"x # y\" # trailing\n  # indented\n";
+        let sets = extract_toml_comments(source);
+        assert_eq!(sets.len(), 2);
+        let lines = sets[0]
+            .literals()
+            .into_iter()
+            .map(|literal| literal.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(lines, vec![" Build", " settings"]);
+        assert_eq!(sets[0].literals()[0].variant(), CommentVariant::TomlComment);
+        let indented = sets[1].literals()[0];
+        assert_eq!(indented.as_str(), " indented");
+        assert_eq!(indented.span().start, LineColumn { line: 5, column: 3 });
+    }
+
+    #[test]
+    fn regexes_compile() {
+        Lazy::force(&BLOCK_COMMENT);
+        Lazy::force(&LINE_COMMENT);
+        Lazy::force(&BLOCK_COMMENT_LINE_PREFIX);
+        Lazy::force(&NOINSPECTION);
+    }
+
+    #[test]
+    fn test_line_index_correctly_locates_lines() {
         // Note: lines are 1 indexed
-        assert_eq!(count_lines(""), 1);
-        assert_eq!(count_lines("test"), 1);
-        assert_eq!(count_lines("test\ntest"), 2);
-        assert_eq!(count_lines("test\ntest\n something else \n"), 4);
-        assert_eq!(count_lines("\n test\ntest\n something else \n"), 5);
+        let index = LineIndex::new("");
+        assert_eq!(index.line(0), 1);
+        assert_eq!(index.line_start(1), 0);
+
+        let index = LineIndex::new("\n test\ntest\n something else \n");
+        assert_eq!(index.line(0), 1);
+        assert_eq!(index.line(1), 2);
+        assert_eq!(index.line(6), 2);
+        assert_eq!(index.line(7), 3);
+        assert_eq!(index.line(12), 4);
+        assert_eq!(index.line(29), 5);
+        assert_eq!(index.line_start(2), 1);
+        assert_eq!(index.line_start(3), 7);
+        assert_eq!(index.line_start(5), 29);
     }
 
     #[test]
-    fn test_calculate_column_correctly_calculates_final_column_of_last_line() {
-        // Note: next column after last, in chars, zero indexed
-        assert_eq!(calculate_column(""), 0);
-        assert_eq!(calculate_column("test中"), 5);
-        assert_eq!(calculate_column("test\n"), 0);
-        assert_eq!(calculate_column("test\ntest2"), 5);
-        assert_eq!(calculate_column("test\ntest中2"), 6);
-        assert_eq!(calculate_column("test\ntest中2\n中3"), 2);
+    fn test_tokens_on_very_long_line() {
+        // an encoded blob of 10MB in a single line
+        let blob = "QUJD".repeat(2_500_000);
+        let source = format!("/* {} */ // tail\n// next", blob);
+        let positions = tokens(&source)
+            .map(|token| (token.line, token.column))
+            .collect::<Vec<_>>();
+        assert_eq!(positions, vec![(1, 0), (1, blob.len() + 7), (2, 0)]);
     }
 
     #[test]
     fn test_tokens_from_source_basic() {
         let source = "/* test */\n// test";
-        let mut tokens = dbg!(Vec::from_iter(source_to_iter(source))).into_iter();
+        let mut tokens = dbg!(Vec::from_iter(tokens(source))).into_iter();
         assert_matches!(
             tokens.next(),
             Some(TokenWithType {
@@ -378,7 +652,7 @@ mod tests {
     #[test]
     fn test_tokens_with_line_column_values_set_correctly_more_unicode() {
         let source = "/* te中st */\n// test";
-        let mut tokens = source_to_iter(source);
+        let mut tokens = tokens(source);
         assert_matches!(
             tokens.next(),
             Some(TokenWithType {
@@ -400,7 +674,7 @@ mod tests {
     #[test]
     fn test_tokens_with_line_column_values_set_correctly_another() {
         let source = "/* te中st */\n// test\nfn 中(){\t}";
-        let mut tokens = source_to_iter(source);
+        let mut tokens = tokens(source);
         assert_matches!(
             tokens.next(),
             Some(TokenWithType {
@@ -419,6 +693,71 @@ mod tests {
         ); // Block comment
     }
 
+    #[test]
+    fn test_tokens_line_column_tracked_across_tokens() {
+        let source = "/* a */ /* 中 */ // b\n  // c\n\nfn x() {} /* d\n e */ // f";
+        let positions = tokens(source)
+            .map(|token| (token.line, token.column))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            positions,
+            vec![(1, 0), (1, 8), (1, 16), (2, 2), (4, 10), (5, 6)]
+        );
+    }
+
+    #[test]
+    fn test_no_comments_fast_path() {
+        assert!(extract_developer_comments("").is_empty());
+        assert!(extract_developer_comments("fn x() -> u8 { 2 / 1 * 3 }").is_empty());
+        assert!(extract_developer_comments("fn x() -> &'static str { \"//\" }").is_empty());
+        assert_eq!(extract_developer_comments("fn x() {} // y").len(), 1);
+    }
+
+    #[test]
+    fn test_tokens_with_raw_identifiers() {
+        let source = "struct r#type { r#fn: u8 } // use r#type as the field name\n\
+            /* r#async and r#fn */ fn r#match(r#in: r#type) {}\n\
+            // r#";
+        let tokens = tokens(source).collect::<Vec<_>>();
+        assert_eq!(tokens.len(), 3);
+        assert_matches!(
+            &tokens[0],
+            TokenWithType {
+                kind: TokenType::LineComment,
+                line: 1,
+                column: 27,
+                content,
+            } => assert_eq!(content, "// use r#type as the field name")
+        );
+        assert_matches!(
+            &tokens[1],
+            TokenWithType {
+                kind: TokenType::BlockComment,
+                line: 2,
+                column: 0,
+                content,
+            } => assert_eq!(content, "/* r#async and r#fn */")
+        );
+        assert_matches!(
+            &tokens[2],
+            TokenWithType {
+                kind: TokenType::LineComment,
+                line: 3,
+                column: 0,
+                content,
+            } => assert_eq!(content, "// r#")
+        );
+
+        // the trailing line comment is adjacent to the block comment
+        let literal_sets = construct_literal_sets(source, source_to_iter(source));
+        assert_eq!(literal_sets.len(), 2);
+        let literals = literal_sets[0].literals();
+        assert_eq!(literals[0].as_str(), " use r#type as the field name");
+        assert_eq!(literals[0].span().start.column, 29);
+        let literals = literal_sets[1].literals();
+        assert_eq!(literals[0].as_str(), " r#async and r#fn ");
+    }
+
     #[test]
     fn test_tokens_retain_empty_lines_for_clustering() {
         let source = r###"// ```c
@@ -427,7 +766,7 @@ mod tests {
 // end
 // ```
 "###;
-        let mut tokens = source_to_iter(source);
+        let mut tokens = tokens(source);
         assert_matches!(
             tokens.next(),
             Some(TokenWithType {
@@ -485,19 +824,29 @@ mod tests {
             },
         ];
         for token in block_comments {
-            assert_eq!(TokenWithType::from(token).kind, TokenType::BlockComment);
+            assert_eq!(identify_token_type(token).kind, TokenType::BlockComment);
         }
     }
 
     #[test]
     fn test_identify_token_type_assigns_line_comment_type_to_line_comments() {
-        let line_comments = vec![TokenWithLineColumn {
-            content: "// Line Comment ".to_string(),
-            line: 0,
-            column: 0,
-        }];
+        let line_comments = vec![
+            token_with_line_column_at_start("// Line Comment "),
+            token_with_line_column_at_start("//"),
+            token_with_line_column_at_start("// "),
+            token_with_line_column_at_start("//\t"),
+            token_with_line_column_at_start("//| table"),
+            token_with_line_column_at_start("//= not an operator"),
+            token_with_line_column_at_start("//// Not a doc comment"),
+        ];
         for token in line_comments {
-            assert_eq!(TokenWithType::from(token).kind, TokenType::LineComment);
+            let content = token.content.clone();
+            assert_eq!(
+                identify_token_type(token).kind,
+                TokenType::LineComment,
+                "{:?}",
+                content
+            );
         }
     }
 
@@ -516,9 +865,11 @@ mod tests {
         let not_developer_comments = vec![
             token_with_line_column_at_start("/// Outer documentation comment"),
             token_with_line_column_at_start("//! Inner documentation comment"),
+            token_with_line_column_at_start("///"),
+            token_with_line_column_at_start("//!"),
         ];
         for token in not_developer_comments {
-            assert_eq!(TokenWithType::from(token).kind, TokenType::Other);
+            assert_eq!(identify_token_type(token).kind, TokenType::Other);
         }
     }
 
@@ -540,8 +891,7 @@ mod tests {
             "fn", "func中", "(", ")", "{", "1", "+", "2", ";", "}", "\n", " ",
         ];
         let source = concatenate_with_line_breaks(&includes, &excludes);
-        let tokens = source_to_iter(&source);
-        for token in tokens {
+        for token in tokens(&source) {
             for content in &excludes {
                 assert_ne!(&token.content, content);
             }
@@ -556,8 +906,7 @@ mod tests {
             "/// An outer documentation comment",
         ];
         let source = concatenate_with_line_breaks(&includes, &excludes);
-        let tokens = source_to_iter(&source);
-        for token in tokens {
+        for token in tokens(&source) {
             for content in &excludes {
                 assert_ne!(&token.content, content);
             }
@@ -571,7 +920,7 @@ mod tests {
             "fn", "func中", "(", ")", "{", "1", "+", "2", ";", "}", "\n", " ",
         ];
         let source = concatenate_with_line_breaks(&includes, &excludes);
-        let tokens = source_to_iter(&source).collect::<Vec<_>>();
+        let tokens = tokens(&source).collect::<Vec<_>>();
         for content in includes {
             let tokens = tokens
                 .iter()
@@ -581,11 +930,31 @@ mod tests {
         }
     }
 
+    #[test]
+    fn noinspection_skips_rest_of_comment() {
+        let source = "// Checked\n//noinspection RsUnusedImport, SpellCheckingInspection\n\
+            // Skipped\n// Skipped too\n\n// Checked again\nfn x() {}\n\
+            // noinspection SpellCheckingInspection\n";
+        let literal_sets = construct_literal_sets(source, source_to_iter(source));
+        assert_eq!(
+            literal_sets
+                .iter()
+                .map(|set| set.to_string())
+                .collect::<Vec<_>>(),
+            vec![" Checked".to_owned(), " Checked again".to_owned()]
+        );
+
+        let source = "// noinspection RsUnresolvedReference\n// Checked\n";
+        let literal_sets = construct_literal_sets(source, source_to_iter(source));
+        assert_eq!(literal_sets.len(), 1);
+        assert_eq!(literal_sets[0].len(), 2);
+    }
+
     #[test]
     fn test_block_comments_to_literal_sets_converter_keeps_block_comment_tokens() {
         let source = "/* block comment */\n/*\n * multi line block comment\n */\n";
         let tokens = source_to_iter(source);
-        let literal_sets = construct_literal_sets(tokens);
+        let literal_sets = construct_literal_sets(source, tokens);
         assert_eq!(literal_sets.len(), 2);
     }
 
@@ -594,7 +963,7 @@ mod tests {
         let source = "/// line comment\n/// outer documentation\npub fn test() -> i32 \
         {\n  //! inner documentation\n  1 + 2\n}";
         let tokens = source_to_iter(source);
-        let literal_sets = construct_literal_sets(tokens);
+        let literal_sets = construct_literal_sets(source, tokens);
         assert_eq!(literal_sets.len(), 0);
     }
 
@@ -604,7 +973,7 @@ mod tests {
         let tokens = source_to_iter(source).collect::<Vec<_>>();
         assert_eq!(tokens.len(), 1);
         let token = tokens.last().unwrap();
-        let literal_set = literal_set_from_block_comment(token);
+        let literal_set = literal_set_from_block_comment(token, source);
         assert!(literal_set.is_ok());
         let literal_set = literal_set.unwrap();
         assert_eq!(literal_set.len(), 1);
@@ -626,7 +995,7 @@ mod tests {
         let tokens = source_to_iter(source).collect::<Vec<_>>();
         assert!(tokens.len() > 0);
         let token = tokens.last().unwrap();
-        let literal_set = literal_set_from_block_comment(&token);
+        let literal_set = literal_set_from_block_comment(&token, source);
         assert!(literal_set.is_ok());
         let literal_set = literal_set.unwrap();
         assert_eq!(literal_set.len(), 1);
@@ -652,7 +1021,7 @@ mod tests {
         let tokens = source_to_iter(source).collect::<Vec<_>>();
         assert_eq!(tokens.len(), 1);
         let token = tokens.into_iter().last().unwrap();
-        let literal_set = literal_set_from_block_comment(&token);
+        let literal_set = literal_set_from_block_comment(&token, source);
         assert!(literal_set.is_ok());
         let literal_set = literal_set.unwrap();
         assert_eq!(literal_set.len(), 3);
@@ -695,12 +1064,104 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_javadoc_style_block_comment_strips_leading_asterisks() {
+        let source = "  /**\n   * Javadoc style\n   *\n   *  comment\n   **/\nfn x() {}";
+        let tokens = source_to_iter(source).collect::<Vec<_>>();
+        // `/**` is a doc comment, unless immediately followed by another `*`
+        assert_eq!(tokens.len(), 0);
+
+        let source = "/*\n * Javadoc style\n *\n *  comment\n */";
+        let tokens = source_to_iter(source).collect::<Vec<_>>();
+        assert_eq!(tokens.len(), 1);
+        let token = tokens.into_iter().last().unwrap();
+        let literal_set = literal_set_from_block_comment(&token, source).unwrap();
+        assert_eq!(literal_set.len(), 5);
+        let literals = literal_set.literals();
+        {
+            let literal = literals.get(1).unwrap();
+            assert_eq!(literal.pre(), " * ".chars().count());
+            assert_eq!(literal.as_str(), "Javadoc style");
+            let span = &literal.span();
+            assert_eq!(span.start.line, 2);
+            assert_eq!(span.start.column, 3);
+            assert_eq!(span.end.line, 2);
+            assert_eq!(span.end.column, " * Javadoc style".chars().count() - 1);
+        }
+        {
+            let literal = literals.get(2).unwrap();
+            assert_eq!(literal.pre(), " *".chars().count());
+            assert_eq!(literal.as_str(), "");
+        }
+        {
+            let literal = literals.get(3).unwrap();
+            assert_eq!(literal.pre(), " * ".chars().count());
+            assert_eq!(literal.as_str(), " comment");
+            let span = &literal.span();
+            assert_eq!(span.start.line, 4);
+            assert_eq!(span.start.column, 3);
+        }
+        {
+            // the closing line is left as is
+            let literal = literals.get(4).unwrap();
+            assert_eq!(literal.pre(), 0);
+            assert_eq!(literal.post(), TokenType::BlockComment.post_in_chars());
+            assert_eq!(literal.as_str(), " ");
+        }
+    }
+
+    #[test]
+    fn test_block_comment_strips_extra_asterisks() {
+        let source = "/*** Banner ***/";
+        let token = source_to_iter(source).last().unwrap();
+        let literal_set = literal_set_from_block_comment(&token, source).unwrap();
+        let literal = literal_set.literals()[0];
+        assert_eq!(literal.as_str(), " Banner ");
+        assert_eq!(literal.pre(), "/***".chars().count());
+        assert_eq!(literal.post(), "***/".chars().count());
+        let span = literal.span();
+        assert_eq!(span.start.column, 4);
+        assert_eq!(span.end.column, "/*** Banner".chars().count());
+
+        let source = "/**/";
+        let token = source_to_iter(source).last().unwrap();
+        let literal_set = literal_set_from_block_comment(&token, source).unwrap();
+        assert_eq!(literal_set.literals()[0].as_str(), "");
+
+        let source = "/*** Javadoc style\n * comment\n **/";
+        let tokens = source_to_iter(source).collect::<Vec<_>>();
+        assert_eq!(tokens.len(), 1);
+        let token = tokens.into_iter().last().unwrap();
+        let literal_set = literal_set_from_block_comment(&token, source).unwrap();
+        let literals = literal_set.literals();
+        assert_eq!(literals.len(), 3);
+        {
+            let literal = literals[0];
+            assert_eq!(literal.pre(), "/***".chars().count());
+            assert_eq!(literal.as_str(), " Javadoc style");
+            let span = literal.span();
+            assert_eq!(span.start.line, 1);
+            assert_eq!(span.start.column, 4);
+            assert_eq!(span.end.column, "/*** Javadoc style".chars().count() - 1);
+        }
+        {
+            let literal = literals[1];
+            assert_eq!(literal.as_str(), "comment");
+            assert_eq!(literal.span().start.column, 3);
+        }
+        {
+            let literal = literals[2];
+            assert_eq!(literal.post(), "**/".chars().count());
+            assert_eq!(literal.as_str(), " ");
+        }
+    }
+
     #[test]
     fn outer_inner_mix() {
         let source = "// line comment\n/// Outer documentation\nfn test(){\n \
         //! Inner documentation\n\tlet i = 1 + 2;\n}";
         let tokens = source_to_iter(source);
-        let sets = construct_literal_sets(tokens);
+        let sets = construct_literal_sets(source, tokens);
         // we only track dev comments
         assert_eq!(sets.len(), 1);
     }
@@ -710,7 +1171,7 @@ mod tests {
         let source = "/* Block comment */\nfn test(i: usize) {\n  let j = 1 + i;\n  j\n}";
         let tokens = source_to_iter(source);
         for token in tokens {
-            assert!(literal_from_line_comment(&token).is_err());
+            assert!(literal_from_line_comment(&token, source).is_err());
         }
     }
 
@@ -719,7 +1180,7 @@ mod tests {
         let source = "/// Outer \nfn(){\n//! Inner \n}";
         let tokens = source_to_iter(source);
         for token in tokens {
-            assert!(literal_from_line_comment(&token).is_err());
+            assert!(literal_from_line_comment(&token, source).is_err());
         }
     }
 
@@ -731,7 +1192,7 @@ mod tests {
         assert_eq!(filtered.len(), 2);
         let literals: Vec<Result<TrimmedLiteral, String>> = filtered
             .into_iter()
-            .map(|t| literal_from_line_comment(&t))
+            .map(|t| literal_from_line_comment(&t, source))
             .collect();
         {
             let literal = literals.get(0).unwrap();
@@ -774,7 +1235,7 @@ mod tests {
         let content = " line comment";
         let source = format!("//{}", content);
         let tokens = source_to_iter(&source);
-        let literal_sets = construct_literal_sets(tokens);
+        let literal_sets = construct_literal_sets(&source, tokens);
         assert_eq!(literal_sets.len(), 1);
         let literal_set = literal_sets.get(0).unwrap();
         let all_literals = literal_set.literals();
@@ -790,7 +1251,7 @@ mod tests {
         let content_2 = " line comment 2 ";
         let source = format!("//{}\n//{}", content_1, content_2);
         let tokens = source_to_iter(&source);
-        let literal_sets = construct_literal_sets(tokens);
+        let literal_sets = construct_literal_sets(&source, tokens);
         assert_eq!(literal_sets.len(), 1);
         let literal_set = literal_sets.get(0).unwrap();
         let all_literals = literal_set.literals();
@@ -811,7 +1272,7 @@ mod tests {
         let content_2 = " line comment 2 ";
         let source = format!("//{}\nfn(){{}}\n//{}", content_1, content_2);
         let tokens = source_to_iter(&source);
-        let literal_sets = construct_literal_sets(tokens);
+        let literal_sets = construct_literal_sets(&source, tokens);
         assert_eq!(literal_sets.len(), 2);
         {
             let literal_set = literal_sets.get(0).unwrap();
@@ -828,4 +1289,27 @@ mod tests {
             assert!(literal.as_str().contains(content_2));
         }
     }
+
+    /// Compare the extraction time before and after changes to the pipeline,
+    /// run with `cargo test --release -- --ignored --nocapture bench_`.
+    #[test]
+    #[ignore = "benchmark, only run on demand"]
+    fn bench_extract_developer_comments() {
+        let source = (0..20_000)
+            .map(|idx| {
+                format!(
+                    "// Developer comment number {}\nfn f{}() {{}} /* trailing ä */\n",
+                    idx, idx
+                )
+            })
+            .collect::<String>();
+        let start = std::time::Instant::now();
+        let sets = extract_developer_comments(&source);
+        println!(
+            "Extracted {} literal sets from {} bytes in {:?}",
+            sets.len(),
+            source.len(),
+            start.elapsed()
+        );
+    }
 }
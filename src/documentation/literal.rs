@@ -51,6 +51,11 @@ pub enum CommentVariant {
     Unknown,
     /// Toml entry
     TomlEntry,
+    /// Toml comment `#`
+    TomlComment,
+    /// Rust string literal, with the total length of `r###` etc. including `r`
+    /// but without `"` for raw string literals, `0` otherwise
+    StringLiteral(usize),
 }
 
 impl Default for CommentVariant {
@@ -71,6 +76,7 @@ impl CommentVariant {
             Self::SlashAsteriskAsterisk => CommentVariantCategory::Doc,
             Self::CommonMark => CommentVariantCategory::CommonMark,
             Self::TomlEntry => CommentVariantCategory::Unmergable,
+            Self::StringLiteral(_) => CommentVariantCategory::Unmergable,
             _ => CommentVariantCategory::Dev,
         }
     }
@@ -98,6 +104,9 @@ impl CommentVariant {
             CommentVariant::SlashAsteriskEM => "/*!".to_string(),
             CommentVariant::SlashAsteriskAsterisk => "/**".to_string(),
             CommentVariant::TomlEntry => "".to_owned(),
+            CommentVariant::TomlComment => "#".to_owned(),
+            CommentVariant::StringLiteral(0) => "\"".to_owned(),
+            CommentVariant::StringLiteral(p) => format!("r{}\"", "#".repeat(p.saturating_sub(1))),
             unhandled => unreachable!(
                 "String representation for comment variant {:?} exists. qed",
                 unhandled
@@ -127,6 +136,7 @@ impl CommentVariant {
             | CommentVariant::SlashAsteriskEM
             | CommentVariant::SlashAsterisk => 2,
             CommentVariant::MacroDocEqMacro => 0,
+            CommentVariant::StringLiteral(p) => p.saturating_sub(1) + 1,
             _ => 0,
         }
     }
@@ -141,6 +151,7 @@ impl CommentVariant {
             CommentVariant::SlashAsteriskAsterisk
             | CommentVariant::SlashAsteriskEM
             | CommentVariant::SlashAsterisk => "*/".to_string(),
+            CommentVariant::StringLiteral(p) => "\"".to_owned() + &"#".repeat(p.saturating_sub(1)),
             _ => "".to_string(),
         }
     }
@@ -162,6 +173,9 @@ pub struct TrimmedLiteral {
     /// Length of rendered **minus** `pre` and `post` in UTF-8 characters.
     len_in_chars: usize,
     len_in_bytes: usize,
+    /// Words that must not be reported for this literal, as given by a
+    /// trailing `// [nocheck: word1, word2]` annotation.
+    ignored_words: Vec<String>,
 }
 
 impl std::cmp::PartialEq for TrimmedLiteral {
@@ -184,6 +198,9 @@ impl std::cmp::PartialEq for TrimmedLiteral {
         if self.variant != other.variant {
             return false;
         }
+        if self.ignored_words != other.ignored_words {
+            return false;
+        }
 
         true
     }
@@ -200,6 +217,7 @@ impl std::hash::Hash for TrimmedLiteral {
         self.post.hash(hasher);
         self.len_in_bytes.hash(hasher);
         self.len_in_chars.hash(hasher);
+        self.ignored_words.hash(hasher);
     }
 }
 
@@ -224,6 +242,29 @@ fn trim_span(content: &str, span: &mut Span, pre: usize, post: usize) {
     }
 }
 
+/// Find a trailing `// [nocheck: word1, word2]` annotation in a single line of
+/// content.
+///
+/// Returns the byte offset where the annotation, including leading whitespace,
+/// starts, and the list of words to ignore.
+fn find_nocheck_annotation(content: &str) -> Option<(usize, Vec<String>)> {
+    lazy_static! {
+        static ref NOCHECK: Regex =
+            Regex::new(r"\s*//\s*\[nocheck:([^\]\n]*)\]\s*$").expect("NOCHECK regex compiles");
+    };
+    let captures = NOCHECK.captures(content).ok().flatten()?;
+    let annotation = captures.get(0)?;
+    let words = captures
+        .get(1)?
+        .as_str()
+        .split(',')
+        .map(str::trim)
+        .filter(|word| !word.is_empty())
+        .map(ToOwned::to_owned)
+        .collect::<Vec<_>>();
+    Some((annotation.start(), words))
+}
+
 /// Detect the comment variant based on the span based str content.
 ///
 /// Became necessary, since the `proc_macro2::Span` does not distinguish between
@@ -282,14 +323,14 @@ fn detect_comment_variant(
         let post = variant.suffix_len();
 
         #[cfg(debug_assertions)]
-        let orig = span.clone();
+        let orig = span;
 
         trim_span(rendered, &mut span, pre, post);
 
         #[cfg(debug_assertions)]
         {
             let raw = util::load_span_from(&mut content.as_bytes(), orig)?;
-            let adjusted = util::load_span_from(&mut content.as_bytes(), span.clone())?;
+            let adjusted = util::load_span_from(&mut content.as_bytes(), span)?;
 
             // we know pre and post only consist of single byte characters
             // so `.len()` is way faster here yet correct.
@@ -373,9 +414,36 @@ impl TrimmedLiteral {
             post: 0,
             len_in_chars: 0,
             len_in_bytes: 0,
+            ignored_words: Vec::new(),
         }
     }
 
+    /// Strip a trailing `// [nocheck: ..]` annotation from the content and
+    /// record the listed words as ignored for this literal.
+    ///
+    /// Only applies to single line literals, the annotation becomes part of
+    /// `post` and is no longer covered by the span.
+    fn strip_nocheck_annotation(mut self) -> Self {
+        if self.span.start.line != self.span.end.line {
+            return self;
+        }
+        let (offset, ignored_words) = match find_nocheck_annotation(self.as_str()) {
+            Some(found) => found,
+            None => return self,
+        };
+        let annotation = &self.as_str()[offset..];
+        let annotation_bytes = annotation.len();
+        let annotation_chars = annotation.chars().count();
+        log::trace!(target: "documentation", "Found nocheck annotation >{}<", annotation);
+
+        self.len_in_bytes -= annotation_bytes;
+        self.len_in_chars -= annotation_chars;
+        self.post += annotation_chars;
+        self.span.end.column = self.span.end.column.saturating_sub(annotation_chars);
+        self.ignored_words = ignored_words;
+        self
+    }
+
     pub(crate) fn load_from(content: &str, mut span: Span) -> Result<Self> {
         // let rendered = literal.to_string();
         // produces pretty unusable garabage, since it modifies the content of `///`
@@ -403,7 +471,7 @@ impl TrimmedLiteral {
             span.end.column += 1;
         }
 
-        let rendered = util::load_span_from(content.as_bytes(), span.clone())?;
+        let rendered = util::load_span_from(content.as_bytes(), span)?;
 
         // TODO cache the offsets for faster processing and avoiding repeated O(n) ops
         // let byteoffset2char = rendered.char_indices().enumerate().collect::<indexmap::IndexMap<_usize, (_usize, char)>>();
@@ -434,8 +502,141 @@ impl TrimmedLiteral {
             span,
             pre,
             post,
+            ignored_words: Vec::new(),
+        };
+        Ok(trimmed_literal.strip_nocheck_annotation())
+    }
+}
+
+/// Replace all escape sequences of a non-raw string literal with spaces.
+///
+/// Keeps the number of characters intact, such that the content still aligns
+/// with the span in the source, but avoids words like `nfoo` for `"\nfoo"`.
+fn blank_escape_sequences(content: &str) -> String {
+    let mut blanked = String::with_capacity(content.len());
+    let mut chars = content.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            blanked.push(c);
+            continue;
+        }
+        blanked.push(' ');
+        match chars.peek().copied() {
+            // line continuation, the newline itself must be retained
+            Some('\n') | Some('\r') | None => {}
+            Some('u') => {
+                // `\u{1F980}`
+                while let Some(c) = chars.next() {
+                    blanked.push(' ');
+                    if c == '}' {
+                        break;
+                    }
+                }
+            }
+            Some('x') => {
+                // `\x7F`
+                chars.by_ref().take(3).for_each(|_| blanked.push(' '));
+            }
+            Some(_) => {
+                // `\n`, `\t`, `\"`, `\\` etc.
+                let _ = chars.next();
+                blanked.push(' ');
+            }
+        }
+    }
+    blanked
+}
+
+/// Replace all format specifiers of a format string, i.e. `{}`, `{:?}` or
+/// `{name}`, with spaces.
+///
+/// Keeps the number of characters intact, just like `blank_escape_sequences`.
+/// The escaped braces `{{` and `}}` are retained.
+fn blank_format_specifiers(content: &str) -> String {
+    let mut blanked = String::with_capacity(content.len());
+    let mut chars = content.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '{' if chars.peek() == Some(&'{') => {
+                let _ = chars.next();
+                blanked.push_str("{{");
+            }
+            '{' => {
+                let specifier = chars
+                    .clone()
+                    .take_while(|&c| c != '}')
+                    .take_while(|&c| c != '{' && c != '"')
+                    .count();
+                if chars.clone().nth(specifier) == Some('}') {
+                    chars.by_ref().take(specifier + 1).for_each(drop);
+                    (0..specifier + 2).for_each(|_| blanked.push(' '));
+                } else {
+                    blanked.push(c);
+                }
+            }
+            _ => blanked.push(c),
+        }
+    }
+    blanked
+}
+
+impl TrimmedLiteral {
+    /// Load a rust string literal, i.e. `"foo"` or `r#"foo"#`, from the
+    /// source `content` given the `span` of the whole string literal token.
+    ///
+    /// Escape sequences are replaced by whitespace characters.
+    pub(crate) fn load_string_literal(content: &str, mut span: Span) -> Result<Self> {
+        // the span end column is exclusive
+        span.end.column = span.end.column.saturating_sub(1);
+
+        let rendered = util::load_span_from(content.as_bytes(), span)?;
+
+        let (variant, pre, post) = if let Some(raw) = rendered.strip_prefix('r') {
+            let pounds = raw.chars().take_while(|c| *c == '#').count();
+            (
+                CommentVariant::StringLiteral(pounds + 1),
+                pounds + 2,
+                pounds + 1,
+            )
+        } else if rendered.starts_with('"') {
+            (CommentVariant::StringLiteral(0), 1, 1)
+        } else {
+            bail!("Not a string literal >{}<", rendered);
+        };
+
+        let rendered_len = rendered.chars().count();
+        if rendered_len <= pre + post {
+            bail!("String literal >{}< is empty", rendered);
+        }
+        // all of the prefix and suffix characters are ASCII
+        let len_in_chars = rendered_len - pre - post;
+        let len_in_bytes = rendered.len() - pre - post;
+
+        trim_span(&rendered, &mut span, pre, post);
+
+        let rendered = if pre == 1 {
+            blank_escape_sequences(&rendered)
+        } else {
+            rendered
         };
-        Ok(trimmed_literal)
+
+        Ok(Self {
+            variant,
+            span,
+            rendered,
+            pre,
+            post,
+            len_in_chars,
+            len_in_bytes,
+            ignored_words: Vec::new(),
+        })
+    }
+
+    /// Replace the format specifiers of a string literal passed to a
+    /// formatting macro, i.e. `println!`, with spaces.
+    pub(crate) fn blank_format_specifiers(mut self) -> Self {
+        self.rendered = blank_format_specifiers(&self.rendered);
+        self
     }
 }
 
@@ -466,7 +667,7 @@ impl TrimmedLiteral {
 
         trim_span(content, &mut span, pre, post + 1);
 
-        Ok(TrimmedLiteral {
+        let literal = TrimmedLiteral {
             variant,
             span,
             rendered: content.to_string(),
@@ -474,7 +675,9 @@ impl TrimmedLiteral {
             post,
             len_in_chars: content_chars_len - pre - post,
             len_in_bytes: content.len() - pre - post,
-        })
+            ignored_words: Vec::new(),
+        };
+        Ok(literal.strip_nocheck_annotation())
     }
 }
 
@@ -531,7 +734,7 @@ impl TrimmedLiteral {
     ///
     /// Covers only the content, no marker or helper characters.
     pub fn span(&self) -> Span {
-        self.span.clone()
+        self.span
     }
 
     /// Access the characters via an iterator.
@@ -539,6 +742,12 @@ impl TrimmedLiteral {
         self.as_str().chars()
     }
 
+    /// Words listed in a trailing `// [nocheck: ..]` annotation, which must not
+    /// be reported for this literal.
+    pub fn ignored_words(&self) -> &[String] {
+        &self.ignored_words
+    }
+
     /// The string variant type, see [`CommentVariant`](self::CommentVariant)
     /// for details.
     pub fn variant(&self) -> CommentVariant {
@@ -552,6 +761,32 @@ impl TrimmedLiteral {
     }
 }
 
+/// The full representation including `prefix` and `postfix` characters, as
+/// returned by [`TrimmedLiteral::as_untrimmed_str`].
+impl AsRef<str> for TrimmedLiteral {
+    fn as_ref(&self) -> &str {
+        self.as_untrimmed_str()
+    }
+}
+
+/// A literal which represents its content without `prefix` and `postfix`
+/// characters, as returned by [`TrimmedLiteral::as_str`], when used as
+/// `AsRef<str>`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct PlainLiteral(pub TrimmedLiteral);
+
+impl AsRef<str> for PlainLiteral {
+    fn as_ref(&self) -> &str {
+        self.0.as_str()
+    }
+}
+
+impl From<TrimmedLiteral> for PlainLiteral {
+    fn from(literal: TrimmedLiteral) -> Self {
+        Self(literal)
+    }
+}
+
 impl fmt::Debug for TrimmedLiteral {
     fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
         use console::Style;
@@ -691,6 +926,47 @@ mod tests {
         });
     }
 
+    #[test]
+    fn as_ref_str() {
+        const CONTENT: &str = "/// Fine.\nstruct Fine;";
+        let literal = annotated_literals_raw(CONTENT).next().unwrap();
+        let tl = TrimmedLiteral::load_from(CONTENT, Span::from(literal.span())).unwrap();
+
+        fn content(s: impl AsRef<str>) -> String {
+            s.as_ref().to_owned()
+        }
+        assert_eq!(content(&tl), tl.as_untrimmed_str());
+        assert_eq!(content(PlainLiteral::from(tl.clone())), " Fine.");
+    }
+
+    #[test]
+    fn span_outlives_literal() {
+        const CONTENT: &str = "/// Fine.\nstruct Fine;";
+        let span = {
+            let literal = annotated_literals_raw(CONTENT).next().unwrap();
+            let tl = TrimmedLiteral::load_from(CONTENT, Span::from(literal.span())).unwrap();
+            tl.span()
+        };
+        let copy = span;
+        assert_eq!(span, copy);
+        assert_eq!(
+            util::load_span_from(CONTENT.as_bytes(), span).unwrap(),
+            " Fine."
+        );
+    }
+
+    #[test]
+    fn format_specifiers() {
+        assert_eq!(
+            blank_format_specifiers(r#""Got {} and {:?}, {name:>8} {{literal}}""#),
+            r#""Got    and     ,           {{literal}}""#
+        );
+        assert_eq!(
+            blank_format_specifiers(r#""open { brace""#),
+            r#""open { brace""#
+        );
+    }
+
     macro_rules! block_comment_test {
         ($name:ident, $content:literal) => {
             #[test]
@@ -1,5 +1,40 @@
 pub use super::{TrimmedLiteral, TrimmedLiteralDisplay};
-use crate::{CheckableChunk, CommentVariant, Range};
+use crate::{CheckableChunk, CommentVariant, CommentVariantCategory, Range};
+
+/// The reason a literal could not be added to a [`LiteralSet`].
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum AdjacencyReason {
+    /// The literal does not continue on the line following the set.
+    #[error("Expected a literal on line {expected}, but it ends on line {got}")]
+    WrongLine {
+        /// The line a literal must end on to be appended.
+        expected: usize,
+        /// The line the rejected literal ends on.
+        got: usize,
+    },
+    /// The literal is of another comment variant category than the set, i.e.
+    /// a developer comment following a doc comment.
+    #[error("Expected a {expected:?} comment, but got a {got:?} comment")]
+    WrongVariant {
+        /// The comment variant category of the set.
+        expected: CommentVariantCategory,
+        /// The comment variant category of the rejected literal.
+        got: CommentVariantCategory,
+    },
+    /// The set has no literals to be adjacent to.
+    #[error("The literal set is empty")]
+    EmptySet,
+}
+
+/// A literal rejected by [`LiteralSet::add_adjacent`].
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("Literal is not adjacent: {reason}")]
+pub struct AdjacencyError {
+    /// The rejected literal, handed back to the caller.
+    pub literal: TrimmedLiteral,
+    /// Why the literal was rejected.
+    pub reason: AdjacencyReason,
+}
 /// A set of consecutive literals.
 ///
 /// Provides means to render them as a code block
@@ -26,18 +61,39 @@ impl LiteralSet {
     /// Add a literal to a literal set, if the previous lines literal already
     /// exists.
     ///
-    /// Returns literal within the Err variant if not adjacent
-    pub fn add_adjacent(&mut self, literal: TrimmedLiteral) -> Result<(), TrimmedLiteral> {
+    /// Returns the literal and the reason within the Err variant if not
+    /// adjacent.
+    pub fn add_adjacent(&mut self, literal: TrimmedLiteral) -> Result<(), AdjacencyError> {
+        if self.literals.is_empty() {
+            return Err(AdjacencyError {
+                literal,
+                reason: AdjacencyReason::EmptySet,
+            });
+        }
         if literal.variant().category() != self.variant.category() {
             log::debug!(
                 "Adjacent literal is not the same comment variant: {:?} vs {:?}",
                 literal.variant().category(),
                 self.variant.category()
             );
-            return Err(literal);
+            return Err(AdjacencyError {
+                reason: AdjacencyReason::WrongVariant {
+                    expected: self.variant.category(),
+                    got: literal.variant().category(),
+                },
+                literal,
+            });
         }
         let previous_line = literal.span().end.line;
         if previous_line == self.coverage.1 + 1 {
+            debug_assert!(
+                self.literals
+                    .last()
+                    .map_or(true, |last| last.span().end.line
+                        < literal.span().start.line),
+                "BUG: Appended literal starting on line {} overlaps the last literal of the set",
+                literal.span().start.line
+            );
             self.coverage.1 += 1;
             self.literals.push(literal);
             return Ok(());
@@ -50,7 +106,40 @@ impl LiteralSet {
             return Ok(());
         }
 
-        Err(literal)
+        Err(AdjacencyError {
+            literal,
+            reason: AdjacencyReason::WrongLine {
+                expected: self.coverage.1 + 1,
+                got: previous_line,
+            },
+        })
+    }
+
+    /// Create a set from literals that are known to be adjacent, without
+    /// checking.
+    ///
+    /// # Safety
+    ///
+    /// `literals` must not be empty, all literals must share the same comment
+    /// variant category and each literal must start on the line following
+    /// the last line of its predecessor, i.e. each `add_adjacent` call would
+    /// succeed. Otherwise the resulting chunk maps content ranges to the wrong
+    /// source spans.
+    pub unsafe fn from_literals_unchecked(literals: Vec<TrimmedLiteral>) -> Self {
+        debug_assert!(!literals.is_empty(), "BUG: A literal set must not be empty");
+        let first = &literals[0];
+        let last = &literals[literals.len() - 1];
+        Self {
+            coverage: (first.span().start.line, last.span().end.line),
+            variant: first.variant(),
+            literals,
+        }
+    }
+
+    /// The line number of the last line of the last literal, `None` if the set
+    /// is empty.
+    pub fn last_line(&self) -> Option<usize> {
+        self.literals.last().map(|literal| literal.span().end.line)
     }
 
     /// The set of trimmed literals that is covered.
@@ -69,6 +158,7 @@ impl LiteralSet {
     pub fn into_chunk(self) -> crate::documentation::CheckableChunk {
         let n = self.len();
         let mut source_mapping = indexmap::IndexMap::with_capacity(n);
+        let mut ignored_words = indexmap::IndexMap::new();
         let mut content = String::with_capacity(n * 120);
         if n > 0 {
             // cursor operates on characters
@@ -94,6 +184,9 @@ impl LiteralSet {
                         assert_eq!(range.len(), span_len);
                     }
                 }
+                if !literal.ignored_words().is_empty() {
+                    ignored_words.insert(range.clone(), literal.ignored_words().to_vec());
+                }
                 // keep zero length values too, to guarantee continuity
                 source_mapping.insert(range, span);
                 content.push_str(literal.as_str());
@@ -113,6 +206,32 @@ impl LiteralSet {
             crate::CommentVariant::Unknown
         };
         CheckableChunk::from_string(content, source_mapping, variant)
+            .with_ignored_words(ignored_words)
+    }
+}
+
+impl std::convert::TryFrom<Vec<TrimmedLiteral>> for LiteralSet {
+    type Error = Vec<TrimmedLiteral>;
+
+    /// Create a set from sorted literals, by adding them one by one.
+    ///
+    /// Returns all literals within the `Err` variant if they are empty or
+    /// any of them is not adjacent.
+    fn try_from(literals: Vec<TrimmedLiteral>) -> Result<Self, Self::Error> {
+        let mut iter = literals.into_iter();
+        let mut set = match iter.next() {
+            Some(first) => LiteralSet::from(first),
+            None => return Err(Vec::new()),
+        };
+        while let Some(literal) = iter.next() {
+            if let Err(AdjacencyError { literal, .. }) = set.add_adjacent(literal) {
+                let mut literals = set.literals;
+                literals.push(literal);
+                literals.extend(iter);
+                return Err(literals);
+            }
+        }
+        Ok(set)
     }
 }
 
@@ -258,6 +377,74 @@ struct Vikings;
         assert_eq!(cls.to_string(), EXMALIBU_CHUNK_STR.to_owned());
     }
 
+    #[test]
+    fn last_line() {
+        let mut literals = annotated_literals(RAW).into_iter();
+        let mut cls = LiteralSet::from(literals.next().unwrap());
+        assert_eq!(cls.last_line(), Some(1));
+        let second = literals.next().unwrap();
+        let third = literals.next().unwrap();
+
+        // not adjacent, line 3 does not follow line 1
+        let third = cls.add_adjacent(third).unwrap_err().literal;
+        assert_eq!(cls.last_line(), Some(1));
+
+        cls.add_adjacent(second).unwrap();
+        cls.add_adjacent(third).unwrap();
+        assert_eq!(cls.last_line(), Some(3));
+    }
+
+    #[test]
+    fn adjacency_reasons() {
+        let literals = annotated_literals(RAW);
+
+        let mut cls = LiteralSet::default();
+        let err = cls.add_adjacent(literals[0].clone()).unwrap_err();
+        assert_eq!(err.reason, AdjacencyReason::EmptySet);
+        assert_eq!(err.literal, literals[0]);
+
+        let mut cls = LiteralSet::from(literals[0].clone());
+        let err = cls.add_adjacent(literals[2].clone()).unwrap_err();
+        assert_eq!(
+            err.reason,
+            AdjacencyReason::WrongLine {
+                expected: 2,
+                got: 3
+            }
+        );
+
+        let dev = TrimmedLiteral::new_empty("", literals[1].span(), CommentVariant::DoubleSlash);
+        let err = cls.add_adjacent(dev).unwrap_err();
+        assert_eq!(
+            err.reason,
+            AdjacencyReason::WrongVariant {
+                expected: CommentVariantCategory::Doc,
+                got: CommentVariantCategory::Dev,
+            }
+        );
+
+        assert_eq!(cls.add_adjacent(literals[1].clone()), Ok(()));
+    }
+
+    #[test]
+    fn try_from_literals() {
+        use std::convert::TryFrom;
+
+        let literals = annotated_literals(RAW);
+        let expected = gen_literal_set(RAW);
+        assert_eq!(LiteralSet::try_from(literals.clone()), Ok(expected.clone()));
+        assert_eq!(
+            unsafe { LiteralSet::from_literals_unchecked(literals.clone()) },
+            expected
+        );
+
+        // the second line is missing
+        let gap = vec![literals[0].clone(), literals[2].clone()];
+        assert_eq!(LiteralSet::try_from(gap.clone()), Err(gap));
+
+        assert_eq!(LiteralSet::try_from(Vec::new()), Err(Vec::new()));
+    }
+
     #[test]
     fn coverage() {
         let _ = env_logger::builder()
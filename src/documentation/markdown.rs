@@ -433,6 +433,33 @@ impl<'a> PlainOverlay<'a> {
     pub fn as_str(&self) -> &str {
         self.plain.as_str()
     }
+
+    /// All ranges of the plain representation which have a direct counterpart
+    /// in the source, together with the `Span` they map to.
+    ///
+    /// Placeholders, i.e. for inline code, are skipped. A range covering
+    /// multiple lines of the source is split up, one item per line.
+    pub fn ranges(&self) -> impl Iterator<Item = (Range, Span)> + '_ {
+        self.mapping
+            .iter()
+            .filter_map(|(plain, raw)| match raw {
+                SourceRange::Direct(raw) if !plain.is_empty() && !raw.is_empty() => {
+                    Some((plain, raw))
+                }
+                _ => None,
+            })
+            .flat_map(move |(plain, raw)| {
+                let plain_start = plain.start;
+                let raw_start = raw.start;
+                self.raw
+                    .find_spans(raw.clone())
+                    .into_iter()
+                    .map(move |(range, span)| {
+                        let start = plain_start + range.start - raw_start;
+                        (start..start + range.len(), span)
+                    })
+            })
+    }
 }
 
 use std::fmt;
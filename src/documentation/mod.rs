@@ -22,11 +22,47 @@ use proc_macro2::TokenTree;
 use rayon::prelude::*;
 use toml::Spanned;
 
-use std::path::PathBuf;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
 
 /// Range based on `usize`, simplification.
 pub type Range = core::ops::Range<usize>;
 
+/// Validate the spans of all chunks extracted from rust sources against the
+/// bounds of the source, always for debug builds.
+static PARANOID: AtomicBool = AtomicBool::new(cfg!(debug_assertions));
+
+/// Validate the spans of all chunks extracted from rust sources, see
+/// `--paranoid`.
+pub fn set_paranoid(paranoid: bool) {
+    PARANOID.store(paranoid || cfg!(debug_assertions), Ordering::Relaxed);
+}
+
+/// Warn about every span of `chunks` exceeding the bounds of `source`, which
+/// is a bug in the span calculation.
+fn warn_spans_out_of_bounds(origin: &ContentOrigin, source: &str, chunks: &[CheckableChunk]) {
+    for chunk in chunks {
+        for (span, position, line_length) in chunk.spans_out_of_bounds(source) {
+            let line = match line_length {
+                Some(line_length) => {
+                    format!("{} characters in line {}", line_length, position.line)
+                }
+                None => format!("no line {}", position.line),
+            };
+            log::warn!(
+                "BUG: span {:?} exceeds the bounds of {} at {}:{}, which has {} lines and {}",
+                span,
+                origin.as_path().display(),
+                position.line,
+                position.column,
+                source.lines().count(),
+                line
+            );
+        }
+    }
+}
+
 /// Apply an offset to `start` and `end` members, equaling a shift of the range.
 pub fn apply_offset(range: &mut Range, offset: usize) {
     range.start = range.start.saturating_add(offset);
@@ -39,17 +75,40 @@ mod developer;
 mod literal;
 pub(crate) mod literalset;
 mod markdown;
+mod rustdoc;
 
 pub use chunk::*;
 pub use cluster::*;
+pub use developer::{identify_token_type, TokenType, TokenWithLineColumn, TokenWithType};
 pub use literal::*;
 pub use literalset::*;
 pub use markdown::*;
+
+/// What is extracted from rust sources besides doc comments.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ExtractOptions {
+    /// Developer comments, i.e. `// ..` and `/* .. */`.
+    pub dev_comments: bool,
+    /// String literals.
+    pub string_literals: bool,
+    /// Names of the attributes whose string values are extracted, i.e.
+    /// `error` for `#[error("..")]`.
+    pub attr_keys: Vec<String>,
+    /// Names of the formatting macros whose string arguments are extracted,
+    /// i.e. `println` for `println!("..")`.
+    pub macro_names: Vec<String>,
+}
+
 /// Collection of all the documentation entries across the project
 #[derive(Debug, Clone)]
 pub struct Documentation {
     /// Mapping of a path to documentation literals
     index: IndexMap<ContentOrigin, Vec<CheckableChunk>>,
+    /// Files skipped during discovery, with the exclude pattern they matched
+    excluded: Vec<(PathBuf, String)>,
+    /// Files skipped during discovery, since they could not be read or have
+    /// binary content
+    skipped: Vec<PathBuf>,
 }
 
 impl Documentation {
@@ -57,9 +116,34 @@ impl Documentation {
     pub fn new() -> Self {
         Self {
             index: IndexMap::with_capacity(64),
+            excluded: Vec::new(),
+            skipped: Vec::new(),
         }
     }
 
+    /// Record a file that was skipped since it matched the exclude `pattern`.
+    pub fn add_excluded(&mut self, path: PathBuf, pattern: impl Into<String>) {
+        self.excluded.push((path, pattern.into()));
+    }
+
+    /// All files skipped during discovery, with the exclude pattern they
+    /// matched.
+    pub fn excluded(&self) -> &[(PathBuf, String)] {
+        &self.excluded
+    }
+
+    /// Record a file that was skipped since it could not be read, i.e. a
+    /// broken symbolic link, or has binary content.
+    pub fn add_skipped(&mut self, path: PathBuf) {
+        self.skipped.push(path);
+    }
+
+    /// All files skipped during discovery, since they could not be read or
+    /// have binary content.
+    pub fn skipped(&self) -> &[PathBuf] {
+        &self.skipped
+    }
+
     /// Check if a particular key is contained.
     pub fn contains_key(&self, key: &ContentOrigin) -> bool {
         self.index.contains_key(key)
@@ -107,6 +191,11 @@ impl Documentation {
         self.index
             .entry(origin)
             .and_modify(|acc: &mut Vec<CheckableChunk>| {
+                // a file included via `include_str!` might be checked by itself as well
+                {
+                    let known = acc.iter().collect::<HashSet<&CheckableChunk>>();
+                    chunks.retain(|chunk| !known.contains(chunk));
+                }
                 acc.append(&mut chunks);
             })
             .or_insert_with(|| chunks);
@@ -118,15 +207,55 @@ impl Documentation {
         &mut self,
         origin: ContentOrigin,
         content: &str,
-        dev_comments: bool,
+        options: &ExtractOptions,
     ) -> Result<()> {
-        let cluster = Clusters::load_from_str(content, dev_comments)?;
+        let mut cluster = Clusters::load_from_str(content, options)?;
+        let included = std::mem::take(&mut cluster.included);
 
         let chunks = Vec::<CheckableChunk>::from(cluster);
+        if PARANOID.load(Ordering::Relaxed) {
+            warn_spans_out_of_bounds(&origin, content, &chunks);
+        }
+        let path = origin.as_path().to_owned();
         self.add_inner(origin, chunks);
+        for file in included {
+            self.add_included_commonmark(&path, &file);
+        }
         Ok(())
     }
 
+    /// Adds the file included via `#[doc = include_str!(..)]` by the rust
+    /// source file at `path` as common mark, resolved relative to the
+    /// directory of `path`. Failures to read it are not fatal.
+    fn add_included_commonmark(&mut self, path: &Path, included: &str) {
+        let dir = path.parent().unwrap_or_else(|| Path::new(""));
+        let origin =
+            ContentOrigin::CommonMarkFile(crate::util::normalize_path(&dir.join(included)));
+        if self.contains_key(&origin) {
+            return;
+        }
+        let content = match fs_err::read_to_string(origin.as_path()) {
+            Ok(content) => content,
+            Err(e) => {
+                log::warn!(
+                    "Failed to read `include_str!(\"{}\")` of {}: {}",
+                    included,
+                    path.display(),
+                    e
+                );
+                return;
+            }
+        };
+        if let Err(e) = self.add_commonmark(origin, &content) {
+            log::warn!(
+                "Skipping `include_str!(\"{}\")` of {}: {}",
+                included,
+                path.display(),
+                e
+            );
+        }
+    }
+
     /// Adds a content string to the documentation sourced from the
     /// `description` field in a `Cargo.toml` manifest.
     pub fn add_cargo_manifest_description(
@@ -216,6 +345,17 @@ impl Documentation {
         Ok(())
     }
 
+    /// Adds the comments of a cargo configuration file, i.e.
+    /// `.cargo/config.toml`, to the documentation.
+    pub fn add_cargo_config(&mut self, path: PathBuf, content: &str) -> Result<()> {
+        let chunks = developer::extract_toml_comments(content)
+            .into_iter()
+            .map(CheckableChunk::from_literalset)
+            .collect::<Vec<_>>();
+        self.add_inner(ContentOrigin::CargoConfig(path), chunks);
+        Ok(())
+    }
+
     /// Adds a common mark content str to the documentation.
     pub fn add_commonmark(&mut self, origin: ContentOrigin, content: &str) -> Result<()> {
         // extract the full content span and range
@@ -246,6 +386,15 @@ impl Documentation {
         Ok(())
     }
 
+    /// Only keep the chunks for which `keep` returns `true`, origins without
+    /// any remaining chunks are dropped.
+    pub fn retain_chunks(&mut self, mut keep: impl FnMut(&ContentOrigin, &CheckableChunk) -> bool) {
+        self.index.retain(|origin, chunks| {
+            chunks.retain(|chunk| keep(origin, chunk));
+            !chunks.is_empty()
+        });
+    }
+
     /// Obtain the set of chunks for a particular origin.
     #[inline(always)]
     pub fn get(&self, origin: &ContentOrigin) -> Option<&[CheckableChunk]> {
@@ -259,34 +408,32 @@ impl Documentation {
     }
 
     /// Load a document from a single string with a defined origin.
-    pub fn load_from_str(origin: ContentOrigin, content: &str, dev_comments: bool) -> Self {
+    pub fn load_from_str(origin: ContentOrigin, content: &str, options: &ExtractOptions) -> Self {
         let mut docs = Documentation::new();
 
         match origin.clone() {
             ContentOrigin::RustDocTest(_path, span) => {
                 if let Ok(excerpt) = load_span_from(&mut content.as_bytes(), span.clone()) {
-                    docs.add_rust(origin.clone(), excerpt.as_str(), dev_comments)
+                    docs.add_rust(origin.clone(), excerpt.as_str(), options)
                 } else {
                     // TODO
                     Ok(())
                 }
             }
-            origin @ ContentOrigin::RustSourceFile(_) => {
-                docs.add_rust(origin, content, dev_comments)
-            }
+            origin @ ContentOrigin::RustSourceFile(_) => docs.add_rust(origin, content, options),
             ContentOrigin::CargoManifestDescription(path) => {
                 docs.add_cargo_manifest_description(path, content)
             }
             origin @ ContentOrigin::CommonMarkFile(_) => docs.add_commonmark(origin, content),
             #[cfg(test)]
-            origin @ ContentOrigin::TestEntityRust => docs.add_rust(origin, content, dev_comments),
+            origin @ ContentOrigin::TestEntityRust => docs.add_rust(origin, content, options),
             #[cfg(test)]
             origin @ ContentOrigin::TestEntityCommonMark => docs.add_commonmark(origin, content),
         }
         .unwrap_or_else(move |e| {
             warn!(
                 "BUG: Failed to load content from {} (dev_comments={:?}): {:?}",
-                origin, dev_comments, e
+                origin, options.dev_comments, e
             );
         });
         docs
@@ -0,0 +1,141 @@
+//! Documentation loaded from the output of `rustdoc --output-format json`.
+//!
+//! Rustdoc only provides the span of an item, not of its documentation, so
+//! the doc lines are assumed to directly precede the item. Line numbers are
+//! approximate as a consequence, columns do not account for the `///` prefix.
+
+use super::*;
+
+use serde::Deserialize;
+use std::collections::HashMap;
+
+#[derive(Deserialize, Debug)]
+struct Crate {
+    index: HashMap<String, Item>,
+}
+
+#[derive(Deserialize, Debug)]
+struct Item {
+    #[serde(default)]
+    crate_id: u32,
+    #[serde(default)]
+    name: Option<String>,
+    #[serde(default)]
+    span: Option<ItemSpan>,
+    #[serde(default)]
+    docs: Option<String>,
+}
+
+#[derive(Deserialize, Debug)]
+struct ItemSpan {
+    filename: PathBuf,
+    begin: (usize, usize),
+}
+
+impl Documentation {
+    /// Adds the docs of all items of the local crate of a rustdoc JSON file.
+    ///
+    /// Each item is a separate chunk with a `RustSourceFile` origin, items
+    /// without a span are skipped.
+    pub fn add_rustdoc_json(&mut self, content: &str) -> Result<()> {
+        let krate = serde_json::from_str::<Crate>(content)
+            .wrap_err_with(|| eyre!("Failed to parse rustdoc JSON"))?;
+        // sort for a reproducible order of chunks
+        let mut items = krate
+            .index
+            .into_values()
+            .filter(|item| item.crate_id == 0)
+            .filter_map(|item| match (item.span, item.docs) {
+                (Some(span), Some(docs)) if !docs.trim().is_empty() => {
+                    Some((span, docs, item.name))
+                }
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        items.sort_by(|a, b| (&a.0.filename, a.0.begin).cmp(&(&b.0.filename, b.0.begin)));
+
+        for (span, docs, name) in items {
+            trace!(
+                "Adding docs of {} in {}",
+                name.as_deref().unwrap_or("<crate>"),
+                span.filename.display()
+            );
+            let n_lines = docs.lines().count();
+            let first_line = span.begin.0.saturating_sub(n_lines).max(1);
+            let end = docs
+                .lines()
+                .enumerate()
+                .last()
+                .map(|(idx, line)| LineColumn {
+                    line: first_line + idx,
+                    column: line.chars().count().saturating_sub(1),
+                })
+                .expect("Docs are not empty. qed");
+            let source_mapping = indexmap::indexmap! {
+                0..docs.chars().count() => Span {
+                    start: LineColumn { line: first_line, column: 0 },
+                    end,
+                }
+            };
+            self.add_inner(
+                ContentOrigin::RustSourceFile(span.filename),
+                vec![CheckableChunk::from_string(
+                    docs,
+                    source_mapping,
+                    CommentVariant::CommonMark,
+                )],
+            );
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn local_items_with_docs() {
+        const JSON: &str = r#"{
+            "root": "0:0",
+            "crate_version": null,
+            "index": {
+                "0:0": {
+                    "id": "0:0", "crate_id": 0, "name": "demo",
+                    "span": { "filename": "src/lib.rs", "begin": [1, 0], "end": [9, 1] },
+                    "docs": "Crate level docs."
+                },
+                "0:1": {
+                    "id": "0:1", "crate_id": 0, "name": "Fancy",
+                    "span": { "filename": "src/lib.rs", "begin": [7, 0], "end": [7, 13] },
+                    "docs": "A fancy struct.\n\nWith a second paragraph."
+                },
+                "0:2": {
+                    "id": "0:2", "crate_id": 0, "name": "undocumented",
+                    "span": { "filename": "src/lib.rs", "begin": [9, 0], "end": [9, 20] },
+                    "docs": null
+                },
+                "1:7": {
+                    "id": "1:7", "crate_id": 1, "name": "Vec",
+                    "span": null,
+                    "docs": "A contiguous growable array type."
+                }
+            }
+        }"#;
+        let mut docs = Documentation::new();
+        docs.add_rustdoc_json(JSON).unwrap();
+        assert_eq!(docs.entry_count(), 1);
+
+        let chunks = docs
+            .get(&ContentOrigin::RustSourceFile(PathBuf::from("src/lib.rs")))
+            .unwrap();
+        assert_eq!(chunks.len(), 2);
+        assert_eq!(chunks[0].as_str(), "Crate level docs.");
+        assert_eq!(
+            chunks[1].as_str(),
+            "A fancy struct.\n\nWith a second paragraph."
+        );
+        let span = chunks[1].find_spans(0..1).into_iter().next().unwrap().1;
+        assert_eq!(span.start, LineColumn { line: 4, column: 0 });
+    }
+}
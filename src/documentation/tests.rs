@@ -21,7 +21,8 @@ fn parse_and_construct() {
     const TEST_PLAIN: &str = r#"A very good test."#;
 
     let origin = ContentOrigin::TestEntityRust;
-    let docs = Documentation::load_from_str(origin.clone(), TEST_SOURCE, false);
+    let docs =
+        Documentation::load_from_str(origin.clone(), TEST_SOURCE, &ExtractOptions::default());
     assert_eq!(docs.index.len(), 1);
     let chunks = docs.index.get(&origin).expect("Must contain dummy path");
     assert_eq!(dbg!(chunks).len(), 1);
@@ -85,7 +86,14 @@ macro_rules! end2end {
             .try_init();
 
         let origin: ContentOrigin = $origin;
-        let docs = Documentation::load_from_str(origin.clone(), $test, true);
+        let docs = Documentation::load_from_str(
+            origin.clone(),
+            $test,
+            &ExtractOptions {
+                dev_comments: true,
+                ..Default::default()
+            },
+        );
         assert_eq!(docs.index.len(), 1);
         let chunks = docs.index.get(&origin).expect("Must contain dummy path");
         assert_eq!(dbg!(chunks).len(), 1);
@@ -271,7 +279,7 @@ struct CAPI;
 
             let origin: ContentOrigin = $origin;
 
-            let docs = Documentation::load_from_str(origin.clone(), $source, false);
+            let docs = Documentation::load_from_str(origin.clone(), $source, &ExtractOptions::default());
             let (origin2, chunks) = docs.into_iter().next().expect("Contains a document");
             let suggestions =
                 dbg!(DummyChecker.check(&origin, &chunks[..])).expect("Dummy checker never fails. qed");
@@ -991,6 +999,101 @@ fn drill_span() {
     assert_eq!(plain.find_spans(9..20).len(), 0);
 }
 
+#[test]
+fn plain_overlay_ranges() {
+    const TEST: &str = r##"ab **🐡** xy"##;
+    let chunk = CheckableChunk::from_str(
+        TEST,
+        indexmap::indexmap! { 0..11 => Span {
+            start: LineColumn {
+                line: 1usize,
+                column: 4usize,
+            },
+            end: LineColumn {
+                line: 1usize,
+                column: 14usize,
+            },
+        }},
+        CommentVariant::CommonMark,
+    );
+
+    let plain = chunk.erase_cmark();
+    let ranges = plain.ranges().collect::<Vec<_>>();
+    assert_eq!(
+        ranges
+            .iter()
+            .map(|(range, _)| sub_chars(plain.as_str(), range.clone()))
+            .collect::<Vec<_>>(),
+        vec!["ab ", "🐡", " xy"]
+    );
+    assert_eq!(
+        ranges
+            .iter()
+            .map(|(_, span)| span.start.column)
+            .collect::<Vec<_>>(),
+        vec![4, 9, 12]
+    );
+}
+
+#[test]
+fn content_origin_fingerprint() {
+    let origin = ContentOrigin::RustSourceFile(PathBuf::from("src/lib.rs"));
+    // must never change, it is persisted across runs
+    assert_eq!(origin.fingerprint(), 0x8019_cc8d_709f_efb5);
+    assert_eq!(
+        ContentOrigin::RustSourceFile(PathBuf::from("src\\lib.rs")).fingerprint(),
+        origin.fingerprint()
+    );
+    assert_ne!(
+        ContentOrigin::CommonMarkFile(PathBuf::from("src/lib.rs")).fingerprint(),
+        origin.fingerprint()
+    );
+
+    let span = |line| Span {
+        start: LineColumn { line, column: 0 },
+        end: LineColumn { line, column: 4 },
+    };
+    let doctest = ContentOrigin::RustDocTest(PathBuf::from("src/lib.rs"), span(3));
+    assert_eq!(
+        doctest.fingerprint(),
+        ContentOrigin::RustDocTest(PathBuf::from("src/lib.rs"), span(3)).fingerprint()
+    );
+    assert_ne!(
+        doctest.fingerprint(),
+        ContentOrigin::RustDocTest(PathBuf::from("src/lib.rs"), span(4)).fingerprint()
+    );
+}
+
+#[test]
+fn content_origin_order() {
+    let span = |line| Span {
+        start: LineColumn { line, column: 0 },
+        end: LineColumn { line, column: 4 },
+    };
+    let mut origins = vec![
+        ContentOrigin::CommonMarkFile(PathBuf::from("README.md")),
+        ContentOrigin::RustDocTest(PathBuf::from("src/lib.rs"), span(7)),
+        ContentOrigin::CargoManifestDescription(PathBuf::from("Cargo.toml")),
+        ContentOrigin::RustSourceFile(PathBuf::from("src/main.rs")),
+        ContentOrigin::RustDocTest(PathBuf::from("src/lib.rs"), span(3)),
+        ContentOrigin::CommonMarkFile(PathBuf::from("CHANGELOG.md")),
+        ContentOrigin::RustSourceFile(PathBuf::from("src/lib.rs")),
+    ];
+    origins.sort();
+    assert_eq!(
+        origins,
+        vec![
+            ContentOrigin::RustSourceFile(PathBuf::from("src/lib.rs")),
+            ContentOrigin::RustDocTest(PathBuf::from("src/lib.rs"), span(3)),
+            ContentOrigin::RustDocTest(PathBuf::from("src/lib.rs"), span(7)),
+            ContentOrigin::RustSourceFile(PathBuf::from("src/main.rs")),
+            ContentOrigin::CommonMarkFile(PathBuf::from("CHANGELOG.md")),
+            ContentOrigin::CommonMarkFile(PathBuf::from("README.md")),
+            ContentOrigin::CargoManifestDescription(PathBuf::from("Cargo.toml")),
+        ]
+    );
+}
+
 #[test]
 fn reduction_complex() {
     const MARKDOWN: &str = r##"# Title number 1
@@ -1745,3 +1848,106 @@ fn variant_consistency() {
         );
     }
 }
+
+#[test]
+fn nocheck_annotation_trimmed() {
+    const SOURCE: &str = fluff_up!("Some xyzzy here // [nocheck: xyzzy, plugh ]");
+    let literals = annotated_literals(SOURCE);
+    assert_eq!(literals.len(), 1);
+    let literal = &literals[0];
+    assert_eq!(literal.as_str(), " Some xyzzy here");
+    assert_eq!(
+        literal.ignored_words(),
+        &["xyzzy".to_owned(), "plugh".to_owned()]
+    );
+    assert_eq!(
+        load_span_from(SOURCE.as_bytes(), literal.span()).unwrap(),
+        " Some xyzzy here"
+    );
+}
+
+#[test]
+fn chunk_to_json() {
+    const TEST_SOURCE: &str = "/// A test.\nstruct Vikings;\n";
+
+    let origin = ContentOrigin::TestEntityRust;
+    let docs =
+        Documentation::load_from_str(origin.clone(), TEST_SOURCE, &ExtractOptions::default());
+    let chunks = docs.get(&origin).expect("Must contain dummy path");
+    assert_eq!(chunks.len(), 1);
+
+    let json = chunks[0].to_json(&origin);
+    assert_eq!(json["origin"], "TestEntityRust");
+    assert_eq!(json["variant"], "TripleSlash");
+    assert_eq!(json["content"], " A test.");
+    assert_eq!(
+        json["source_mapping"],
+        serde_json::json!([{
+            "range": [0, 8],
+            "start": { "line": 1, "column": 4 },
+            "end": { "line": 1, "column": 11 },
+        }])
+    );
+}
+
+#[test]
+fn doc_include_str() {
+    const TEST_SOURCE: &str = r#"#![doc = include_str!("../README.md")]
+#[doc = include_str!("missing.md")]
+struct Vikings;
+"#;
+    let dir = std::env::temp_dir().join(uuid::Uuid::new_v4().to_string());
+    fs_err::create_dir_all(dir.join("src")).unwrap();
+    fs_err::write(dir.join("README.md"), "# Vikings\n\nSailing the seas.\n").unwrap();
+
+    let docs = Documentation::load_from_str(
+        ContentOrigin::RustSourceFile(dir.join("src").join("lib.rs")),
+        TEST_SOURCE,
+        &ExtractOptions::default(),
+    );
+    let readme = ContentOrigin::CommonMarkFile(dir.join("README.md"));
+    let chunks = docs.get(&readme).expect("Included file is checked");
+    assert_eq!(chunks.len(), 1);
+    assert_eq!(chunks[0].as_str(), "# Vikings\n\nSailing the seas.\n");
+    assert!(!docs.contains_key(&ContentOrigin::CommonMarkFile(
+        dir.join("src").join("missing.md")
+    )));
+
+    // checking the file by itself as well does not duplicate it
+    let mut all = Documentation::new();
+    all.extend(docs.clone());
+    all.extend(docs);
+    assert_eq!(all.get(&readme).unwrap().len(), 1);
+}
+
+#[test]
+fn spans_out_of_bounds() {
+    const TEST_SOURCE: &str = "/// A test.\nstruct Vikings;\n";
+
+    let origin = ContentOrigin::TestEntityRust;
+    let docs =
+        Documentation::load_from_str(origin.clone(), TEST_SOURCE, &ExtractOptions::default());
+    let chunks = docs.get(&origin).expect("Must contain dummy path");
+    assert!(chunks[0].spans_out_of_bounds(TEST_SOURCE).is_empty());
+
+    let span = Span {
+        start: LineColumn { line: 1, column: 4 },
+        end: LineColumn {
+            line: 1,
+            column: 42,
+        },
+    };
+    let chunk = CheckableChunk::from_str(
+        " A test.",
+        indexmap::indexmap! { 0..8 => span },
+        CommentVariant::TripleSlash,
+    );
+    assert_eq!(
+        chunk.spans_out_of_bounds(TEST_SOURCE),
+        vec![(span, span.end, Some(11))]
+    );
+    assert_eq!(
+        chunk.spans_out_of_bounds(""),
+        vec![(span, span.start, None)]
+    );
+}
@@ -0,0 +1,179 @@
+//! Management of the git `pre-commit` hook, which checks all staged files.
+//!
+//! The hook is wrapped in marker lines, so it can be appended to an existing
+//! `pre-commit` script and be removed again without touching the rest.
+
+use crate::errors::*;
+
+use fs_err as fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// The hook script snippet, including the start and end markers.
+const HOOK_TEMPLATE: &str = include_str!("pre-commit.sh");
+
+const MARKER_START: &str = "# >>> cargo-spellcheck >>>";
+const MARKER_END: &str = "# <<< cargo-spellcheck <<<";
+
+/// Locate the `pre-commit` hook of the git repository in the current working
+/// directory.
+fn pre_commit_path() -> Result<PathBuf> {
+    let output = Command::new("git")
+        .args(&["rev-parse", "--git-path", "hooks"])
+        .output()
+        .wrap_err("Failed to execute `git`, is it installed?")?;
+    if !output.status.success() {
+        bail!(
+            "Failed to determine the git hooks directory, is this a git repository? {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    let hooks = PathBuf::from(String::from_utf8_lossy(&output.stdout).trim());
+    Ok(hooks.join("pre-commit"))
+}
+
+/// Install the hook into the current git repository.
+pub(crate) fn install() -> Result<()> {
+    install_into(pre_commit_path()?)
+}
+
+/// Remove the hook from the current git repository.
+pub(crate) fn uninstall() -> Result<()> {
+    uninstall_from(pre_commit_path()?)
+}
+
+/// Write the hook to `path`, or append it if the file exists already.
+fn install_into(path: impl AsRef<Path>) -> Result<()> {
+    let path = path.as_ref();
+    let content = match fs::read_to_string(path) {
+        Ok(existing) if existing.contains(MARKER_START) => {
+            log::info!("Hook is already installed in {}", path.display());
+            return Ok(());
+        }
+        Ok(existing) if existing.ends_with('\n') || existing.is_empty() => existing + HOOK_TEMPLATE,
+        Ok(existing) => existing + "\n" + HOOK_TEMPLATE,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            "#!/bin/sh\n".to_owned() + HOOK_TEMPLATE
+        }
+        Err(e) => return Err(e.into()),
+    };
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, content)?;
+    make_executable(path)?;
+    log::info!("Installed hook in {}", path.display());
+    Ok(())
+}
+
+/// Remove the hook lines from `path`, the file is removed if nothing but the
+/// shebang is left.
+fn uninstall_from(path: impl AsRef<Path>) -> Result<()> {
+    let path = path.as_ref();
+    let existing = match fs::read_to_string(path) {
+        Ok(existing) => existing,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            log::info!("No hook present at {}", path.display());
+            return Ok(());
+        }
+        Err(e) => return Err(e.into()),
+    };
+
+    let mut within = false;
+    let remaining = existing
+        .lines()
+        .filter(|line| match line.trim() {
+            MARKER_START => {
+                within = true;
+                false
+            }
+            MARKER_END => {
+                within = false;
+                false
+            }
+            _ => !within,
+        })
+        .fold(String::with_capacity(existing.len()), |mut acc, line| {
+            acc.push_str(line);
+            acc.push('\n');
+            acc
+        });
+
+    if remaining
+        .lines()
+        .all(|line| line.trim().is_empty() || line.starts_with("#!"))
+    {
+        fs::remove_file(path)?;
+        log::info!("Removed hook {}", path.display());
+    } else {
+        fs::write(path, remaining)?;
+        log::info!("Removed hook lines from {}", path.display());
+    }
+    Ok(())
+}
+
+#[cfg(unix)]
+fn make_executable(path: &Path) -> Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+    let mut permissions = fs::metadata(path)?.permissions();
+    permissions.set_mode(permissions.mode() | 0o755);
+    fs::set_permissions(path, permissions)?;
+    Ok(())
+}
+
+#[cfg(not(unix))]
+fn make_executable(_path: &Path) -> Result<()> {
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tmp_hook_path() -> PathBuf {
+        std::env::temp_dir()
+            .join(uuid::Uuid::new_v4().to_string())
+            .join("pre-commit")
+    }
+
+    #[test]
+    fn template_has_markers() {
+        assert!(HOOK_TEMPLATE.starts_with(MARKER_START));
+        assert!(HOOK_TEMPLATE.trim_end().ends_with(MARKER_END));
+    }
+
+    #[test]
+    fn install_uninstall_fresh() {
+        let path = tmp_hook_path();
+        install_into(&path).unwrap();
+        let content = fs::read_to_string(&path).unwrap();
+        assert!(content.starts_with("#!/bin/sh\n"));
+        assert!(content.contains("git diff --cached --name-only"));
+
+        // idempotent
+        install_into(&path).unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), content);
+
+        uninstall_from(&path).unwrap();
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn install_uninstall_existing() {
+        const EXISTING: &str = "#!/bin/sh\ncargo fmt --check";
+        let path = tmp_hook_path();
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, EXISTING).unwrap();
+
+        install_into(&path).unwrap();
+        let content = fs::read_to_string(&path).unwrap();
+        assert!(content.starts_with(EXISTING));
+        assert!(content.contains(MARKER_START));
+
+        uninstall_from(&path).unwrap();
+        assert_eq!(
+            fs::read_to_string(&path).unwrap(),
+            EXISTING.to_owned() + "\n"
+        );
+    }
+}
@@ -0,0 +1,13 @@
+# >>> cargo-spellcheck >>>
+# Generated by `cargo spellcheck hook install`, remove with
+# `cargo spellcheck hook uninstall`.
+if ! command -v cargo-spellcheck >/dev/null 2>&1; then
+	echo "cargo-spellcheck is not in \$PATH, install it with \`cargo install --locked cargo-spellcheck\` or remove this hook with \`cargo spellcheck hook uninstall\`." >&2
+	exit 1
+fi
+if [ -n "$(git diff --cached --name-only --diff-filter=ACMR -- '*.rs' '*.md')" ]; then
+	git diff --cached --name-only -z --diff-filter=ACMR -- '*.rs' '*.md' \
+		| xargs -0 cargo spellcheck check --code=1 -- \
+		|| exit 1
+fi
+# <<< cargo-spellcheck <<<
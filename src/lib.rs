@@ -14,12 +14,18 @@
 //! cargo-spellcheck
 //!
 //! A syntax tree based doc comment and common mark spell checker.
+//!
+//! When used as a library, [`check_str`] is the primary entry point, it checks
+//! a single string and returns the found mistakes.
 
 pub mod action;
+mod baseline;
+mod cache;
 mod checker;
 mod config;
 mod documentation;
 pub mod errors;
+mod hook;
 mod reflow;
 mod span;
 mod suggestion;
@@ -28,13 +34,13 @@ mod util;
 
 pub use self::action::*;
 pub use self::config::args::*;
-pub use self::config::{Config, HunspellConfig, LanguageToolConfig};
+pub use self::config::{Config, HunspellConfig, LanguageToolConfig, OutputFormat};
 pub use self::documentation::*;
 pub use self::span::*;
 pub use self::suggestion::*;
 pub use self::util::*;
 
-use self::errors::{bail, Result};
+use self::errors::{bail, eyre, Result, WrapErr};
 
 use log::{debug, info, trace, warn};
 use serde::Deserialize;
@@ -56,17 +62,26 @@ use checker::Checker;
 /// A simple exit code representation.
 ///
 /// `Custom` can be specified by the user, others map to their UNIX equivalents
-/// where available.
+/// where available. The codes are stable across releases:
+///
+/// * `0` success, no spelling mistakes were found
+/// * `1` spelling mistakes were found, unless overridden with `--code=<code>`
+/// * `2` invalid configuration, i.e. a missing dictionary
+/// * `3` internal error, i.e. an I/O error or a panic
+/// * `130` terminated by a signal
 #[derive(Debug, Clone, Copy, Eq, PartialEq)]
 pub enum ExitCode {
-    /// Regular termination and does not imply anything in regards to spelling
-    /// mistakes found or not.
+    /// Regular termination, no spelling mistakes were found.
     Success,
     /// Terminate requested by a *nix signal.
     Signal,
-    /// A custom exit code, as specified with `--code=<code>`.
+    /// Spelling mistakes were found, `1` unless specified otherwise with
+    /// `--code=<code>`.
     Custom(u8),
-    // Failure is already default for `Err(_)`
+    /// The configuration is invalid or refers to files which do not exist.
+    Configuration,
+    /// Any other failure.
+    Internal,
 }
 
 impl ExitCode {
@@ -76,10 +91,84 @@ impl ExitCode {
             Self::Success => 0u8,
             Self::Signal => 130u8,
             Self::Custom(code) => code,
+            Self::Configuration => 2u8,
+            Self::Internal => 3u8,
+        }
+    }
+
+    /// The exit code for an error returned by [`run`].
+    ///
+    /// A closed pipe on the receiving end of the output is not a failure.
+    pub fn from_error(error: &errors::Error) -> Self {
+        if error
+            .downcast_ref::<std::io::Error>()
+            .filter(|e| e.kind() == std::io::ErrorKind::BrokenPipe)
+            .is_some()
+        {
+            Self::Success
+        } else if error.downcast_ref::<ConfigurationError>().is_some()
+            || error
+                .downcast_ref::<config::DictionaryNotFoundError>()
+                .is_some()
+        {
+            Self::Configuration
+        } else {
+            Self::Internal
         }
     }
 }
 
+/// Marks an error to be caused by the configuration or the command line
+/// arguments, which results in [`ExitCode::Configuration`].
+#[derive(Debug, Clone, Copy, thiserror::Error)]
+#[error("Invalid configuration")]
+pub struct ConfigurationError;
+
+/// A spelling mistake found by [`check_str`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SpellingError {
+    /// The misspelled word.
+    pub word: String,
+    /// The location of the word within the checked content.
+    pub span: Span,
+    /// Replacement suggestions, the most likely first.
+    pub suggestions: Vec<String>,
+}
+
+/// Check `content` with the checkers enabled in `config`.
+///
+/// The `origin` determines how `content` is processed: rust sources and doc
+/// tests are parsed for their doc comments, common mark files are checked as a
+/// whole and manifests only for their `description`. The path of the origin
+/// does not need to exist, it is only used to apply `[[file_type]]` entries.
+pub fn check_str(
+    content: &str,
+    origin: ContentOrigin,
+    config: &Config,
+) -> Result<Vec<SpellingError>> {
+    let docs = Documentation::load_from_str(
+        origin,
+        content,
+        &ExtractOptions {
+            dev_comments: config.dev_comments,
+            string_literals: config.check_string_literals,
+            attr_keys: config.attr_keys().to_vec(),
+            macro_names: config.check_macro_strings.clone(),
+        },
+    );
+    let checkers = checker::Checkers::new(config.clone())?;
+    let mut errors = Vec::new();
+    for (origin, chunks) in docs.iter() {
+        let suggestions = checkers.check(origin, &chunks[..])?;
+        errors.extend(suggestions.into_iter().map(|suggestion| SpellingError {
+            word: suggestion.word(),
+            span: suggestion.span,
+            suggestions: suggestion.replacements,
+        }));
+    }
+    Ok(errors)
+}
+
 /// Global atomic to block signal processing while a file write is currently in
 /// progress.
 static WRITE_IN_PROGRESS: AtomicU16 = AtomicU16::new(0);
@@ -145,23 +234,50 @@ pub fn run() -> Result<ExitCode> {
         .num_threads(args.job_count())
         .build_global();
 
-    env_logger::Builder::from_env(env_logger::Env::new().filter_or("CARGO_SPELLCHECK", "warn"))
-        .filter_level(args.verbosity())
+    // the verbosity flags take precedence over the `CARGO_SPELLCHECK` and
+    // `RUST_LOG` filters, without either only errors are logged
+    let filter_env = if std::env::var_os("CARGO_SPELLCHECK").is_some() {
+        "CARGO_SPELLCHECK"
+    } else {
+        "RUST_LOG"
+    };
+    let mut logger =
+        env_logger::Builder::from_env(env_logger::Env::new().filter_or(filter_env, "error"));
+    if let Some(level) = args.verbosity() {
+        logger.filter_level(level);
+    }
+    logger
         .filter_module("nlprule", log::LevelFilter::Error)
         .filter_module("mio", log::LevelFilter::Error)
         .init();
 
+    let colors = args.should_use_color();
+    console::set_colors_enabled(colors);
+    console::set_colors_enabled_stderr(colors);
+
     #[cfg(not(target_os = "windows"))]
     signal_handler();
 
+    documentation::set_paranoid(args.paranoid);
+
     let (unified, config) = match &args.command {
-        Some(Sub::Completions { shell }) => {
+        Some(Sub::Completions { shell, shell_flag }) => {
+            let shell = shell_flag.or(*shell).ok_or_else(|| {
+                eyre!("Missing shell, expected one of `bash`, `elvish`, `fish`, `powershell` or `zsh`")
+            })?;
             let sink = &mut std::io::stdout();
-            generate_completions(*shell, sink);
+            generate_completions(shell, sink);
             let _ = sink.flush();
             return Ok(ExitCode::Success);
         }
-        _ => args.unified()?,
+        Some(Sub::Hook { action }) => {
+            match action {
+                HookAction::Install => hook::install()?,
+                HookAction::Uninstall => hook::uninstall()?,
+            }
+            return Ok(ExitCode::Success);
+        }
+        _ => args.unified().wrap_err(ConfigurationError)?,
     };
 
     match unified {
@@ -169,8 +285,19 @@ pub fn run() -> Result<ExitCode> {
         UnifiedArgs::Config {
             dest_config,
             checker_filter_set,
+            commented,
+            summary,
         } => {
             trace!("Configuration chore");
+            let stdout = std::io::stdout();
+            let mut out = stdout.lock();
+            if let Some(summary) = summary {
+                for line in summary {
+                    writeln!(out, "# {}", line)?;
+                }
+                writeln!(out, "{}", config.to_toml()?)?;
+                return Ok(ExitCode::Success);
+            }
             let mut config = Config::full();
             Args::checker_selection_override(
                 checker_filter_set.as_ref().map(AsRef::as_ref),
@@ -178,8 +305,12 @@ pub fn run() -> Result<ExitCode> {
             )?;
 
             match dest_config {
+                ConfigWriteDestination::Stdout if commented => {
+                    write!(out, "{}", config.to_commented_toml()?)?;
+                    return Ok(ExitCode::Success);
+                }
                 ConfigWriteDestination::Stdout => {
-                    println!("{}", config.to_toml()?);
+                    writeln!(out, "{}", config.to_toml()?)?;
                     return Ok(ExitCode::Success);
                 }
                 ConfigWriteDestination::File { overwrite, path } => {
@@ -196,31 +327,203 @@ pub fn run() -> Result<ExitCode> {
             }
             return Ok(ExitCode::Success);
         }
+        UnifiedArgs::Dict {
+            action: DictAction::Available,
+        } => {
+            let hunspell = config.hunspell.unwrap_or_default();
+            let stdout = std::io::stdout();
+            let mut out = stdout.lock();
+            for (dic, _aff) in hunspell.available_dictionaries() {
+                let lang = dic.file_stem().unwrap_or_default().to_string_lossy();
+                writeln!(out, "{}\t{}", lang, dic.display())?;
+            }
+            return Ok(ExitCode::Success);
+        }
+        UnifiedArgs::MigrateConfig {
+            config_path,
+            in_place,
+        } => {
+            let config_path = config_path
+                .ok_or_else(|| eyre!("No configuration file in use, nothing to migrate"))?;
+            let (content, config_path) = Config::load_content(&config_path)?;
+            let migrated = config::migrate(&content)?;
+            if in_place {
+                info!(
+                    "Writing migrated configuration to {}",
+                    config_path.display()
+                );
+                fs_err::write(&config_path, migrated)?;
+            } else {
+                let stdout = std::io::stdout();
+                let mut out = stdout.lock();
+                write!(out, "{}", migrated)?;
+            }
+            return Ok(ExitCode::Success);
+        }
+        UnifiedArgs::Explain { word } => {
+            #[cfg(feature = "hunspell")]
+            {
+                checker::explain(&word, &config)?;
+                return Ok(ExitCode::Success);
+            }
+            #[cfg(not(feature = "hunspell"))]
+            bail!("Explaining `{}` requires the `hunspell` feature", word);
+        }
         UnifiedArgs::Operate {
             action,
             paths,
             recursive,
             skip_readme,
+            no_ignore,
             config_path,
             dev_comments,
+            check_string_literals,
+            rustdoc_json,
+            doc_test_lines,
+            fix_count,
+            max_errors,
+            dry_run,
             exit_code_override,
+            quiet,
         } => {
             debug!(
                 "Executing: {:?} with {:?} from {:?}",
                 action, &config, config_path
             );
 
-            let documents =
-                traverse::extract(paths, recursive, skip_readme, dev_comments, &config)?;
+            if fix_count.is_some() && action != Action::Check {
+                bail!("`--fix-count` is only supported when checking");
+            }
+            if max_errors.is_some() && action != Action::Check {
+                bail!("`--max-errors` is only supported when checking");
+            }
+            if dry_run && action != Action::Fix {
+                bail!("`--dry-run` is only supported when fixing");
+            }
+
+            let mut documents = if let Some(rustdoc_json) = rustdoc_json {
+                if matches!(action, Action::Fix | Action::Reflow) || fix_count.is_some() {
+                    bail!("Spans of `--rustdoc-json` are approximate, only checking is supported");
+                }
+                let mut documents = Documentation::new();
+                documents.add_rustdoc_json(&fs_err::read_to_string(rustdoc_json)?)?;
+                documents
+            } else {
+                traverse::extract(
+                    paths,
+                    recursive,
+                    skip_readme,
+                    no_ignore,
+                    dev_comments,
+                    check_string_literals,
+                    &config,
+                )?
+            };
+            if let Some(doc_test_lines) = doc_test_lines {
+                // only the doc comments containing one of the failed doc tests
+                documents.retain_chunks(|origin, chunk| {
+                    doc_test_lines
+                        .iter()
+                        .filter(|(path, _lines)| origin.as_path().ends_with(path))
+                        .flat_map(|(_path, lines)| lines)
+                        .any(|&line| {
+                            chunk.iter().any(|(_range, span)| {
+                                span.start.line <= line && line <= span.end.line
+                            })
+                        })
+                });
+            }
 
             let rt = tokio::runtime::Runtime::new()?;
-            let finish = rt.block_on(async move { action.run(documents, config).await })?;
+            let finish = rt.block_on(async move {
+                action
+                    .run(documents, config, fix_count, max_errors, dry_run, quiet)
+                    .await
+            })?;
 
             match finish {
                 Finish::Success | Finish::MistakeCount(0) => Ok(ExitCode::Success),
+                // stopping early or files which would change must not pass as
+                // success, even with `--code=0`
+                Finish::MistakeCount(n)
+                    if exit_code_override == 0
+                        && (dry_run || max_errors.map(|max| n >= max).unwrap_or(false)) =>
+                {
+                    Ok(ExitCode::Custom(1))
+                }
                 Finish::MistakeCount(_n) => Ok(ExitCode::Custom(exit_code_override)),
                 Finish::Abort => Ok(ExitCode::Signal),
             }
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(feature = "hunspell")]
+    #[test]
+    fn check_str_rust_and_markdown() {
+        let mut config = Config::default();
+        config.nlprules = None;
+        config.hunspell = Some(HunspellConfig {
+            skip_os_lookups: true,
+            use_builtin: true,
+            ..Default::default()
+        });
+
+        let errors = check_str(
+            "/// A tset of the code.\nstruct X;\n",
+            ContentOrigin::RustSourceFile(std::path::PathBuf::from("lib.rs")),
+            &config,
+        )
+        .unwrap();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].word, "tset");
+        assert_eq!(errors[0].span.start, LineColumn { line: 1, column: 6 });
+        assert!(errors[0].suggestions.contains(&"test".to_owned()));
+
+        let errors = check_str(
+            "# Title\n\nA tset of the code.\n",
+            ContentOrigin::CommonMarkFile(std::path::PathBuf::from("README.md")),
+            &config,
+        )
+        .unwrap();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].word, "tset");
+        assert_eq!(errors[0].span.start, LineColumn { line: 3, column: 2 });
+    }
+
+    #[test]
+    fn exit_code_from_error() {
+        let config_error = Config::parse("[hunspell").wrap_err(ConfigurationError);
+        assert_eq!(
+            ExitCode::from_error(&config_error.unwrap_err().wrap_err("Some more context")),
+            ExitCode::Configuration
+        );
+
+        let missing_dictionary = errors::Error::from(config::DictionaryNotFoundError {
+            path: std::path::PathBuf::from("xx_XX.dic"),
+            config_key: "Hunspell.lang",
+            search_dirs: Vec::new(),
+        });
+        assert_eq!(
+            ExitCode::from_error(&missing_dictionary),
+            ExitCode::Configuration
+        );
+
+        assert_eq!(
+            ExitCode::from_error(&eyre!("Failed to read a file")),
+            ExitCode::Internal
+        );
+        assert_eq!(
+            ExitCode::from_error(&errors::Error::from(std::io::Error::from(
+                std::io::ErrorKind::BrokenPipe
+            ))),
+            ExitCode::Success
+        );
+        assert_eq!(ExitCode::Configuration.as_u8(), 2);
+        assert_eq!(ExitCode::Internal.as_u8(), 3);
+    }
+}
@@ -1,16 +1,41 @@
 use log::warn;
 
-use cargo_spellcheck::{action, errors::Result, run};
+use cargo_spellcheck::{action, errors::Result, run, ExitCode};
+
+/// Terminate silently if the reading end of a pipe is closed, i.e. with
+/// `cargo spellcheck | head`, like other unix tools do. Rust ignores `SIGPIPE`
+/// by default, so writing to stdout would panic instead.
+#[cfg(unix)]
+fn reset_sigpipe() {
+    // SAFETY: called first thing in `main`, before any other thread exists
+    unsafe {
+        libc::signal(libc::SIGPIPE, libc::SIG_DFL);
+    }
+}
+
+#[cfg(not(unix))]
+fn reset_sigpipe() {}
 
 #[allow(missing_docs)]
 fn main() -> Result<()> {
+    reset_sigpipe();
     let _ = color_eyre::install()?;
-    let res = run();
+    // the panic hook already reported the panic
+    let res = std::panic::catch_unwind(run).unwrap_or(Ok(ExitCode::Internal));
     // no matter what, restore the terminal
     if let Err(e) = action::interactive::ScopedRaw::restore_terminal() {
         warn!("Failed to restore terminal: {}", e);
     }
-    let val = res?.as_u8();
+    let val = match res {
+        Ok(exit_code) => exit_code.as_u8(),
+        Err(e) => {
+            let exit_code = ExitCode::from_error(&e);
+            if exit_code != ExitCode::Success {
+                eprintln!("Error: {:?}", e);
+            }
+            exit_code.as_u8()
+        }
+    };
     if val != 0 {
         std::process::exit(val as i32)
     }
@@ -11,7 +11,7 @@ use crate::documentation::CheckableChunk;
 use crate::util::load_span_from;
 use crate::util::{byte_range_to_char_range, byte_range_to_char_range_many, sub_char_range};
 
-use crate::{CommentVariant, ContentOrigin, Detector, Range, Span, Suggestion};
+use crate::{CommentVariant, ContentOrigin, Detector, Range, Severity, Span, Suggestion};
 
 use pulldown_cmark::{Event, Options, Parser, Tag};
 
@@ -51,7 +51,8 @@ impl Checker for Reflow {
             match chunk.variant() {
                 CommentVariant::SlashAsterisk
                 | CommentVariant::SlashAsteriskAsterisk
-                | CommentVariant::SlashAsteriskEM => continue,
+                | CommentVariant::SlashAsteriskEM
+                | CommentVariant::StringLiteral(_) => continue,
                 _ => {}
             }
             let suggestions = reflow(&origin, chunk, &self.config)?;
@@ -427,6 +428,7 @@ fn store_suggestion<'s>(
                 detector: Detector::Reflow,
                 origin: origin.clone(),
                 description: None,
+                severity: Severity::Error,
                 range,
                 replacements: vec![replacement],
                 span,
@@ -1,7 +1,7 @@
 use super::*;
 use crate::util::load_span_from;
 use crate::{chyrp_up, fluff_up};
-use crate::{Documentation, LineColumn, Span};
+use crate::{Documentation, ExtractOptions, LineColumn, Span};
 
 macro_rules! verify_reflow_inner {
     ($n:literal break [ $( $line:literal ),+ $(,)?] => $expected:literal) => {
@@ -13,7 +13,7 @@ macro_rules! verify_reflow_inner {
             .try_init();
 
         const CONTENT: &str = fluff_up!($( $line ),+);
-        let docs = Documentation::load_from_str(ContentOrigin::TestEntityRust, CONTENT, false);
+        let docs = Documentation::load_from_str(ContentOrigin::TestEntityRust, CONTENT, &ExtractOptions::default());
         assert_eq!(docs.entry_count(), 1);
         let chunks = docs.get(&ContentOrigin::TestEntityRust).expect("Must contain dummy path");
         assert_eq!(dbg!(chunks).len(), 1);
@@ -87,7 +87,7 @@ macro_rules! reflow_content {
             .is_test(true)
             .try_init();
 
-        let docs = Documentation::load_from_str($content_type, $content, false);
+        let docs = Documentation::load_from_str($content_type, $content, &ExtractOptions::default());
         assert_eq!(docs.entry_count(), 1);
         let chunks = docs.get(&$content_type).expect("Contains test data. qed");
         assert_eq!(dbg!(chunks).len(), 1);
@@ -129,7 +129,7 @@ macro_rules! reflow_content {
             .is_test(true)
             .try_init();
 
-        let docs = Documentation::load_from_str($content_type, $content, false);
+        let docs = Documentation::load_from_str($content_type, $content, &ExtractOptions::default());
         assert_eq!(docs.entry_count(), 1);
         let chunks = docs.get(&$content_type).expect("Contains test data. qed");
         assert_eq!(dbg!(chunks).len(), 1);
@@ -154,7 +154,7 @@ macro_rules! reflow_content {
             .is_test(true)
             .try_init();
 
-        let docs = Documentation::load_from_str($content_type, $content, false);
+        let docs = Documentation::load_from_str($content_type, $content, &ExtractOptions::default());
         assert_eq!(docs.entry_count(), 1);
         let chunks = docs.get(&$content_type).expect("Contains test data. qed");
         assert_eq!(dbg!(chunks).len(), 1);
@@ -286,7 +286,11 @@ fn reflow_indentations() {
         max_line_length: 10,
     };
 
-    let docs = Documentation::load_from_str(ContentOrigin::TestEntityRust, CONTENT, false);
+    let docs = Documentation::load_from_str(
+        ContentOrigin::TestEntityRust,
+        CONTENT,
+        &ExtractOptions::default(),
+    );
     assert_eq!(docs.entry_count(), 1);
     let chunks = docs
         .get(&ContentOrigin::TestEntityRust)
@@ -322,7 +326,11 @@ fn reflow_doc_indentations() {
     #[doc = r#"that spans over two lines and"#]
     #[doc = r#"should be rewrapped."##;
 
-    let docs = Documentation::load_from_str(ContentOrigin::TestEntityRust, CONTENT, false);
+    let docs = Documentation::load_from_str(
+        ContentOrigin::TestEntityRust,
+        CONTENT,
+        &ExtractOptions::default(),
+    );
     assert_eq!(dbg!(&docs).entry_count(), 1);
     let chunks = docs
         .get(&ContentOrigin::TestEntityRust)
@@ -392,7 +400,11 @@ fn reflow_markdown_two_paragraphs() {
         .is_test(true)
         .try_init();
 
-    let docs = Documentation::load_from_str(ContentOrigin::TestEntityRust, CONTENT, false);
+    let docs = Documentation::load_from_str(
+        ContentOrigin::TestEntityRust,
+        CONTENT,
+        &ExtractOptions::default(),
+    );
     assert_eq!(docs.entry_count(), 1);
     let chunks = docs
         .get(&ContentOrigin::TestEntityRust)
@@ -433,7 +445,11 @@ With a second part that is fine"#
         r#"With a second part that is fine"#,
     ];
 
-    let docs = Documentation::load_from_str(ContentOrigin::TestEntityRust, chyrped, false);
+    let docs = Documentation::load_from_str(
+        ContentOrigin::TestEntityRust,
+        chyrped,
+        &ExtractOptions::default(),
+    );
     assert_eq!(docs.entry_count(), 1);
     let chunks = docs
         .get(&ContentOrigin::TestEntityRust)
@@ -527,7 +543,11 @@ multiline. Fullstop."#,
         .is_test(true)
         .try_init();
 
-    let docs = Documentation::load_from_str(ContentOrigin::TestEntityCommonMark, CONTENT, false);
+    let docs = Documentation::load_from_str(
+        ContentOrigin::TestEntityCommonMark,
+        CONTENT,
+        &ExtractOptions::default(),
+    );
     assert_eq!(docs.entry_count(), 1);
     let chunks = docs
         .get(&ContentOrigin::TestEntityCommonMark)
@@ -595,7 +615,11 @@ struct Fff;
         end: LineColumn { line: 2, column: 8 },
     };
 
-    let docs = Documentation::load_from_str(ContentOrigin::TestEntityRust, CONTENT, false);
+    let docs = Documentation::load_from_str(
+        ContentOrigin::TestEntityRust,
+        CONTENT,
+        &ExtractOptions::default(),
+    );
     assert_eq!(docs.entry_count(), 1);
     let chunks = docs
         .get(&ContentOrigin::TestEntityRust)
@@ -8,6 +8,7 @@ use crate::util;
 use crate::Range;
 pub use proc_macro2::LineColumn;
 
+use std::fmt;
 use std::hash::{Hash, Hasher};
 
 use crate::errors::*;
@@ -38,6 +39,21 @@ impl Hash for Span {
     }
 }
 
+/// Displays as `line:column-line:column`, where both lines and columns are
+/// 1-indexed, like most editors do.
+impl fmt::Display for Span {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            formatter,
+            "{}:{}-{}:{}",
+            self.start.line,
+            self.start.column + 1,
+            self.end.line,
+            self.end.column + 1
+        )
+    }
+}
+
 impl Span {
     /// Converts a span to a range, where `self` is converted to a range
     /// relative to the passed span `scope`. Only works for literals spanning a
@@ -77,6 +93,43 @@ impl Span {
         }
     }
 
+    /// Convert the character based columns to byte offsets within
+    /// `source_line`, the line the span is located in.
+    ///
+    /// Returns the byte offset of the first character and the byte offset just
+    /// after the last character, so the result can be used to slice
+    /// `source_line`. Columns past the end of the line are clamped to its
+    /// length. Only meaningful for spans covering a single line.
+    pub fn byte_columns(&self, source_line: &str) -> (usize, usize) {
+        let byte_offset = |column: usize| {
+            source_line
+                .char_indices()
+                .nth(column)
+                .map(|(offset, _c)| offset)
+                .unwrap_or(source_line.len())
+        };
+        (
+            byte_offset(self.start.column),
+            byte_offset(self.end.column + 1),
+        )
+    }
+
+    /// Convert the span to the byte range it covers within `source`, which
+    /// starts at line 1 and column 0.
+    ///
+    /// Columns count characters, so multi-byte UTF-8 characters are accounted
+    /// for. The returned range is exclusive and can be used to slice `source`,
+    /// positions past the end of `source` are clamped to its length.
+    pub fn to_byte_range(&self, source: &str) -> Range {
+        let start = util::byte_offset_at(source, self.start);
+        let end = util::iter_with_line_column(source)
+            .take_while(|&(_c, _byte_offset, _idx, cursor)| cursor <= self.end)
+            .last()
+            .map(|(c, byte_offset, _idx, _cursor)| byte_offset + c.len_utf8())
+            .unwrap_or(0);
+        start..std::cmp::max(start, end)
+    }
+
     ///  Check if `self` covers multiple lines
     pub fn is_multiline(&self) -> bool {
         self.start.line != self.end.line
@@ -286,6 +339,84 @@ mod tests {
     use crate::{chyrp_dbg, chyrp_up, fluff_up};
     use crate::{LineColumn, Range, Span};
 
+    #[test]
+    fn byte_columns() {
+        const LINE: &str = "/// Größe of 中文 text";
+        let span = Span {
+            start: LineColumn { line: 1, column: 4 },
+            end: LineColumn { line: 1, column: 8 },
+        };
+        let (start, end) = span.byte_columns(LINE);
+        assert_eq!((start, end), (4, 11));
+        assert_eq!(&LINE[start..end], "Größe");
+
+        let span = Span {
+            start: LineColumn {
+                line: 1,
+                column: 13,
+            },
+            end: LineColumn {
+                line: 1,
+                column: 14,
+            },
+        };
+        let (start, end) = span.byte_columns(LINE);
+        assert_eq!(&LINE[start..end], "中文");
+
+        let span = Span {
+            start: LineColumn {
+                line: 1,
+                column: 19,
+            },
+            end: LineColumn {
+                line: 1,
+                column: 42,
+            },
+        };
+        assert_eq!(span.byte_columns(LINE), (LINE.len() - 1, LINE.len()));
+    }
+
+    #[test]
+    fn to_byte_range() {
+        const SOURCE: &str = "/// 中文 text\n/// 🐠 and 🐢 swim\n";
+        let span = Span {
+            start: LineColumn { line: 1, column: 4 },
+            end: LineColumn { line: 1, column: 5 },
+        };
+        let range = span.to_byte_range(SOURCE);
+        assert_eq!(range, 4..10);
+        assert_eq!(&SOURCE[range], "中文");
+
+        let span = Span {
+            start: LineColumn { line: 2, column: 4 },
+            end: LineColumn { line: 2, column: 4 },
+        };
+        assert_eq!(&SOURCE[span.to_byte_range(SOURCE)], "🐠");
+
+        let span = Span {
+            start: LineColumn { line: 2, column: 6 },
+            end: LineColumn {
+                line: 2,
+                column: 11,
+            },
+        };
+        assert_eq!(&SOURCE[span.to_byte_range(SOURCE)], "and 🐢 ");
+
+        // spanning the line break
+        let span = Span {
+            start: LineColumn { line: 1, column: 7 },
+            end: LineColumn { line: 2, column: 4 },
+        };
+        assert_eq!(&SOURCE[span.to_byte_range(SOURCE)], "text\n/// 🐠");
+
+        // past the end
+        let span = Span {
+            start: LineColumn { line: 3, column: 0 },
+            end: LineColumn { line: 3, column: 5 },
+        };
+        assert_eq!(span.to_byte_range(SOURCE), SOURCE.len()..SOURCE.len());
+    }
+
     #[test]
     fn span_to_range_singleline() {
         let _ = env_logger::builder()
@@ -524,4 +655,29 @@ three"#;
             assert_eq!(range, 5..7);
         }
     }
+
+    #[test]
+    fn display() {
+        const SPAN: Span = Span {
+            start: LineColumn {
+                line: 42,
+                column: 4,
+            },
+            end: LineColumn {
+                line: 42,
+                column: 11,
+            },
+        };
+        assert_eq!(SPAN.to_string(), "42:5-42:12");
+        assert_eq!(
+            crate::ContentOrigin::RustDocTest(std::path::PathBuf::from("src/lib.rs"), SPAN)
+                .to_string(),
+            "src/lib.rs:42:5"
+        );
+        assert_eq!(
+            crate::ContentOrigin::RustSourceFile(std::path::PathBuf::from("src/lib.rs"))
+                .to_string(),
+            "src/lib.rs"
+        );
+    }
 }
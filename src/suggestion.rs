@@ -11,10 +11,13 @@
 //!     |     - you can add it to your personal dictionary to prevent future alerts.
 //! ```
 
+use crate::checker::replacement_confidence;
 use crate::documentation::{CheckableChunk, ContentOrigin};
+use crate::util::sub_chars;
 
 use std::cmp;
 use std::convert::TryFrom;
+use std::path::PathBuf;
 
 use rayon::iter::{IntoParallelRefMutIterator, ParallelIterator};
 
@@ -29,6 +32,10 @@ pub enum Detector {
     NlpRules,
     /// Reflow according to a given max column.
     Reflow,
+    /// Non-ASCII characters in comments, see `ascii_only` of `[checks]`.
+    Ascii,
+    /// Words repeated in sequence, see `duplicate_words` of `[checks]`.
+    DuplicateWord,
     /// Detection of nothing, a test helper.
     #[cfg(test)]
     Dummy,
@@ -41,19 +48,74 @@ impl Detector {
             Self::Hunspell => "Hunspell",
             Self::NlpRules => "NlpRules",
             Self::Reflow => "Reflow",
+            Self::Ascii => "Ascii",
+            Self::DuplicateWord => "DuplicateWord",
             #[cfg(test)]
             Self::Dummy => "Dummy",
         }
     }
 }
 
-/// Terminal size in characters.
+/// How severe a suggestion is, see `warn_threshold`.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum Severity {
+    /// A mistake, which counts towards the exit code.
+    Error,
+    /// A likely false positive, which is only printed.
+    Warning,
+}
+
+impl Severity {
+    /// Converts the severity to its static str representation.
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::Error => "error",
+            Self::Warning => "warning",
+        }
+    }
+}
+
+/// Confidence of a replacement being the correct fix, in the range of `0.0` to
+/// `1.0` where `1.0` means identical to the misspelled word.
+#[derive(Debug, Clone, Copy, Default, PartialEq, PartialOrd)]
+pub struct Confidence(f32);
+
+impl Confidence {
+    /// The confidence of an identical replacement.
+    pub const IDENTICAL: Self = Self(1.0);
+
+    /// Create a confidence, clamped to the range of `0.0` to `1.0`.
+    pub fn new(value: f32) -> Self {
+        Self(value.clamp(0.0, 1.0))
+    }
+
+    /// The confidence as a number in the range of `0.0` to `1.0`.
+    pub const fn value(self) -> f32 {
+        self.0
+    }
+}
+
+impl fmt::Display for Confidence {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(formatter, "{:.2}", self.0)
+    }
+}
+
+/// Terminal size in characters, `$COLUMNS` takes precedence if set.
 ///
 /// Returns `80usize` for tests and in case the terminal size can not be
 /// retrieved.
 pub fn get_terminal_size() -> usize {
     const DEFAULT_TERMINAL_SIZE: usize = 80;
     #[cfg(not(test))]
+    if let Some(columns) = std::env::var("COLUMNS")
+        .ok()
+        .and_then(|columns| columns.trim().parse::<usize>().ok())
+        .filter(|&columns| columns > 0)
+    {
+        return columns;
+    }
+    #[cfg(not(test))]
     match crossterm::terminal::size() {
         Ok((terminal_size, _)) => terminal_size as usize,
         Err(_) => {
@@ -91,6 +153,9 @@ impl fmt::Display for Detector {
     }
 }
 
+/// Sentences longer than this are truncated around the mistake when displayed.
+const MAX_SENTENCE_DISPLAY_LEN: usize = 100;
+
 /// For long lines, literal will be trimmed to display in one terminal line.
 /// Misspelled words that are too long shall also be ellipsized.
 pub fn condition_display_content(
@@ -295,6 +360,103 @@ pub fn condition_display_content(
     (conditioned_line, offset, marker_size)
 }
 
+/// Find the sentence containing `range` within `content`, in characters.
+///
+/// A sentence ends with `.`, `!` or `?`, followed by whitespace and an
+/// uppercase letter, or with an empty line. The range is extended in case the
+/// mistake itself crosses a sentence boundary.
+fn find_sentence(content: &str, range: Range) -> Range {
+    let chars = content.chars().collect::<Vec<char>>();
+    let skip_whitespace = |mut idx: usize| {
+        while idx < chars.len() && chars[idx].is_whitespace() {
+            idx += 1;
+        }
+        idx
+    };
+
+    let mut start = 0;
+    let mut idx = 0;
+    while idx < chars.len() {
+        let boundary = match chars[idx] {
+            '.' | '!' | '?' => {
+                let next_start = skip_whitespace(idx + 1);
+                if next_start > idx + 1
+                    && next_start < chars.len()
+                    && chars[next_start].is_uppercase()
+                {
+                    Some((idx + 1, next_start))
+                } else {
+                    None
+                }
+            }
+            '\n' => {
+                let next_line = chars[idx + 1..]
+                    .iter()
+                    .take_while(|c| **c != '\n' && c.is_whitespace())
+                    .count()
+                    + idx
+                    + 1;
+                if next_line < chars.len() && chars[next_line] == '\n' {
+                    Some((idx, skip_whitespace(next_line)))
+                } else {
+                    None
+                }
+            }
+            _ => None,
+        };
+        match boundary {
+            Some((end, next_start)) if range.start < next_start => {
+                return start..cmp::max(end, range.end);
+            }
+            Some((_, next_start)) => {
+                start = next_start;
+                idx = next_start;
+            }
+            None => idx += 1,
+        }
+    }
+    let end = chars
+        .iter()
+        .rposition(|c| !c.is_whitespace())
+        .map(|idx| idx + 1)
+        .unwrap_or_default();
+    start..cmp::min(cmp::max(end, range.end), chars.len())
+}
+
+/// Extract the sentence containing `range` as a single line, with line breaks
+/// and the indentation of continuation lines collapsed to a single space.
+///
+/// Returns the line and the range of the mistake within it, in characters.
+fn sentence_excerpt(content: &str, range: Range) -> (String, Range) {
+    let sentence = find_sentence(content, range.clone());
+
+    let mut excerpt = String::with_capacity(sentence.len());
+    let mut mistake = (range.start - sentence.start)..(range.end - sentence.start);
+    let mut after_newline = false;
+    for (idx, c) in content
+        .chars()
+        .enumerate()
+        .skip(sentence.start)
+        .take(sentence.len())
+    {
+        if c == '\n' {
+            excerpt.push(' ');
+            after_newline = true;
+        } else if after_newline && c.is_whitespace() {
+            if idx < range.start {
+                mistake.start -= 1;
+            }
+            if idx < range.end {
+                mistake.end -= 1;
+            }
+        } else {
+            after_newline = false;
+            excerpt.push(c);
+        }
+    }
+    (excerpt, mistake)
+}
+
 /// A suggestion for certain offending span.
 #[derive(Clone, Hash, PartialEq, Eq)]
 pub struct Suggestion<'s> {
@@ -313,14 +475,383 @@ pub struct Suggestion<'s> {
     pub replacements: Vec<String>,
     /// Descriptive reason for the suggestion.
     pub description: Option<String>,
+    /// Whether the suggestion is a mistake or only a warning.
+    pub severity: Severity,
+}
+
+impl<'s> Suggestion<'s> {
+    /// The content covered by the suggestion, i.e. the misspelled word.
+    pub fn word(&self) -> String {
+        sub_chars(self.chunk.as_str(), self.range.clone())
+    }
+
+    /// The confidence of each of the `replacements`, in the same order, where
+    /// `1.0` means identical to the misspelled word.
+    pub fn confidences(&self) -> Vec<Confidence> {
+        let word = self.word();
+        self.replacements
+            .iter()
+            .map(|replacement| replacement_confidence(word.as_str(), replacement.as_str()))
+            .collect()
+    }
+
+    /// The highest confidence of all `replacements`, `0.0` if there are none.
+    pub fn best_confidence(&self) -> Confidence {
+        self.confidences()
+            .into_iter()
+            .fold(Confidence::default(), |best, confidence| {
+                if confidence > best {
+                    confidence
+                } else {
+                    best
+                }
+            })
+    }
 }
 
 impl<'s> fmt::Display for Suggestion<'s> {
     fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.fmt_limited(formatter, None, get_terminal_size(), false)
+    }
+}
+
+/// A display wrapper for a suggestion, which limits the number of shown
+/// replacements.
+///
+/// Only the replacements with the highest confidence are shown.
+#[derive(Debug, Clone)]
+pub struct SuggestionDisplay<'a, 's> {
+    suggestion: &'a Suggestion<'s>,
+    max_replacements: usize,
+    display_width: usize,
+    show_confidence: bool,
+}
+
+impl<'a, 's> SuggestionDisplay<'a, 's> {
+    /// Show the confidence next to each replacement, i.e. when running
+    /// verbose.
+    pub fn with_confidence(mut self, show_confidence: bool) -> Self {
+        self.show_confidence = show_confidence;
+        self
+    }
+}
+
+impl<'a, 's> fmt::Display for SuggestionDisplay<'a, 's> {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.suggestion.fmt_limited(
+            formatter,
+            Some(self.max_replacements),
+            self.display_width,
+            self.show_confidence,
+        )
+    }
+}
+
+/// A display wrapper for a suggestion, which shows it on a single line like
+/// `src/lib.rs:42:7: frobulate (frobnicate, frob)`.
+///
+/// Paths are relative to the current working directory where possible, the
+/// format is understood by vim's `errorformat` and emacs' `compilation-mode`.
+#[derive(Debug, Clone)]
+pub struct CompactDisplay<'a, 's> {
+    suggestion: &'a Suggestion<'s>,
+    max_replacements: usize,
+}
+
+impl<'a, 's> fmt::Display for CompactDisplay<'a, 's> {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let suggestion = self.suggestion;
+        let (path, line, column) = suggestion.location();
+
+        let (shown, _hidden) = suggestion.ranked_replacements(Some(self.max_replacements));
+        let replacements = if shown.is_empty() {
+            "no suggestions".to_owned()
+        } else {
+            shown
+                .into_iter()
+                .map(|idx| suggestion.replacements[idx].as_str())
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+        let severity = match suggestion.severity {
+            Severity::Error => "",
+            Severity::Warning => "warning: ",
+        };
+        write!(
+            formatter,
+            "{}:{}:{}: {}{} ({})",
+            path.display(),
+            line,
+            column,
+            severity,
+            suggestion.word(),
+            replacements
+        )
+    }
+}
+
+impl<'s> Suggestion<'s> {
+    /// Display the suggestion with at most `max_replacements` replacements,
+    /// the context is truncated to fit into `display_width` columns.
+    pub fn display_limited<'a>(
+        &'a self,
+        max_replacements: usize,
+        display_width: usize,
+    ) -> SuggestionDisplay<'a, 's> {
+        SuggestionDisplay {
+            suggestion: self,
+            max_replacements,
+            display_width,
+            show_confidence: false,
+        }
+    }
+
+    /// The indices of the replacements to show, and the number of hidden
+    /// replacements.
+    ///
+    /// Without a limit, all replacements are shown in the order provided by
+    /// the checker. Otherwise those with the highest confidence are kept, the
+    /// sort is stable so the order provided by the checker is retained for
+    /// equal confidences.
+    fn ranked_replacements(&self, max_replacements: Option<usize>) -> (Vec<usize>, usize) {
+        let n = self.replacements.len();
+        match max_replacements {
+            Some(max_replacements) if n > max_replacements => {
+                let confidences = self.confidences();
+                let mut ranked = (0..n).collect::<Vec<_>>();
+                ranked.sort_by(|a, b| {
+                    confidences[*b]
+                        .partial_cmp(&confidences[*a])
+                        .unwrap_or(cmp::Ordering::Equal)
+                });
+                ranked.truncate(max_replacements);
+                (ranked, n - max_replacements)
+            }
+            _ => ((0..n).collect(), 0),
+        }
+    }
+
+    /// The path relative to the current working directory, if possible, and
+    /// the 1-indexed line and column of the start of the suggestion.
+    fn location(&self) -> (PathBuf, usize, usize) {
+        let (path, line) = match self.origin {
+            ContentOrigin::RustDocTest(ref path, ref span) => {
+                (path.as_path(), self.span.start.line + span.start.line)
+            }
+            ref origin => (origin.as_path(), self.span.start.line),
+        };
+        (
+            crate::util::relative_to_cwd(path),
+            line,
+            self.span.start.column + 1,
+        )
+    }
+
+    /// A JSON object describing the suggestion, with at most
+    /// `max_replacements` replacements.
+    pub fn to_json(&self, max_replacements: usize) -> serde_json::Value {
+        let (path, line, column) = self.location();
+        let (shown, _hidden) = self.ranked_replacements(Some(max_replacements));
+        serde_json::json!({
+            "path": path,
+            "line": line,
+            "column": column,
+            "word": self.word(),
+            "detector": self.detector.to_string(),
+            "replacements": shown
+                .into_iter()
+                .map(|idx| self.replacements[idx].as_str())
+                .collect::<Vec<_>>(),
+            "description": self.description,
+            "severity": self.severity.as_str(),
+        })
+    }
+
+    /// A one sentence message naming the word and at most `max_replacements`
+    /// replacements.
+    fn message(&self, max_replacements: usize) -> String {
+        let (shown, _hidden) = self.ranked_replacements(Some(max_replacements));
+        if shown.is_empty() {
+            format!("Unknown word `{}`", self.word())
+        } else {
+            format!(
+                "Unknown word `{}`, did you mean {}?",
+                self.word(),
+                shown
+                    .into_iter()
+                    .map(|idx| self.replacements[idx].as_str())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )
+        }
+    }
+
+    /// A GitLab code quality issue of the suggestion, with at most
+    /// `max_replacements` replacements in the description.
+    ///
+    /// The fingerprint only depends on the path, line and word, and is
+    /// hashed with FNV-1a, so it is stable across runs and toolchains.
+    pub fn to_gitlab_code_quality(&self, max_replacements: usize) -> serde_json::Value {
+        use crate::util::{fnv1a as feed, FNV_OFFSET_BASIS};
+
+        let (path, line, _column) = self.location();
+        let fingerprint = feed(
+            feed(
+                feed(
+                    FNV_OFFSET_BASIS,
+                    path.to_string_lossy().replace('\\', "/").as_bytes(),
+                ),
+                &(line as u64).to_le_bytes(),
+            ),
+            self.word().as_bytes(),
+        );
+        serde_json::json!({
+            "description": self.message(max_replacements),
+            "check_name": format!("spellcheck({})", self.detector),
+            "fingerprint": format!("{:016x}", fingerprint),
+            "severity": match self.severity {
+                Severity::Error => "minor",
+                Severity::Warning => "info",
+            },
+            "location": {
+                "path": path,
+                "lines": {
+                    "begin": line,
+                },
+            },
+        })
+    }
+
+    /// An Azure DevOps `task.logissue` logging command of the suggestion, with
+    /// at most `max_replacements` replacements in the message.
+    pub fn to_azure_devops(&self, max_replacements: usize) -> String {
+        fn escape(s: &str, property: bool) -> String {
+            let s = s
+                .replace('%', "%AZP25")
+                .replace('\r', "%0D")
+                .replace('\n', "%0A");
+            if property {
+                s.replace(';', "%3B").replace(']', "%5D")
+            } else {
+                s
+            }
+        }
+        let (path, line, column) = self.location();
+        format!(
+            "##vso[task.logissue type={};sourcepath={};linenumber={};columnnumber={}]{}",
+            self.severity.as_str(),
+            escape(&path.display().to_string(), true),
+            line,
+            column,
+            escape(&self.message(max_replacements), false)
+        )
+    }
+
+    /// A Checkstyle `<error>` element of the suggestion, with at most
+    /// `max_replacements` replacements in the message.
+    pub fn to_checkstyle(&self, max_replacements: usize) -> String {
+        let (_path, line, column) = self.location();
+        let severity = match self.severity {
+            Severity::Error => "warning",
+            Severity::Warning => "info",
+        };
+        format!(
+            r#"<error line="{}" column="{}" severity="{}" message="{}" source="cargo-spellcheck"/>"#,
+            line,
+            column,
+            severity,
+            crate::util::xml_escape(&self.message(max_replacements))
+        )
+    }
+
+    /// A reviewdog diagnostic of the suggestion, with at most
+    /// `max_replacements` replacements as suggestions.
+    ///
+    /// The range is 1-indexed with an exclusive end. As reviewdog expects, the
+    /// columns are counted in UTF-8 bytes, which requires the `source` content
+    /// of the file the suggestion relates to. Without it, or for doc tests
+    /// whose spans are relative to the extracted code, the columns are counted
+    /// in characters.
+    pub fn to_rdjson(&self, max_replacements: usize, source: Option<&str>) -> serde_json::Value {
+        let (path, line, _column) = self.location();
+        let end_line = line + self.span.end.line - self.span.start.line;
+        let source = match self.origin {
+            ContentOrigin::RustDocTest(..) => None,
+            _ => source,
+        };
+        let byte_column = |line: usize, column: usize| {
+            source
+                .and_then(|source| source.lines().nth(line.saturating_sub(1)))
+                .map(|source_line| {
+                    source_line
+                        .char_indices()
+                        .nth(column)
+                        .map(|(offset, _c)| offset)
+                        .unwrap_or(source_line.len())
+                })
+                .unwrap_or(column)
+                + 1
+        };
+        let range = serde_json::json!({
+            "start": {
+                "line": line,
+                "column": byte_column(self.span.start.line, self.span.start.column),
+            },
+            "end": {
+                "line": end_line,
+                "column": byte_column(self.span.end.line, self.span.end.column + 1),
+            },
+        });
+        let (shown, _hidden) = self.ranked_replacements(Some(max_replacements));
+        serde_json::json!({
+            "message": self.message(max_replacements),
+            "location": {
+                "path": path,
+                "range": range,
+            },
+            "severity": match self.severity {
+                Severity::Error => "WARNING",
+                Severity::Warning => "INFO",
+            },
+            "source": {
+                "name": "cargo-spellcheck",
+            },
+            "code": {
+                "value": self.detector.to_string(),
+            },
+            "suggestions": shown
+                .into_iter()
+                .map(|idx| serde_json::json!({
+                    "range": range,
+                    "text": self.replacements[idx],
+                }))
+                .collect::<Vec<_>>(),
+        })
+    }
+
+    /// Display the suggestion on a single line, with at most
+    /// `max_replacements` replacements.
+    pub fn display_compact<'a>(&'a self, max_replacements: usize) -> CompactDisplay<'a, 's> {
+        CompactDisplay {
+            suggestion: self,
+            max_replacements,
+        }
+    }
+
+    fn fmt_limited(
+        &self,
+        formatter: &mut fmt::Formatter<'_>,
+        max_replacements: Option<usize>,
+        display_width: usize,
+        show_confidence: bool,
+    ) -> fmt::Result {
         use console::Style;
 
         let highlight = Style::new().bold().white();
-        let error = Style::new().bold().red();
+        let severity = match self.severity {
+            Severity::Error => Style::new().bold().red(),
+            Severity::Warning => Style::new().bold().yellow(),
+        };
         let arrow_marker = Style::new().blue();
         let context_marker = Style::new().bold().blue();
         let fix = Style::new().green();
@@ -329,7 +860,7 @@ impl<'s> fmt::Display for Suggestion<'s> {
         let line_number_digit_count = self.span.start.line.to_string().len();
         let indent = 3 + line_number_digit_count;
 
-        error.apply_to("error").fmt(formatter)?;
+        severity.apply_to(self.severity.as_str()).fmt(formatter)?;
         highlight
             .apply_to(format!(": spellcheck({})", &self.detector))
             .fmt(formatter)?;
@@ -339,14 +870,13 @@ impl<'s> fmt::Display for Suggestion<'s> {
             .apply_to(format!("{:>width$}", "-->", width = indent + 1))
             .fmt(formatter)?;
 
-        let x = self.span.start.line;
-        let (path, line) = match self.origin {
-            ContentOrigin::RustDocTest(ref path, ref span) => {
-                (path.display().to_string(), x + span.start.line)
-            }
-            ref origin => (origin.as_path().display().to_string(), x),
-        };
-        writeln!(formatter, " {path}:{line}", path = path, line = line)?;
+        let (path, line, _column) = self.location();
+        writeln!(
+            formatter,
+            " {path}:{line}",
+            path = path.display(),
+            line = line
+        )?;
         context_marker
             .apply_to(format!("{:>width$}", "|", width = indent))
             .fmt(formatter)?;
@@ -368,39 +898,21 @@ impl<'s> fmt::Display for Suggestion<'s> {
                 .saturating_sub(self.span.start.column)
         });
 
-        // assumes the _mistake_ is within one line
-        // if not we chop it down to the first line
-        let mistake_lines = self.chunk.find_covered_lines(self.range.clone());
-        let (line_range, start_of_line_offset) = mistake_lines
-            .first()
-            .map(|line_range| {
-                (
-                    line_range,
-                    self.range.start.saturating_sub(line_range.start),
-                )
-            })
-            .expect("Lines covered must exist");
-
-        let intra_line_mistake_range = Range {
-            start: start_of_line_offset,
-            end: cmp::min(start_of_line_offset + self.range.len(), line_range.len()),
-        };
-        let relevant_line = self
-            .chunk
-            .as_str()
-            .chars()
-            .enumerate()
-            .skip_while(|(idx, _)| line_range.start > *idx)
-            .take(line_range.len())
-            .map(|(_, c)| c)
-            .collect::<String>();
-
-        let terminal_size = get_terminal_size();
+        // show the full sentence containing the mistake, which reads a lot
+        // better than a fixed window for flowing prose
+        let (relevant_line, intra_line_mistake_range) =
+            sentence_excerpt(self.chunk.as_str(), self.range.clone());
 
         // this values is dynamically calculated for each line where the doc is.
         // the line being analysed can affect how the indentation is done.
         let padding_till_excerpt_start = indent + 2;
 
+        // overly long sentences are truncated around the mistake
+        let terminal_size = cmp::min(
+            display_width,
+            padding_till_excerpt_start + MAX_SENTENCE_DISPLAY_LEN,
+        );
+
         let (formatted, offset, marker_size) = condition_display_content(
             terminal_size,
             indent,
@@ -430,10 +942,9 @@ impl<'s> fmt::Display for Suggestion<'s> {
             );
         } else {
             log::warn!(
-                "marker_size={} span {{ {:?} .. {:?} }} >> {:?} <<",
+                "marker_size={} span {} >> {:?} <<",
                 marker_size,
-                self.span.start,
-                self.span.end,
+                self.span,
                 self,
             );
         }
@@ -442,17 +953,39 @@ impl<'s> fmt::Display for Suggestion<'s> {
             .apply_to(format!("{:>width$}", "|", width = indent))
             .fmt(formatter)?;
 
-        let replacement = match self.replacements.len() {
+        let confidences = self.confidences();
+        let (shown, hidden) = self.ranked_replacements(max_replacements);
+        let replacements = shown
+            .into_iter()
+            .map(|idx| {
+                if show_confidence {
+                    format!("{} ({:.2})", self.replacements[idx], confidences[idx])
+                } else {
+                    self.replacements[idx].clone()
+                }
+            })
+            .collect::<Vec<_>>();
+
+        let replacement = match replacements.len() {
             0 => String::new(),
-            1 => format!(" - {}", fix.apply_to(&self.replacements[0])),
+            n if hidden > 0 => {
+                let joined = replacements[..n]
+                    .iter()
+                    .map(|x| fix.apply_to(x.to_owned()).to_string())
+                    .collect::<Vec<String>>()
+                    .as_slice()
+                    .join(", ");
+                format!(" - {} (and {} more…)", joined, hidden)
+            }
+            1 => format!(" - {}", fix.apply_to(&replacements[0])),
             2 => format!(
                 " - {} or {}",
-                fix.apply_to(&self.replacements[0]).to_string(),
-                fix.apply_to(&self.replacements[1]).to_string()
+                fix.apply_to(&replacements[0]).to_string(),
+                fix.apply_to(&replacements[1]).to_string()
             ),
             n if (n < 7) => {
-                let last = fix.apply_to(&self.replacements[n - 1]).to_string();
-                let joined = self.replacements[..n - 1]
+                let last = fix.apply_to(&replacements[n - 1]).to_string();
+                let joined = replacements[..n - 1]
                     .iter()
                     .map(|x| fix.apply_to(x.to_owned()).to_string())
                     .collect::<Vec<String>>()
@@ -461,22 +994,22 @@ impl<'s> fmt::Display for Suggestion<'s> {
                 format!(" - {}, or {}", joined, last)
             }
             _n => {
-                let joined = self.replacements[..=6]
+                let joined = replacements[..=6]
                     .iter()
                     .map(|x| fix.apply_to(x.to_owned()).to_string())
                     .collect::<Vec<String>>()
                     .as_slice()
                     .join(", ");
 
-                let remaining = self.replacements.len() - 6;
+                let remaining = replacements.len() - 6;
                 let remaining = fix.apply_to(format!("{}", remaining)).to_string();
                 format!(" - {}, or one of {} others", joined, remaining)
             }
         };
 
-        error.apply_to(replacement).fmt(formatter)?;
+        severity.apply_to(replacement).fmt(formatter)?;
 
-        if !self.replacements.is_empty() {
+        if !replacements.is_empty() {
             formatter.write_str("\n")?;
             context_marker
                 .apply_to(format!("{:>width$}", "|\n", width = indent + 1))
@@ -683,6 +1216,48 @@ impl<'s> IntoIterator for &'s SuggestionSet<'s> {
     }
 }
 
+/// Test fixture, the single line chunk ` Is it dyrck again?`.
+#[cfg(test)]
+pub(crate) fn dyrck_chunk() -> CheckableChunk {
+    use crate::{CommentVariant, LineColumn};
+
+    CheckableChunk::from_str(
+        " Is it dyrck again?",
+        indexmap::indexmap! { 0..18 => Span {
+                start: LineColumn { line: 1, column: 0 },
+                end: LineColumn { line: 1, column: 17 },
+            }
+        },
+        CommentVariant::TripleSlash,
+    )
+}
+
+/// Test fixture, an error for `dyrck` within [`dyrck_chunk`].
+#[cfg(test)]
+pub(crate) fn dyrck_suggestion<'s>(
+    chunk: &'s CheckableChunk,
+    replacements: &[&str],
+) -> Suggestion<'s> {
+    use crate::LineColumn;
+
+    Suggestion {
+        detector: Detector::Dummy,
+        origin: ContentOrigin::TestEntityRust,
+        chunk,
+        range: 7..12,
+        span: Span {
+            start: LineColumn { line: 1, column: 6 },
+            end: LineColumn {
+                line: 1,
+                column: 10,
+            },
+        },
+        replacements: replacements.iter().map(|r| r.to_string()).collect(),
+        description: None,
+        severity: Severity::Error,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -706,45 +1281,16 @@ mod tests {
 
     #[test]
     fn fmt_0_single() {
-        const CONTENT: &str = " Is it dyrck again?";
-        let chunk = CheckableChunk::from_str(
-            CONTENT,
-            indexmap::indexmap! { 0..18 => Span {
-                    start: LineColumn {
-                        line: 1,
-                        column: 0,
-                    },
-                    end: LineColumn {
-                        line: 1,
-                        column: 17,
-                    }
-                }
-            },
-            CommentVariant::TripleSlash,
-        );
-
-        let suggestion = Suggestion {
-            detector: Detector::Dummy,
-            origin: ContentOrigin::TestEntityRust,
-            chunk: &chunk,
-            range: 7..12,
-            span: Span {
-                start: LineColumn { line: 1, column: 6 },
-                end: LineColumn {
-                    line: 1,
-                    column: 10,
-                },
-            },
-            replacements: vec![
-                "replacement_0".to_owned(),
-                "replacement_1".to_owned(),
-                "replacement_2".to_owned(),
-            ],
-            description: Some("Possible spelling mistake found.".to_owned()),
-        };
+        let chunk = dyrck_chunk();
+        let mut suggestion =
+            dyrck_suggestion(&chunk, &["replacement_0", "replacement_1", "replacement_2"]);
+        suggestion.description = Some("Possible spelling mistake found.".to_owned());
+        // shown relative to the current working directory
+        suggestion.origin =
+            ContentOrigin::RustSourceFile(std::env::current_dir().unwrap().join("src/entity.rs"));
 
         const EXPECTED: &str = r#"error: spellcheck(Dummy)
-  --> /tmp/test/entity.rs:1
+  --> src/entity.rs:1
    |
  1 |  Is it dyrck again?
    |        ^^^^^
@@ -756,40 +1302,178 @@ mod tests {
     }
 
     #[test]
-    fn fmt_0_no_suggestion() {
-        const CONTENT: &str = " Is it dyrck again?";
+    fn gitlab_code_quality() {
+        let chunk = dyrck_chunk();
+        let suggestion = dyrck_suggestion(&chunk, &["dark", "dyrk"]);
+
+        let issue = suggestion.to_gitlab_code_quality(5);
+        assert_eq!(
+            issue["description"],
+            "Unknown word `dyrck`, did you mean dark, dyrk?"
+        );
+        assert_eq!(issue["severity"], "minor");
+        assert_eq!(issue["location"]["path"], "/tmp/test/entity.rs");
+        assert_eq!(issue["location"]["lines"]["begin"], 1);
+        assert_eq!(issue["fingerprint"], "a3bab0e962b71e77");
+        assert_eq!(
+            issue["fingerprint"],
+            suggestion.to_gitlab_code_quality(1)["fingerprint"]
+        );
+    }
+
+    #[test]
+    fn azure_devops() {
+        let chunk = dyrck_chunk();
+        let suggestion = dyrck_suggestion(&chunk, &["dark"]);
+
+        assert_eq!(
+            suggestion.to_azure_devops(5),
+            "##vso[task.logissue type=error;sourcepath=/tmp/test/entity.rs;linenumber=1;columnnumber=7]Unknown word `dyrck`, did you mean dark?"
+        );
+    }
+
+    #[test]
+    fn checkstyle() {
+        let chunk = dyrck_chunk();
+        let suggestion = dyrck_suggestion(&chunk, &["dark", "\"dry\""]);
+
+        assert_eq!(
+            suggestion.to_checkstyle(5),
+            r#"<error line="1" column="7" severity="warning" message="Unknown word `dyrck`, did you mean dark, &quot;dry&quot;?" source="cargo-spellcheck"/>"#
+        );
+    }
+
+    #[test]
+    fn rdjson() {
+        let chunk = dyrck_chunk();
+        let suggestion = dyrck_suggestion(&chunk, &["dark", "dry"]);
+
+        let range = serde_json::json!({
+            "start": { "line": 1, "column": 7 },
+            "end": { "line": 1, "column": 12 },
+        });
+        assert_eq!(
+            suggestion.to_rdjson(1, None),
+            serde_json::json!({
+                "message": "Unknown word `dyrck`, did you mean dark?",
+                "location": {
+                    "path": "/tmp/test/entity.rs",
+                    "range": range,
+                },
+                "severity": "WARNING",
+                "source": { "name": "cargo-spellcheck" },
+                "code": { "value": "Dummy" },
+                "suggestions": [{ "range": range, "text": "dark" }],
+            })
+        );
+    }
+
+    #[test]
+    fn rdjson_byte_columns() {
+        use crate::{CommentVariant, LineColumn};
+
+        let source = "/// Über dyrck";
         let chunk = CheckableChunk::from_str(
-            CONTENT,
-            indexmap::indexmap! { 0..18 => Span {
-                    start: LineColumn {
-                        line: 1,
-                        column: 0,
-                    },
-                    end: LineColumn {
-                        line: 1,
-                        column: 17,
-                    }
+            " Über dyrck",
+            indexmap::indexmap! { 0..11 => Span {
+                    start: LineColumn { line: 1, column: 3 },
+                    end: LineColumn { line: 1, column: 13 },
                 }
             },
             CommentVariant::TripleSlash,
         );
-
-        let suggestion = Suggestion {
-            detector: Detector::Dummy,
-            origin: ContentOrigin::TestEntityRust,
-            chunk: &chunk,
-            range: 7..12,
-            span: Span {
-                start: LineColumn { line: 1, column: 6 },
-                end: LineColumn {
-                    line: 1,
-                    column: 10,
-                },
+        let mut suggestion = dyrck_suggestion(&chunk, &["dark"]);
+        suggestion.range = 6..11;
+        suggestion.span = Span {
+            start: LineColumn { line: 1, column: 9 },
+            end: LineColumn {
+                line: 1,
+                column: 13,
             },
-            replacements: vec![],
-            description: Some("Possible spelling mistake found.".to_owned()),
         };
 
+        // `Ü` takes two bytes, shifting the columns by one
+        let range = &suggestion.to_rdjson(1, Some(source))["location"]["range"];
+        assert_eq!(
+            range,
+            &serde_json::json!({
+                "start": { "line": 1, "column": 11 },
+                "end": { "line": 1, "column": 16 },
+            })
+        );
+        // character based without the source
+        let range = &suggestion.to_rdjson(1, None)["location"]["range"];
+        assert_eq!(
+            range,
+            &serde_json::json!({
+                "start": { "line": 1, "column": 10 },
+                "end": { "line": 1, "column": 15 },
+            })
+        );
+    }
+
+    #[test]
+    fn fmt_compact() {
+        let chunk = dyrck_chunk();
+        let mut suggestion = dyrck_suggestion(&chunk, &["dark", "truck", "dyrk"]);
+        suggestion.description = Some("Possible spelling mistake found.".to_owned());
+
+        assert_eq!(
+            suggestion.display_compact(2).to_string(),
+            "/tmp/test/entity.rs:1:7: dyrck (dyrk, dark)"
+        );
+        suggestion.replacements.clear();
+        assert_eq!(
+            suggestion.display_compact(2).to_string(),
+            "/tmp/test/entity.rs:1:7: dyrck (no suggestions)"
+        );
+        suggestion.severity = Severity::Warning;
+        assert_eq!(
+            suggestion.display_compact(2).to_string(),
+            "/tmp/test/entity.rs:1:7: warning: dyrck (no suggestions)"
+        );
+        assert_eq!(suggestion.best_confidence(), Confidence::default());
+    }
+
+    #[test]
+    fn fmt_0_limited() {
+        let chunk = dyrck_chunk();
+        let mut suggestion = dyrck_suggestion(&chunk, &["dark", "truck", "dyrk"]);
+        suggestion.description = Some("Possible spelling mistake found.".to_owned());
+
+        const EXPECTED: &str = r#"error: spellcheck(Dummy)
+  --> /tmp/test/entity.rs:1
+   |
+ 1 |  Is it dyrck again?
+   |        ^^^^^
+   | - dyrk, dark (and 1 more…)
+   |
+   |   Possible spelling mistake found.
+"#;
+        assert_display_eq(suggestion.display_limited(2, 80), EXPECTED);
+
+        let verbose = suggestion
+            .display_limited(2, 80)
+            .with_confidence(true)
+            .to_string();
+        assert!(console::strip_ansi_codes(verbose.as_str()).contains("dyrk (0.80), dark (0.60)"));
+
+        const EXPECTED_NONE: &str = r#"error: spellcheck(Dummy)
+  --> /tmp/test/entity.rs:1
+   |
+ 1 |  Is it dyrck again?
+   |        ^^^^^
+   |   Possible spelling mistake found.
+"#;
+        assert_display_eq(suggestion.display_limited(0, 80), EXPECTED_NONE);
+    }
+
+    #[test]
+    fn fmt_0_no_suggestion() {
+        let chunk = dyrck_chunk();
+        let mut suggestion = dyrck_suggestion(&chunk, &[]);
+        suggestion.description = Some("Possible spelling mistake found.".to_owned());
+
         const EXPECTED: &str = r#"error: spellcheck(Dummy)
   --> /tmp/test/entity.rs:1
    |
@@ -864,12 +1548,13 @@ mod tests {
                 "replacement_2".to_owned(),
             ],
             description: Some("Possible spelling mistake found.".to_owned()),
+            severity: Severity::Error,
         };
 
         const EXPECTED: &str = r#"error: spellcheck(Dummy)
   --> /tmp/test/entity.rs:1
    |
- 1 |  Line mitake 1
+ 1 |  Line mitake 1 Anowher 2 Last
    |       ^^^^^^
    | - replacement_0, replacement_1, or replacement_2
    |
@@ -930,6 +1615,7 @@ mod tests {
                 "replacement_2".to_owned(),
             ],
             description: Some("Possible spelling mistake found.".to_owned()),
+            severity: Severity::Error,
         };
 
         const EXPECTED: &str = r#"error: spellcheck(Dummy)
@@ -942,9 +1628,44 @@ mod tests {
    |   Possible spelling mistake found.
 "#;
 
+        // the excerpt and the marker line fit into narrow displays as well
+        let narrow = suggestion.display_limited(3, 40).to_string();
+        let narrow = console::strip_ansi_codes(narrow.as_str());
+        let lines = narrow.lines().collect::<Vec<_>>();
+        assert!(lines[3].starts_with(" 2 | .."));
+        assert!(lines[3].chars().count() <= 40);
+        assert!(lines[4].ends_with("^^^^^^^^^^^"));
+        assert!(lines[4].chars().count() <= 40);
+
         assert_display_eq(suggestion, EXPECTED);
     }
 
+    #[test]
+    fn sentence_boundaries() {
+        const CONTENT: &str = " First one. The secnd one is
+ wrapped over lines! Third?
+
+ New paragraph";
+        assert_eq!(find_sentence(CONTENT, 2..7), 0..11);
+        assert_eq!(find_sentence(CONTENT, 16..21), 12..49);
+        assert_eq!(find_sentence(CONTENT, 50..55), 50..56);
+        assert_eq!(find_sentence(CONTENT, 59..62), 59..72);
+        // no uppercase letter, no boundary
+        assert_eq!(find_sentence("e.g. this", 5..9), 0..9);
+
+        let (excerpt, mistake) = sentence_excerpt(CONTENT, 43..48);
+        assert_eq!(excerpt, "The secnd one is wrapped over lines!");
+        assert_eq!(mistake, 30..35);
+        assert_eq!(
+            excerpt
+                .chars()
+                .skip(mistake.start)
+                .take(mistake.len())
+                .collect::<String>(),
+            "lines"
+        );
+    }
+
     #[test]
     fn multiline_is_dbg_printable() {
         let _ = env_logger::builder()
@@ -987,6 +1708,7 @@ mod tests {
             range: 2..6,
             replacements: vec!["whocares".to_owned()],
             description: None,
+            severity: Severity::Error,
         };
 
         let suggestion = dbg!(suggestion);
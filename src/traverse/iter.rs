@@ -1,5 +1,4 @@
 use super::*;
-use crate::Documentation;
 
 use fs_err as fs;
 
@@ -86,7 +85,6 @@ impl TraverseModulesIter {
     }
 
     /// Create a new path with (almost) infinite depth bounds
-    #[allow(unused)]
     pub fn new<P: AsRef<Path>>(path: P) -> Result<Self> {
         Self::with_depth_limit(path, usize::MAX)
     }
@@ -122,38 +120,6 @@ impl Iterator for TraverseModulesIter {
     }
 }
 
-/// traverse path with a depth limit, if the path is a directory all its
-/// children will be collected instead
-// TODO should not read the documentation, that is out of scope.
-// TODO should not have knowledge of `dev_comments`.
-pub(crate) fn traverse(
-    path: &Path,
-    dev_comments: bool,
-) -> Result<impl Iterator<Item = Documentation>> {
-    traverse_with_depth_limit(path, usize::MAX, dev_comments)
-}
-
-/// traverse path with a depth limit, if the path is a directory all its
-/// children will be collected as depth 0 instead
-pub(crate) fn traverse_with_depth_limit(
-    path: &Path,
-    max_depth: usize,
-    dev_comments: bool,
-) -> Result<impl Iterator<Item = Documentation>> {
-    let it = TraverseModulesIter::with_depth_limit(path, max_depth)?
-        .filter_map(move |path: PathBuf| -> Option<Documentation> {
-            fs::read_to_string(&path).ok().map(|content| {
-                Documentation::load_from_str(
-                    ContentOrigin::RustSourceFile(path),
-                    content.as_str(),
-                    dev_comments,
-                )
-            })
-        })
-        .filter(|documentation| !documentation.is_empty());
-    Ok(it)
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
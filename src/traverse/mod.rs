@@ -4,12 +4,14 @@
 //! `Origin`.
 
 use super::*;
+use crate::documentation::tokenize_cached;
 use crate::Documentation;
 
 use crate::errors::*;
 use log::{debug, trace, warn};
 
 use fs_err as fs;
+use globset::{Glob, GlobSet, GlobSetBuilder};
 use std::collections::HashSet;
 use std::path::{Path, PathBuf};
 
@@ -17,6 +19,19 @@ pub(crate) fn cwd() -> Result<PathBuf> {
     std::env::current_dir().wrap_err_with(|| eyre!("Missing cwd!"))
 }
 
+/// Find the root of the cargo workspace `dir` belongs to, which is the closest
+/// ancestor with a manifest declaring a `[workspace]`, like `cargo` does.
+pub(crate) fn workspace_root(dir: &Path) -> Option<PathBuf> {
+    dir.ancestors()
+        .find(|ancestor| {
+            fs::read_to_string(ancestor.join("Cargo.toml"))
+                .ok()
+                .and_then(|content| content.parse::<toml::Value>().ok())
+                .map_or(false, |manifest| manifest.get("workspace").is_some())
+        })
+        .map(Path::to_path_buf)
+}
+
 #[cfg(test)]
 fn manifest_dir() -> PathBuf {
     PathBuf::from(env!("CARGO_MANIFEST_DIR"))
@@ -158,7 +173,7 @@ pub(crate) fn extract_modules_from_file<P: AsRef<Path>>(path: P) -> Result<HashS
     let path: &Path = path.as_ref();
     if let Some(path_str) = path.to_str() {
         let s = fs::read_to_string(path_str)?;
-        let stream = syn::parse_str::<proc_macro2::TokenStream>(s.as_str())
+        let stream = tokenize_cached(s.as_str())
             .wrap_err_with(|| eyre!("File {} has syntax errors", path_str))?;
         let acc = extract_modules_recurse(path.to_owned(), stream)?;
         log::debug!(
@@ -186,6 +201,7 @@ pub enum CheckEntity {
     Markdown(PathBuf),
     Source(PathBuf, bool), // recurse is the bool
     ManifestDescription(PathBuf, String),
+    CargoConfig(PathBuf),
 }
 
 impl CheckEntity {
@@ -195,6 +211,7 @@ impl CheckEntity {
             Self::Markdown(ref path) => path,
             Self::Source(ref path, _) => path,
             Self::ManifestDescription(ref path, _) => path,
+            Self::CargoConfig(ref path) => path,
         }
         .as_path()
     }
@@ -324,9 +341,42 @@ fn extract_description(
         }))
 }
 
+/// The cargo configuration files applying to the crate in `manifest_dir`, the
+/// ones of the crate itself and of every directory up to the workspace root,
+/// as well as the one of `$CARGO_HOME`, if present.
+///
+/// Like cargo, the legacy name `config` is preferred over `config.toml` and
+/// `$CARGO_HOME` defaults to `~/.cargo`.
+fn extract_cargo_configs(manifest_dir: &Path) -> Vec<CheckEntity> {
+    let root = workspace_root(manifest_dir);
+    let ancestors = manifest_dir.ancestors().take_while(|dir| match root {
+        Some(ref root) => dir.starts_with(root),
+        None => *dir == manifest_dir,
+    });
+    let cargo_home = std::env::var_os("CARGO_HOME")
+        .map(PathBuf::from)
+        .or_else(|| directories::BaseDirs::new().map(|base| base.home_dir().join(".cargo")));
+    let mut dirs = ancestors.map(|dir| dir.join(".cargo")).collect::<Vec<_>>();
+    if let Some(cargo_home) = cargo_home {
+        if !dirs.contains(&cargo_home) {
+            dirs.push(cargo_home);
+        }
+    }
+    dirs.into_iter()
+        .filter_map(|dir| {
+            ["config", "config.toml"]
+                .iter()
+                .map(|name| dir.join(name))
+                .find(|path| path.is_file())
+        })
+        .map(CheckEntity::CargoConfig)
+        .collect()
+}
+
 fn handle_manifest<P: AsRef<Path>>(
     manifest_dir: P,
     skip_readme: bool,
+    dev_comments: bool,
 ) -> Result<HashSet<CheckEntity>> {
     let manifest_dir = to_manifest_dir(manifest_dir)?;
     trace!("📜 Handle manifest in dir: {}", manifest_dir.display());
@@ -356,6 +406,11 @@ fn handle_manifest<P: AsRef<Path>>(
         acc.extend(v);
     }
 
+    // comments only, just like developer comments in rust sources
+    if dev_comments {
+        acc.extend(extract_cargo_configs(manifest_dir));
+    }
+
     // TODO not quite ready for prime time
     if false {
         let v = extract_description(&manifest, &manifest_dir, &manifest_content).wrap_err_with(
@@ -420,15 +475,176 @@ fn handle_manifest<P: AsRef<Path>>(
     Ok(acc)
 }
 
+/// Matches discovered paths against the `exclude` glob patterns.
+#[derive(Debug, Clone)]
+pub(crate) struct Exclude {
+    patterns: Vec<String>,
+    set: GlobSet,
+    base: PathBuf,
+}
+
+impl Exclude {
+    /// Patterns are relative to `base`, unless they are absolute.
+    pub(crate) fn new(patterns: &[String], base: PathBuf) -> Result<Self> {
+        let mut builder = GlobSetBuilder::new();
+        for pattern in patterns {
+            builder.add(
+                Glob::new(pattern)
+                    .wrap_err_with(|| eyre!("Invalid exclude pattern `{}`", pattern))?,
+            );
+        }
+        Ok(Self {
+            patterns: patterns.to_vec(),
+            set: builder.build()?,
+            base,
+        })
+    }
+
+    /// The first pattern matching `path`, if any.
+    pub(crate) fn matching(&self, path: &Path) -> Option<&str> {
+        if self.patterns.is_empty() {
+            return None;
+        }
+        let relative = path.strip_prefix(&self.base).unwrap_or(path);
+        self.set
+            .matches(relative)
+            .into_iter()
+            .chain(self.set.matches(path))
+            .min()
+            .map(|idx| self.patterns[idx].as_str())
+    }
+}
+
+/// List the direct children of directory `dir`.
+///
+/// Unless `no_ignore` is set, entries matched by `.gitignore`, `.ignore` or
+/// `.git/info/exclude` files are skipped.
+fn read_dir_filtered(dir: &Path, no_ignore: bool) -> Result<Vec<PathBuf>> {
+    let walker = ignore::WalkBuilder::new(dir)
+        .max_depth(Some(1))
+        .standard_filters(!no_ignore)
+        .hidden(false)
+        .build();
+    let mut entries = Vec::with_capacity(32);
+    for entry in walker {
+        let entry = entry?;
+        // the directory itself
+        if entry.depth() == 0 {
+            continue;
+        }
+        entries.push(entry.into_path());
+    }
+    Ok(entries)
+}
+
+/// Identity of a directory, independent of the path it is reached by.
+#[cfg(unix)]
+type DirId = (u64, u64);
+#[cfg(not(unix))]
+type DirId = PathBuf;
+
+/// The identity of the directory at `path`, to detect directories which are
+/// reached again via symbolic links.
+#[cfg(unix)]
+fn dir_id(_path: &Path, meta: &std::fs::Metadata) -> Option<DirId> {
+    use std::os::unix::fs::MetadataExt;
+    Some((meta.dev(), meta.ino()))
+}
+
+/// The identity of the directory at `path`, to detect directories which are
+/// reached again via symbolic links.
+///
+/// The volume serial number and file index are not available on stable, but
+/// canonicalization resolves all links as well.
+#[cfg(not(unix))]
+fn dir_id(path: &Path, _meta: &std::fs::Metadata) -> Option<DirId> {
+    path.canonicalize().ok()
+}
+
+/// A symbolic link pointing to a directory.
+fn is_symlinked_dir(path: &Path) -> bool {
+    path.is_dir()
+        && path
+            .symlink_metadata()
+            .map(|meta| meta.file_type().is_symlink())
+            .unwrap_or(false)
+}
+
+/// A symbolic link pointing to a path that does not exist.
+fn is_broken_symlink(path: &Path) -> bool {
+    path.metadata().is_err()
+        && path
+            .symlink_metadata()
+            .map(|meta| meta.file_type().is_symlink())
+            .unwrap_or(false)
+}
+
+/// Number of leading bytes of a file which are looked at to detect binary
+/// content.
+const BINARY_DETECTION_LEN: usize = 8 * 1024;
+
+/// A file is considered binary if there is a null byte within its first
+/// `BINARY_DETECTION_LEN` bytes, just like `git` does it.
+fn is_binary(content: &[u8]) -> bool {
+    content
+        .iter()
+        .take(BINARY_DETECTION_LEN)
+        .any(|&byte| byte == 0)
+}
+
+/// Read a file found during discovery, `None` if it can not be read or has
+/// binary content, in which case it is recorded as skipped.
+fn read_discovered(docs: &mut Documentation, path: &Path) -> Option<String> {
+    let content = match fs::read(path) {
+        Ok(content) => content,
+        Err(_) if is_broken_symlink(path) => {
+            warn!("Skipping broken symbolic link {}", path.display());
+            docs.add_skipped(path.to_owned());
+            return None;
+        }
+        Err(err) => {
+            warn!("Skipping {}, failed to read: {}", path.display(), err);
+            docs.add_skipped(path.to_owned());
+            return None;
+        }
+    };
+    if is_binary(&content) {
+        warn!(
+            "Skipping {}, it has binary content, add it to `exclude` if it is not meant to be checked",
+            path.display()
+        );
+        docs.add_skipped(path.to_owned());
+        return None;
+    }
+    match String::from_utf8(content) {
+        Ok(content) => Some(content),
+        Err(err) => {
+            warn!("Skipping {}, failed to read: {}", path.display(), err);
+            docs.add_skipped(path.to_owned());
+            None
+        }
+    }
+}
+
 /// Extract all chunks from
 pub(crate) fn extract(
     mut paths: Vec<PathBuf>,
     mut recurse: bool,
     skip_readme: bool,
+    no_ignore: bool,
     dev_comments: bool,
-    _config: &Config,
+    string_literals: bool,
+    config: &Config,
 ) -> Result<Documentation> {
     let cwd = cwd()?;
+    let exclude = Exclude::new(&config.exclude, cwd.clone()).wrap_err(crate::ConfigurationError)?;
+    let options = ExtractOptions {
+        dev_comments,
+        string_literals,
+        attr_keys: config.attr_keys().to_vec(),
+        macro_names: config.check_macro_strings.clone(),
+    };
+    let mut docs = Documentation::new();
     // if there are no arguments, pretend to be told to check the whole project
     if paths.is_empty() {
         paths.push(cwd.clone());
@@ -447,21 +663,34 @@ pub(crate) fn extract(
 
     // stage 1 - obtain canonical paths
     let mut flow = VecDeque::<PathBuf>::with_capacity(32);
-    flow.extend(paths.into_iter().filter_map(|path_in| {
+    for path_in in paths {
         let path = if path_in.is_absolute() {
             path_in.to_owned()
         } else {
             cwd.join(&path_in)
         };
         debug!("Processing {} -> {}", path_in.display(), path.display());
-        path.canonicalize().ok()
-    }));
+        match path.canonicalize() {
+            Ok(path) => flow.push_back(path),
+            Err(_) if is_broken_symlink(&path) => {
+                warn!("Skipping broken symbolic link {}", path.display());
+                docs.add_skipped(path);
+            }
+            Err(_) => {}
+        }
+    }
 
     debug!("Running on absolute dirs {:?} ", &flow);
 
     // stage 2 - check for manifest, .rs , .md files and directories
     let mut files_to_check = Vec::with_capacity(64);
+    let mut visited = HashSet::<DirId>::new();
     while let Some(path) = flow.pop_front() {
+        if let Some(pattern) = exclude.matching(&path) {
+            debug!("Excluding {} matching {}", path.display(), pattern);
+            docs.add_excluded(path, pattern);
+            continue;
+        }
         let x = if let Ok(meta) = path.metadata() {
             if meta.is_file() {
                 match path.file_name().map(|x| x.to_str()).flatten() {
@@ -474,6 +703,15 @@ pub(crate) fn extract(
                     }
                 }
             } else if meta.is_dir() {
+                if let Some(id) = dir_id(&path, &meta) {
+                    if !visited.insert(id) {
+                        warn!(
+                            "Skipping {}, the directory was visited before, possibly due to a symbolic link cycle",
+                            path.display()
+                        );
+                        continue;
+                    }
+                }
                 let cargo_toml = to_manifest_dir(&path).unwrap().join("Cargo.toml");
                 if cargo_toml.is_file() {
                     Extraction::Manifest(cargo_toml)
@@ -481,11 +719,14 @@ pub(crate) fn extract(
                     // keep walking directories and feed the path back
                     // if recursing is wanted
                     // and if it doesn't contain a manifest file
-                    match fs::read_dir(path) {
+                    match read_dir_filtered(&path, no_ignore) {
                         Err(err) => warn!("Listing directory contents {} failed", err),
                         Ok(entries) => {
-                            for entry in entries.flatten() {
-                                let path = entry.path();
+                            for path in entries {
+                                if !config.follow_links && is_symlinked_dir(&path) {
+                                    debug!("Not following symbolic link {}", path.display());
+                                    continue;
+                                }
                                 // let's try with that path again
                                 flow.push_back(path);
                             }
@@ -493,11 +734,10 @@ pub(crate) fn extract(
                     }
                     continue;
                 } else {
-                    match fs::read_dir(path) {
+                    match read_dir_filtered(&path, no_ignore) {
                         Err(err) => warn!("Listing directory contents {} failed", err),
                         Ok(entries) => {
-                            for entry in entries.flatten() {
-                                let path = entry.path();
+                            for path in entries {
                                 // let's try attempt with that .rs file
                                 // if we end up here, recursion is off already
                                 if path.is_file() {
@@ -511,6 +751,10 @@ pub(crate) fn extract(
             } else {
                 Extraction::Missing(path)
             }
+        } else if is_broken_symlink(&path) {
+            warn!("Skipping broken symbolic link {}", path.display());
+            docs.add_skipped(path);
+            continue;
         } else {
             Extraction::Missing(path)
         };
@@ -525,8 +769,20 @@ pub(crate) fn extract(
         .try_fold::<Vec<_>, _, Result<_>>(Vec::with_capacity(64), |mut acc, tagged_path| {
             match tagged_path {
                 Extraction::Manifest(ref cargo_toml_path) => {
-                    let manifest_list = handle_manifest(cargo_toml_path, skip_readme)?;
-                    acc.extend(manifest_list);
+                    let manifest_list =
+                        handle_manifest(cargo_toml_path, skip_readme, dev_comments)?;
+                    for check_entity in manifest_list {
+                        if let Some(pattern) = exclude.matching(check_entity.as_path()) {
+                            debug!(
+                                "Excluding {} matching {}",
+                                check_entity.as_path().display(),
+                                pattern
+                            );
+                            docs.add_excluded(check_entity.as_path().to_owned(), pattern);
+                        } else {
+                            acc.push(check_entity);
+                        }
+                    }
                 }
                 Extraction::Missing(ref missing_path) => warn!(
                     "File passed as argument or listed in Cargo.toml manifest does not exist: {}",
@@ -539,51 +795,75 @@ pub(crate) fn extract(
         })?;
 
     // stage 4 - expand from the passed source files, if recursive, recurse down the module train
-    let docs =
-        files_to_check
-            .into_iter()
-            .try_fold(Documentation::new(), |mut docs, check_entity| {
-                match check_entity {
-                    CheckEntity::Source(path, recurse) => {
-                        let content: String = fs::read_to_string(&path)?;
-                        docs.add_rust(
-                            ContentOrigin::RustSourceFile(path.clone()),
-                            content.as_str(),
-                            dev_comments,
-                        )?;
-
-                        if recurse {
-                            let iter = traverse(path.as_path(), dev_comments)?
-                                .map(|documentation| {
-                                    // Filter out duplicate _chunks_
-                                    // that `extend` would happily duplicate.
-                                    documentation
-                                        .into_iter()
-                                        .filter(|(origin, _chunks)| !docs.contains_key(origin))
-                                })
-                                .flatten()
-                                .collect::<Vec<_>>();
-                            docs.extend(iter);
+    let docs = files_to_check
+        .into_iter()
+        .try_fold(docs, |mut docs, check_entity| {
+            match check_entity {
+                CheckEntity::Source(path, recurse) => {
+                    let content = match read_discovered(&mut docs, &path) {
+                        Some(content) => content,
+                        None => return Ok(docs),
+                    };
+                    docs.add_rust(
+                        ContentOrigin::RustSourceFile(path.clone()),
+                        content.as_str(),
+                        &options,
+                    )?;
+
+                    if recurse {
+                        for module in TraverseModulesIter::new(path.as_path())? {
+                            let origin = ContentOrigin::RustSourceFile(module);
+                            // Filter out duplicate _chunks_
+                            // that `extend` would happily duplicate.
+                            if docs.contains_key(&origin) {
+                                continue;
+                            }
+                            if let Some(pattern) = exclude.matching(origin.as_path()) {
+                                debug!(
+                                    "Excluding {} matching {}",
+                                    origin.as_path().display(),
+                                    pattern
+                                );
+                                docs.add_excluded(origin.as_path().to_owned(), pattern);
+                                continue;
+                            }
+                            if let Some(content) = read_discovered(&mut docs, origin.as_path()) {
+                                let documentation = Documentation::load_from_str(
+                                    origin,
+                                    content.as_str(),
+                                    &options,
+                                );
+                                if !documentation.is_empty() {
+                                    docs.extend(documentation);
+                                }
+                            }
                         }
                     }
-                    CheckEntity::Markdown(path) => {
-                        let content = fs::read_to_string(&path).wrap_err_with(|| {
-                            eyre!("Common mark / markdown file does not exist")
-                        })?;
-                        if content.is_empty() {
-                            bail!("Common mark / markdown file is empty")
-                        }
-                        docs.add_commonmark(ContentOrigin::CommonMarkFile(path), content.as_str())?;
+                }
+                CheckEntity::Markdown(path) => {
+                    let content = match read_discovered(&mut docs, &path) {
+                        Some(content) => content,
+                        None => return Ok(docs),
+                    };
+                    if content.is_empty() {
+                        bail!("Common mark / markdown file is empty")
                     }
-                    CheckEntity::ManifestDescription(path, content) => {
-                        if content.is_empty() {
-                            bail!("Cargo.toml manifest description field is empty")
-                        }
-                        docs.add_cargo_manifest_description(path, content.as_str())?;
+                    docs.add_commonmark(ContentOrigin::CommonMarkFile(path), content.as_str())?;
+                }
+                CheckEntity::ManifestDescription(path, content) => {
+                    if content.is_empty() {
+                        bail!("Cargo.toml manifest description field is empty")
                     }
+                    docs.add_cargo_manifest_description(path, content.as_str())?;
                 }
-                Ok(docs)
-            })?;
+                CheckEntity::CargoConfig(path) => {
+                    if let Some(content) = read_discovered(&mut docs, &path) {
+                        docs.add_cargo_config(path, content.as_str())?;
+                    }
+                }
+            }
+            Ok(docs)
+        })?;
 
     Ok(docs)
 }
@@ -647,6 +927,33 @@ mod tests {
         );
     }
 
+    #[test]
+    fn read_dir_respects_ignore_files() {
+        let dir = std::env::temp_dir().join(uuid::Uuid::new_v4().to_string());
+        fs::create_dir_all(dir.join("target")).unwrap();
+        fs::write(dir.join(".ignore"), "target/\ngenerated.rs\n").unwrap();
+        fs::write(dir.join("lib.rs"), "//! Docs").unwrap();
+        fs::write(dir.join("generated.rs"), "//! Generated").unwrap();
+
+        let filtered = into_hashset(read_dir_filtered(&dir, false).unwrap());
+        assert_eq!(
+            filtered,
+            maplit::hashset![dir.join(".ignore"), dir.join("lib.rs")]
+        );
+
+        let all = into_hashset(read_dir_filtered(&dir, true).unwrap());
+        assert_eq!(
+            all,
+            maplit::hashset![
+                dir.join(".ignore"),
+                dir.join("lib.rs"),
+                dir.join("generated.rs"),
+                dir.join("target"),
+            ]
+        );
+        let _ = fs::remove_dir_all(dir);
+    }
+
     fn demo_dir() -> PathBuf {
         manifest_dir().join("demo")
     }
@@ -725,7 +1032,9 @@ mod tests {
                 ],
                 $recurse,
                 false,
+                false,
                 true,
+                false,
                 &Config::default(),
             )
             .expect("Must be able to extract demo dir");
@@ -769,6 +1078,214 @@ mod tests {
         ]);
     }
 
+    #[test]
+    fn traverse_manifest_exclude() {
+        let config = Config {
+            exclude: vec!["**/src/nested/**".to_owned(), "**/README.md".to_owned()],
+            ..Config::default()
+        };
+        let docs = extract(
+            vec![demo_dir().join("Cargo.toml")],
+            true,
+            false,
+            false,
+            true,
+            false,
+            &config,
+        )
+        .expect("Must be able to extract demo dir");
+
+        let strip = |path: &Path| {
+            path.strip_prefix(demo_dir())
+                .expect("Must have common prefix")
+                .to_owned()
+        };
+        assert_hashset_eq_pretty!(
+            into_hashset(docs.iter().map(|(origin, _chunks)| strip(origin.as_path()))),
+            pathset![
+                "src/lib.rs",
+                "src/main.rs",
+                "member/true/lib.rs",
+                "member/procmacro/src/lib.rs",
+            ]
+        );
+
+        let excluded = docs
+            .excluded()
+            .iter()
+            .map(|(path, pattern)| (strip(path), pattern.as_str()))
+            .collect::<Vec<_>>();
+        assert!(excluded.contains(&(PathBuf::from("README.md"), "**/README.md")));
+        assert!(excluded.contains(&(PathBuf::from("src/nested/mod.rs"), "**/src/nested/**")));
+        assert!(excluded.contains(&(
+            PathBuf::from("src/nested/again/code.rs"),
+            "**/src/nested/**"
+        )));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn traverse_skips_broken_symlinks() {
+        let dir = std::env::temp_dir().join(uuid::Uuid::new_v4().to_string());
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("fine.rs"), "/// Fine.\nstruct Fine;\n").unwrap();
+        std::os::unix::fs::symlink(dir.join("deleted.rs"), dir.join("broken.rs")).unwrap();
+        let dir = dir.canonicalize().unwrap();
+
+        let docs = extract(
+            vec![dir.clone()],
+            true,
+            false,
+            false,
+            false,
+            false,
+            &Config::default(),
+        )
+        .expect("Broken symbolic links must not fail the extraction");
+
+        assert_eq!(
+            docs.iter()
+                .map(|(origin, _chunks)| origin.as_path().to_owned())
+                .collect::<Vec<_>>(),
+            vec![dir.join("fine.rs")]
+        );
+        assert_eq!(docs.skipped(), &[dir.join("broken.rs")]);
+        let _ = fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn traverse_skips_binary_files() {
+        let dir = std::env::temp_dir().join(uuid::Uuid::new_v4().to_string());
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("fine.rs"), "/// Fine.\nstruct Fine;\n").unwrap();
+        fs::write(dir.join("fixture.md"), b"PK\x03\x04\x00\x00binary").unwrap();
+        let dir = dir.canonicalize().unwrap();
+
+        let docs = extract(
+            vec![dir.clone()],
+            true,
+            false,
+            false,
+            false,
+            false,
+            &Config::default(),
+        )
+        .expect("Binary files must not fail the extraction");
+
+        assert_eq!(
+            docs.iter()
+                .map(|(origin, _chunks)| origin.as_path().to_owned())
+                .collect::<Vec<_>>(),
+            vec![dir.join("fine.rs")]
+        );
+        assert_eq!(docs.skipped(), &[dir.join("fixture.md")]);
+        let _ = fs::remove_dir_all(dir);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn traverse_terminates_on_symlink_cycles() {
+        let dir = std::env::temp_dir().join(uuid::Uuid::new_v4().to_string());
+        let outside = std::env::temp_dir().join(uuid::Uuid::new_v4().to_string());
+        fs::create_dir_all(dir.join("docs")).unwrap();
+        fs::create_dir_all(&outside).unwrap();
+        fs::write(dir.join("docs").join("guide.md"), "A guide.\n").unwrap();
+        fs::write(outside.join("extra.md"), "Extra docs.\n").unwrap();
+        std::os::unix::fs::symlink(&dir, dir.join("docs").join("loop")).unwrap();
+        std::os::unix::fs::symlink(&outside, dir.join("docs").join("linked")).unwrap();
+        let dir = dir.canonicalize().unwrap();
+
+        let paths = |config: &Config| {
+            let docs = extract(vec![dir.clone()], true, false, false, false, false, config)
+                .expect("Symbolic link cycles must not fail the extraction");
+            let mut paths = docs
+                .iter()
+                .map(|(origin, _chunks)| origin.as_path().to_owned())
+                .collect::<Vec<_>>();
+            paths.sort();
+            paths
+        };
+
+        assert_eq!(
+            paths(&Config::default()),
+            vec![
+                dir.join("docs").join("guide.md"),
+                dir.join("docs").join("linked").join("extra.md"),
+            ]
+        );
+
+        let config = Config {
+            follow_links: false,
+            ..Config::default()
+        };
+        assert_eq!(paths(&config), vec![dir.join("docs").join("guide.md")]);
+        let _ = fs::remove_dir_all(dir);
+        let _ = fs::remove_dir_all(outside);
+    }
+
+    #[test]
+    fn workspace_root_of_member() {
+        let root = std::env::temp_dir().join(uuid::Uuid::new_v4().to_string());
+        let member = root.join("member");
+        fs::create_dir_all(member.join("src")).unwrap();
+        fs::write(
+            root.join("Cargo.toml"),
+            "[workspace]\nmembers = [\"member\"]\n",
+        )
+        .unwrap();
+        fs::write(
+            member.join("Cargo.toml"),
+            "[package]\nname = \"member\"\nversion = \"0.1.0\"\n",
+        )
+        .unwrap();
+
+        assert_eq!(workspace_root(&member.join("src")), Some(root.clone()));
+        assert_eq!(workspace_root(&member), Some(root.clone()));
+        assert_eq!(workspace_root(&root), Some(root.clone()));
+        let _ = fs::remove_dir_all(root);
+    }
+
+    #[test]
+    fn cargo_configs_of_member() {
+        let root = std::env::temp_dir().join(uuid::Uuid::new_v4().to_string());
+        let crates = root.join("crates");
+        let member = crates.join("member");
+        for dir in [&root, &crates, &member] {
+            fs::create_dir_all(dir.join(".cargo")).unwrap();
+        }
+        fs::create_dir_all(member.join("src")).unwrap();
+        fs::write(
+            root.join("Cargo.toml"),
+            "[workspace]\nmembers = [\"crates/member\"]\n",
+        )
+        .unwrap();
+        fs::write(root.join(".cargo").join("config.toml"), "# Shared.\n").unwrap();
+        // the legacy name takes precedence, like with cargo
+        fs::write(crates.join(".cargo").join("config"), "# Legacy.\n").unwrap();
+        fs::write(crates.join(".cargo").join("config.toml"), "# Ignored.\n").unwrap();
+        fs::write(member.join(".cargo").join("config.toml"), "# Own.\n").unwrap();
+        fs::write(
+            member.join("Cargo.toml"),
+            "[package]\nname = \"member\"\nversion = \"0.1.0\"\n",
+        )
+        .unwrap();
+
+        let configs = extract_cargo_configs(&member);
+        assert!(configs.contains(&CheckEntity::CargoConfig(
+            root.join(".cargo").join("config.toml")
+        )));
+        assert!(configs.contains(&CheckEntity::CargoConfig(
+            crates.join(".cargo").join("config")
+        )));
+        assert!(!configs.contains(&CheckEntity::CargoConfig(
+            crates.join(".cargo").join("config.toml")
+        )));
+        assert!(configs.contains(&CheckEntity::CargoConfig(
+            member.join(".cargo").join("config.toml")
+        )));
+        let _ = fs::remove_dir_all(root);
+    }
+
     extract_test!(traverse_source_dir_1, ["src"] + false => [
         "src/lib.rs",
         "src/main.rs"]);
@@ -1,8 +1,9 @@
 use crate::errors::*;
 use crate::{LineColumn, Range, Span};
 use fs_err as fs;
+use once_cell::sync::Lazy;
 use std::io::Read;
-use std::path::Path;
+use std::path::{Component, Path, PathBuf};
 
 /// Iterate over a str and annotate with line and column.
 ///
@@ -46,6 +47,52 @@ pub fn iter_with_line_column<'a>(
     iter_with_line_column_from(s, LineColumn { line: 1, column: 0 })
 }
 
+/// The byte offset of the character at `position` within `s`, which starts at
+/// line 1 and column 0.
+///
+/// Returns the length of `s` if `position` is past its end.
+pub(crate) fn byte_offset_at(s: &str, position: LineColumn) -> usize {
+    iter_with_line_column(s)
+        .find(|&(_c, _byte_offset, _idx, cursor)| cursor >= position)
+        .map(|(_c, byte_offset, _idx, _cursor)| byte_offset)
+        .unwrap_or(s.len())
+}
+
+/// Resolve the `.` and `..` components of `path` lexically, without touching
+/// the file system, i.e. `src/../README.md` becomes `README.md`.
+pub(crate) fn normalize_path(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => match normalized.components().next_back() {
+                Some(Component::Normal(_)) => {
+                    normalized.pop();
+                }
+                Some(Component::RootDir) | Some(Component::Prefix(_)) => {}
+                _ => normalized.push(".."),
+            },
+            component => normalized.push(component),
+        }
+    }
+    normalized
+}
+
+/// The offset basis of the 64 bit FNV-1a hash, the hash of no bytes at all.
+pub(crate) const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+
+/// Continue the 64 bit FNV-1a `hash` with `bytes`, starting from
+/// `FNV_OFFSET_BASIS`.
+///
+/// Unlike `std::hash::Hash`, the result is stable across platforms, compiler
+/// versions and runs.
+pub(crate) fn fnv1a(hash: u64, bytes: &[u8]) -> u64 {
+    const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+    bytes.iter().fold(hash, |hash, byte| {
+        (hash ^ u64::from(*byte)).wrapping_mul(FNV_PRIME)
+    })
+}
+
 /// Extract `span` from a `Read`-able source as `String`.
 ///
 /// # Errors
@@ -59,10 +106,10 @@ where
         bail!("Lines are 1-indexed, can't be less than 1")
     }
     if span.end.line < span.start.line {
-        bail!("Line range would be negative, bail")
+        bail!("Line range of span {} would be negative, bail", span)
     }
     if span.end.line == span.start.line && span.end.column < span.start.column {
-        bail!("Column range would be negative, bail")
+        bail!("Column range of span {} would be negative, bail", span)
     }
     let mut s = String::with_capacity(256);
     source
@@ -100,6 +147,61 @@ pub(crate) fn load_span_from_file(path: impl AsRef<Path>, span: Span) -> Result<
     load_span_from(reader, span)
 }
 
+/// Strip the current working directory prefix of `path`, if there is one.
+///
+/// Paths elsewhere in the cargo workspace of the current working directory are
+/// made relative with leading `..` components, so they stay short when running
+/// from within a member crate.
+pub(crate) fn relative_to_cwd(path: &Path) -> PathBuf {
+    static WORKSPACE_ROOT: Lazy<Option<PathBuf>> = Lazy::new(|| {
+        std::env::current_dir()
+            .ok()
+            .and_then(|cwd| crate::traverse::workspace_root(&cwd))
+    });
+    std::env::current_dir()
+        .ok()
+        .and_then(|cwd| relative_within(path, &cwd, WORKSPACE_ROOT.as_deref()))
+        .unwrap_or_else(|| path.to_path_buf())
+}
+
+/// Make `path` relative to `base`, if it is within `base` or both are within
+/// `root`.
+fn relative_within(path: &Path, base: &Path, root: Option<&Path>) -> Option<PathBuf> {
+    if let Ok(relative) = path.strip_prefix(base) {
+        return Some(relative.to_path_buf());
+    }
+    let root = root?;
+    let path = path.strip_prefix(root).ok()?;
+    let base = base.strip_prefix(root).ok()?;
+    let common = path
+        .components()
+        .zip(base.components())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let mut relative = PathBuf::new();
+    for _ in base.components().skip(common) {
+        relative.push("..");
+    }
+    relative.extend(path.components().skip(common));
+    Some(relative)
+}
+
+/// Escape `s` for use in XML text and attribute values.
+pub(crate) fn xml_escape(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
 /// Extract a subset of chars by iterating. Range must be in characters.
 pub fn sub_chars(s: &str, range: Range) -> String {
     s.chars()
@@ -259,6 +361,26 @@ where
 #[cfg(test)]
 mod tests {
     use super::*;
+
+    #[test]
+    fn normalize() {
+        assert_eq!(
+            normalize_path(Path::new("src/../README.md")),
+            PathBuf::from("README.md")
+        );
+        assert_eq!(
+            normalize_path(Path::new("./crate/src/./docs/../../../x.md")),
+            PathBuf::from("x.md")
+        );
+        assert_eq!(
+            normalize_path(Path::new("../docs/../intro.md")),
+            PathBuf::from("../intro.md")
+        );
+        assert_eq!(
+            normalize_path(Path::new("/../etc/x.md")),
+            PathBuf::from("/etc/x.md")
+        );
+    }
     macro_rules! lcc {
         ($line:literal, $column:literal, $c:literal) => {
             (
@@ -405,4 +527,41 @@ Schlupfwespe,
             vec![0..0, 1..3]
         );
     }
+
+    #[test]
+    fn relative_within_workspace() {
+        let root = Path::new("/ws");
+        assert_eq!(
+            relative_within(
+                Path::new("/ws/a/src/lib.rs"),
+                Path::new("/ws/a"),
+                Some(root)
+            ),
+            Some(PathBuf::from("src/lib.rs"))
+        );
+        assert_eq!(
+            relative_within(
+                Path::new("/ws/b/src/lib.rs"),
+                Path::new("/ws/a/src"),
+                Some(root)
+            ),
+            Some(PathBuf::from("../../b/src/lib.rs"))
+        );
+        assert_eq!(
+            relative_within(Path::new("/ws/README.md"), Path::new("/ws/a"), Some(root)),
+            Some(PathBuf::from("../README.md"))
+        );
+        assert_eq!(
+            relative_within(
+                Path::new("/elsewhere/lib.rs"),
+                Path::new("/ws/a"),
+                Some(root)
+            ),
+            None
+        );
+        assert_eq!(
+            relative_within(Path::new("/ws/README.md"), Path::new("/ws/a"), None),
+            None
+        );
+    }
 }
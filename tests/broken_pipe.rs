@@ -0,0 +1,79 @@
+//! A reader closing the pipe early, i.e. `cargo spellcheck | head`, is not a
+//! failure and must not panic.
+
+use std::io::Read;
+use std::process::{Command, Stdio};
+
+const MANIFEST: &str = r#"[package]
+name = "mistakes"
+version = "0.1.0"
+edition = "2021"
+"#;
+
+/// Use the builtin dictionary only, so a user configuration does not matter.
+const CONFIG: &str = r#"[Hunspell]
+skip_os_lookups = true
+use_builtin = true
+"#;
+
+/// Whether the process was terminated by `SIGPIPE`, the default disposition
+/// on unix.
+#[cfg(unix)]
+fn killed_by_sigpipe(status: std::process::ExitStatus) -> bool {
+    use std::os::unix::process::ExitStatusExt;
+    status.signal() == Some(13)
+}
+
+#[cfg(not(unix))]
+fn killed_by_sigpipe(_status: std::process::ExitStatus) -> bool {
+    false
+}
+
+#[test]
+fn reader_closes_early() {
+    let dir = std::env::temp_dir().join(uuid::Uuid::new_v4().to_string());
+    // far more output than a pipe buffers
+    let source = (0..2000)
+        .map(|idx| format!("/// Thiss iz wrongg.\npub fn f{}() {{}}\n", idx))
+        .collect::<String>();
+    for (path, content) in [
+        ("Cargo.toml", MANIFEST),
+        (".config/spellcheck.toml", CONFIG),
+        ("src/lib.rs", source.as_str()),
+    ] {
+        let path = dir.join(path);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, content).unwrap();
+    }
+
+    let mut child = Command::new(env!("CARGO_BIN_EXE_cargo-spellcheck"))
+        .args(&[
+            "spellcheck",
+            "check",
+            "--code",
+            "1",
+            "--checkers",
+            "hunspell",
+        ])
+        .current_dir(&dir)
+        .env("NO_COLOR", "1")
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .expect("Failed to execute cargo-spellcheck");
+    let mut stdout = child.stdout.take().unwrap();
+    let mut head = [0_u8; 64];
+    stdout.read_exact(&mut head).unwrap();
+    drop(stdout);
+    let output = child.wait_with_output().unwrap();
+    let _ = std::fs::remove_dir_all(dir);
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(!stderr.contains("panicked"), "stderr: {}", stderr);
+    assert!(
+        output.status.code() == Some(0) || killed_by_sigpipe(output.status),
+        "status: {:?}, stderr: {}",
+        output.status,
+        stderr
+    );
+}
@@ -0,0 +1,80 @@
+//! Running on crates without any checkable content must succeed quietly.
+
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+const MANIFEST: &str = r#"[package]
+name = "empty"
+version = "0.1.0"
+edition = "2021"
+"#;
+
+/// Use the builtin dictionary only, so a user configuration does not matter.
+const CONFIG: &str = r#"[Hunspell]
+skip_os_lookups = true
+use_builtin = true
+"#;
+
+fn workspace(files: &[(&str, &str)]) -> PathBuf {
+    let dir = std::env::temp_dir().join(uuid::Uuid::new_v4().to_string());
+    for (path, content) in [
+        ("Cargo.toml", MANIFEST),
+        (".config/spellcheck.toml", CONFIG),
+    ]
+    .iter()
+    .chain(files)
+    {
+        let path = dir.join(path);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, content).unwrap();
+    }
+    dir
+}
+
+fn spellcheck(dir: &Path, args: &[&str]) -> std::process::Output {
+    Command::new(env!("CARGO_BIN_EXE_cargo-spellcheck"))
+        .arg("spellcheck")
+        .args(args)
+        .current_dir(dir)
+        .env("NO_COLOR", "1")
+        .output()
+        .expect("Failed to execute cargo-spellcheck")
+}
+
+fn assert_success_without_output(output: std::process::Output) {
+    assert_eq!(
+        output.status.code(),
+        Some(0),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert!(
+        output.stdout.is_empty(),
+        "stdout: {}",
+        String::from_utf8_lossy(&output.stdout)
+    );
+}
+
+#[test]
+fn manifest_only() {
+    let dir = workspace(&[]);
+    assert_success_without_output(spellcheck(&dir, &["check", "--code", "1"]));
+    let _ = std::fs::remove_dir_all(dir);
+}
+
+#[test]
+fn sources_without_comments() {
+    let dir = workspace(&[
+        (
+            "src/lib.rs",
+            "pub mod inner;\n\npub fn one() -> u8 {\n    1\n}\n",
+        ),
+        ("src/inner.rs", ""),
+        ("src/main.rs", "fn main() {}\n"),
+    ]);
+    assert_success_without_output(spellcheck(
+        &dir,
+        &["check", "--code", "1", "--dev-comments", "--recursive"],
+    ));
+    let _ = std::fs::remove_dir_all(dir);
+}